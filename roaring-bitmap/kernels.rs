@@ -0,0 +1,364 @@
+// Kernels for container set operations
+
+//! The inner loops of container set operations, in portable and SIMD forms:
+//!
+//! - Sorted array intersection: a scalar merge, or SSE4.2 `pcmpestrm` block
+//!   compares. Whichever kernel is active, an array much smaller than the
+//!   other gallops through it (exponential then binary search) instead.
+//! - Bitmap word operations: AND, OR, XOR and AND NOT over the 1024 words of
+//!   a bitmap container, fused with counting the result.
+//! - Popcount over a bitmap container's words.
+//!
+//! The fastest kernel the CPU supports is detected once at runtime.
+//! [`Kernel::force`] runs a closure with a chosen kernel on the current
+//! thread, so each path can be cross-checked against the scalar one on any
+//! machine that supports it.
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Implementation used by the container set operation kernels
+///
+/// # Examples
+///
+/// ```
+/// use roaring_bitmap::{Kernel, RoaringBitmap};
+///
+/// let a: RoaringBitmap = (0..10_000).collect();
+/// let b: RoaringBitmap = (5_000..20_000).collect();
+///
+/// for kernel in Kernel::ALL.into_iter().filter(|k| k.is_supported()) {
+///     let len = kernel.force(|| (&a & &b).len());
+///     assert_eq!(len, 5_000);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Kernel {
+    /// Portable code, available on every CPU
+    Scalar,
+    /// SSE4.2 string compares for array intersections, `popcnt` for counting
+    Sse42,
+    /// AVX2 bitmap word operations and popcount, plus the SSE4.2 array kernel
+    Avx2,
+}
+
+/// Size ratio beyond which array intersections gallop through the larger array
+const GALLOP_RATIO: usize = 32;
+
+/// Detected kernel: 0 until detection has run, otherwise its index in `ALL` + 1
+static DETECTED: AtomicU8 = AtomicU8::new(0);
+
+thread_local! {
+    /// Kernel forced on this thread by `Kernel::force`
+    static FORCED: Cell<Option<Kernel>> = const { Cell::new(None) };
+}
+
+impl Kernel {
+    /// Every kernel, from the most portable to the fastest
+    pub const ALL: [Kernel; 3] = [Kernel::Scalar, Kernel::Sse42, Kernel::Avx2];
+
+    /// Returns the fastest kernel the CPU supports (detected once)
+    pub fn detected() -> Kernel {
+        match DETECTED.load(Ordering::Relaxed) {
+            0 => {
+                let kernel = Self::ALL
+                    .into_iter()
+                    .rev()
+                    .find(|kernel| kernel.is_supported())
+                    .unwrap_or(Kernel::Scalar);
+                DETECTED.store(kernel as u8 + 1, Ordering::Relaxed);
+                kernel
+            }
+            index => Self::ALL[index as usize - 1],
+        }
+    }
+
+    /// Returns the kernel set operations on the current thread use: the forced
+    /// one inside [`force`](Self::force), otherwise the detected one
+    pub fn active() -> Kernel {
+        FORCED.with(Cell::get).unwrap_or_else(Kernel::detected)
+    }
+
+    /// Returns `true` if the CPU can run this kernel
+    pub fn is_supported(self) -> bool {
+        match self {
+            Kernel::Scalar => true,
+            #[cfg(target_arch = "x86_64")]
+            Kernel::Sse42 => {
+                is_x86_feature_detected!("sse4.2") && is_x86_feature_detected!("popcnt")
+            }
+            #[cfg(target_arch = "x86_64")]
+            Kernel::Avx2 => Kernel::Sse42.is_supported() && is_x86_feature_detected!("avx2"),
+            #[cfg(not(target_arch = "x86_64"))]
+            Kernel::Sse42 | Kernel::Avx2 => false,
+        }
+    }
+
+    /// Runs `f` with every set operation on the current thread using this
+    /// kernel, then restores the previous one. Meant for tests and benchmarks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the CPU doesn't support the kernel.
+    pub fn force<R>(self, f: impl FnOnce() -> R) -> R {
+        assert!(
+            self.is_supported(),
+            "the {self:?} kernel is not supported on this CPU"
+        );
+
+        /// Puts the previous kernel back, even if `f` panics
+        struct Restore(Option<Kernel>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                FORCED.with(|forced| forced.set(self.0));
+            }
+        }
+
+        let _restore = Restore(FORCED.with(|forced| forced.replace(Some(self))));
+        f()
+    }
+}
+
+/// Bitwise operation combining two bitmaps word by word
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum WordOp {
+    And,
+    Or,
+    Xor,
+    AndNot,
+}
+
+impl WordOp {
+    #[inline(always)]
+    pub(crate) fn apply(self, a: u64, b: u64) -> u64 {
+        match self {
+            WordOp::And => a & b,
+            WordOp::Or => a | b,
+            WordOp::Xor => a ^ b,
+            WordOp::AndNot => a & !b,
+        }
+    }
+}
+
+/// Intersects two sorted, duplicate-free arrays
+pub(crate) fn intersect_arrays(a: &[u16], b: &[u16]) -> Vec<u16> {
+    let (small, large) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    let mut out = Vec::with_capacity(small.len());
+
+    if small.len() * GALLOP_RATIO < large.len() {
+        intersect_galloping(small, large, &mut out);
+        return out;
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    if Kernel::active() != Kernel::Scalar {
+        // SAFETY: Both SIMD kernels require SSE4.2 and are only active when
+        // the CPU supports them
+        unsafe { x86::intersect_sse42(a, b, &mut out) };
+        return out;
+    }
+
+    intersect_scalar(a, b, &mut out);
+    out
+}
+
+/// Computes `op(a, b)` into `out` word by word and returns the number of set
+/// bits in the result
+pub(crate) fn combine_words(
+    op: WordOp,
+    a: &[u64; 1024],
+    b: &[u64; 1024],
+    out: &mut [u64; 1024],
+) -> u64 {
+    match Kernel::active() {
+        // SAFETY: A kernel is only active when the CPU supports it
+        #[cfg(target_arch = "x86_64")]
+        Kernel::Sse42 => unsafe { x86::combine_popcnt(op, a, b, out) },
+        #[cfg(target_arch = "x86_64")]
+        Kernel::Avx2 => unsafe { x86::combine_avx2(op, a, b, out) },
+        _ => combine_scalar(op, a, b, out),
+    }
+}
+
+/// Returns the number of set bits in a bitmap's words
+pub(crate) fn popcount(words: &[u64; 1024]) -> u64 {
+    match Kernel::active() {
+        // SAFETY: A kernel is only active when the CPU supports it
+        #[cfg(target_arch = "x86_64")]
+        Kernel::Sse42 => unsafe { x86::popcount_popcnt(words) },
+        #[cfg(target_arch = "x86_64")]
+        Kernel::Avx2 => unsafe { x86::popcount_avx2(words) },
+        _ => popcount_scalar(words),
+    }
+}
+
+/// Merges two sorted arrays, appending the common values to `out`
+fn intersect_scalar(a: &[u16], b: &[u16], out: &mut Vec<u16>) {
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Equal => {
+                out.push(a[i]);
+                i += 1;
+                j += 1;
+            }
+            std::cmp::Ordering::Greater => j += 1,
+        }
+    }
+}
+
+/// Looks up each value of `small` in `large`, doubling the step from the last
+/// match until it passes the value and then binary searching the last step,
+/// so the cost grows with `small.len() * log(large.len() / small.len())`
+fn intersect_galloping(small: &[u16], large: &[u16], out: &mut Vec<u16>) {
+    let mut base = 0;
+    for &value in small {
+        let rest = &large[base..];
+        if rest.is_empty() {
+            break;
+        }
+
+        let mut bound = 1;
+        while bound < rest.len() && rest[bound] < value {
+            bound *= 2;
+        }
+
+        let low = bound / 2;
+        match rest[low..(bound + 1).min(rest.len())].binary_search(&value) {
+            Ok(index) => {
+                out.push(value);
+                base += low + index + 1;
+            }
+            Err(index) => base += low + index,
+        }
+    }
+}
+
+#[inline(always)]
+fn combine_scalar(op: WordOp, a: &[u64; 1024], b: &[u64; 1024], out: &mut [u64; 1024]) -> u64 {
+    let mut count = 0;
+    for ((out, &a), &b) in out.iter_mut().zip(a.iter()).zip(b.iter()) {
+        *out = op.apply(a, b);
+        count += out.count_ones() as u64;
+    }
+    count
+}
+
+#[inline(always)]
+fn popcount_scalar(words: &[u64; 1024]) -> u64 {
+    words.iter().map(|word| word.count_ones() as u64).sum()
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use super::WordOp;
+    use std::arch::x86_64::*;
+
+    /// Intersects sorted arrays eight values at a time: `pcmpestrm` marks the
+    /// values of a block of `a` found anywhere in a block of `b`, and the
+    /// block with the smaller last value is replaced next
+    #[target_feature(enable = "sse4.2")]
+    pub(super) unsafe fn intersect_sse42(a: &[u16], b: &[u16], out: &mut Vec<u16>) {
+        const MODE: i32 = _SIDD_UWORD_OPS | _SIDD_CMP_EQUAL_ANY | _SIDD_BIT_MASK;
+
+        let (mut i, mut j) = (0, 0);
+        while i + 8 <= a.len() && j + 8 <= b.len() {
+            let block_a = _mm_loadu_si128(a.as_ptr().add(i).cast());
+            let block_b = _mm_loadu_si128(b.as_ptr().add(j).cast());
+            let mut found = _mm_extract_epi32::<0>(_mm_cmpestrm::<MODE>(block_b, 8, block_a, 8));
+            while found != 0 {
+                out.push(a[i + found.trailing_zeros() as usize]);
+                found &= found - 1;
+            }
+
+            let (last_a, last_b) = (a[i + 7], b[j + 7]);
+            if last_a <= last_b {
+                i += 8;
+            }
+            if last_b <= last_a {
+                j += 8;
+            }
+        }
+
+        // Values already matched in a partly consumed block can't match the
+        // other side's unseen values, so the tails merge without duplicates
+        super::intersect_scalar(&a[i..], &b[j..], out);
+    }
+
+    #[target_feature(enable = "popcnt")]
+    pub(super) unsafe fn combine_popcnt(
+        op: WordOp,
+        a: &[u64; 1024],
+        b: &[u64; 1024],
+        out: &mut [u64; 1024],
+    ) -> u64 {
+        super::combine_scalar(op, a, b, out)
+    }
+
+    #[target_feature(enable = "popcnt")]
+    pub(super) unsafe fn popcount_popcnt(words: &[u64; 1024]) -> u64 {
+        super::popcount_scalar(words)
+    }
+
+    /// Combines four words per instruction and counts the result with the
+    /// nibble-lookup popcount, accumulating per-lane totals
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn combine_avx2(
+        op: WordOp,
+        a: &[u64; 1024],
+        b: &[u64; 1024],
+        out: &mut [u64; 1024],
+    ) -> u64 {
+        let mut totals = _mm256_setzero_si256();
+        for index in (0..1024).step_by(4) {
+            let left = _mm256_loadu_si256(a.as_ptr().add(index).cast());
+            let right = _mm256_loadu_si256(b.as_ptr().add(index).cast());
+            let result = match op {
+                WordOp::And => _mm256_and_si256(left, right),
+                WordOp::Or => _mm256_or_si256(left, right),
+                WordOp::Xor => _mm256_xor_si256(left, right),
+                WordOp::AndNot => _mm256_andnot_si256(right, left),
+            };
+            _mm256_storeu_si256(out.as_mut_ptr().add(index).cast(), result);
+            totals = _mm256_add_epi64(totals, popcount_lanes(result));
+        }
+        sum_lanes(totals)
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn popcount_avx2(words: &[u64; 1024]) -> u64 {
+        let mut totals = _mm256_setzero_si256();
+        for index in (0..1024).step_by(4) {
+            let chunk = _mm256_loadu_si256(words.as_ptr().add(index).cast());
+            totals = _mm256_add_epi64(totals, popcount_lanes(chunk));
+        }
+        sum_lanes(totals)
+    }
+
+    /// Counts the set bits of each 64-bit lane: every nibble indexes a
+    /// 16-entry table of bit counts, and the byte counts are summed per lane
+    #[target_feature(enable = "avx2")]
+    unsafe fn popcount_lanes(v: __m256i) -> __m256i {
+        let table = _mm256_setr_epi8(
+            0, 1, 1, 2, 1, 2, 2, 3, 1, 2, 2, 3, 2, 3, 3, 4, //
+            0, 1, 1, 2, 1, 2, 2, 3, 1, 2, 2, 3, 2, 3, 3, 4,
+        );
+        let low_nibbles = _mm256_set1_epi8(0x0f);
+        let low = _mm256_and_si256(v, low_nibbles);
+        let high = _mm256_and_si256(_mm256_srli_epi16::<4>(v), low_nibbles);
+        let counts = _mm256_add_epi8(
+            _mm256_shuffle_epi8(table, low),
+            _mm256_shuffle_epi8(table, high),
+        );
+        _mm256_sad_epu8(counts, _mm256_setzero_si256())
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn sum_lanes(v: __m256i) -> u64 {
+        let mut lanes = [0u64; 4];
+        _mm256_storeu_si256(lanes.as_mut_ptr().cast(), v);
+        lanes.iter().sum()
+    }
+}
@@ -8,8 +8,21 @@
 //!
 //! This allows efficient storage of sparse and dense integer sets.
 
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io::{self, Read, Write};
+use std::iter::FusedIterator;
 use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Sub, SubAssign};
 
+mod kernels;
+mod treemap;
+mod view;
+
+pub use kernels::Kernel;
+use kernels::WordOp;
+pub use treemap::{RoaringTreemap, TreemapIntoIter, TreemapIter, TreemapMemoryUsage};
+pub use view::{RoaringBitmapView, ViewIter};
+
 /// Main Roaring Bitmap structure
 #[derive(Clone)]
 pub struct RoaringBitmap {
@@ -69,6 +82,24 @@ enum Container {
 /// Threshold for converting between array and bitmap containers
 const ARRAY_TO_BITMAP_THRESHOLD: usize = 4096;
 
+/// Number of values printed by the `Debug` implementation before truncating
+const DEBUG_MAX_VALUES: usize = 16;
+
+/// Cookie identifying the portable format when no Run containers are present
+const SERIAL_COOKIE_NO_RUNCONTAINER: u32 = 12346;
+
+/// Cookie identifying the portable format when Run containers are present
+/// (stored in the low 16 bits; the high 16 bits hold `container_count - 1`)
+const SERIAL_COOKIE: u16 = 12347;
+
+/// With Run containers present, the offset header is only written for
+/// bitmaps with at least this many containers
+const NO_OFFSET_THRESHOLD: usize = 4;
+
+/// Largest cardinality stored with the Array layout in the portable format.
+/// Containers above this are stored as 8KB bitmaps.
+const SERIAL_MAX_ARRAY_CARDINALITY: u64 = 4096;
+
 /// Array container: stores values as a sorted Vec<u16>
 #[derive(Clone)]
 struct ArrayContainer {
@@ -128,21 +159,90 @@ impl Default for RoaringBitmap {
 }
 
 /// Iterator over elements in a RoaringBitmap
+///
+/// Yields values in ascending order from the front and descending order from
+/// the back. Both ends may be used on the same iterator, and `advance_to` can
+/// skip ahead from the front without yielding the values in between.
 pub struct Iter<'a> {
-    /// Reference to the bitmap being iterated
-    bitmap: &'a RoaringBitmap,
-    /// Index of current container
-    container_index: usize,
-    /// Index within current container (for array containers, or run index for run containers)
-    value_index: usize,
-    /// For bitmap containers: current word index
-    bitmap_word_index: usize,
-    /// For bitmap containers: current word being scanned
-    bitmap_current_word: u64,
-    /// For bitmap containers: bit position within current word
-    bitmap_bit_position: u8,
-    /// For run containers: offset within current run
-    run_offset: u16,
+    /// Containers not yet entered from either end
+    containers: std::slice::Iter<'a, (u16, Container)>,
+    /// Key and cursor of the container being iterated from the front
+    front: Option<(u16, ContainerIter<'a>)>,
+    /// Key and cursor of the container being iterated from the back
+    back: Option<(u16, ContainerIter<'a>)>,
+    /// Number of values not yet yielded from either end
+    remaining: u64,
+}
+
+/// Owning iterator over elements in a RoaringBitmap
+///
+/// Created by `RoaringBitmap::into_iter`. Consumes the bitmap's containers one
+/// at a time and, like `Iter`, can be driven from both ends.
+pub struct IntoIter {
+    /// Containers not yet entered from either end
+    containers: std::vec::IntoIter<(u16, Container)>,
+    /// Key and cursor of the container being iterated from the front
+    front: Option<(u16, ContainerIntoIter)>,
+    /// Key and cursor of the container being iterated from the back
+    back: Option<(u16, ContainerIntoIter)>,
+    /// Number of values not yet yielded from either end
+    remaining: u64,
+}
+
+/// Double-ended cursor over the low 16-bit values of a single container
+enum ContainerIter<'a> {
+    Array {
+        values: std::slice::Iter<'a, u16>,
+    },
+    Bitmap {
+        bits: &'a [u64; 1024],
+        cursor: BitmapCursor,
+    },
+    Run {
+        runs: &'a [(u16, u16)],
+        cursor: RunCursor,
+    },
+}
+
+/// Owning counterpart of `ContainerIter`
+enum ContainerIntoIter {
+    Array {
+        values: std::vec::IntoIter<u16>,
+    },
+    Bitmap {
+        bits: Box<[u64; 1024]>,
+        cursor: BitmapCursor,
+    },
+    Run {
+        runs: Vec<(u16, u16)>,
+        cursor: RunCursor,
+    },
+}
+
+/// Front and back positions within the words of a bitmap container
+///
+/// Counts the values left between the two ends, so the ends never cross even
+/// when they share a word.
+struct BitmapCursor {
+    front_index: usize,
+    /// Bits of word `front_index` not yet yielded from the front
+    front_word: u64,
+    back_index: usize,
+    /// Bits of word `back_index` not yet yielded from the back
+    back_word: u64,
+    len: u64,
+}
+
+/// Front and back positions within the runs of a run container
+///
+/// Counts the values left between the two ends, so the ends never cross even
+/// when they share a run.
+struct RunCursor {
+    front_run: usize,
+    front_offset: u16,
+    back_run: usize,
+    back_offset: u16,
+    len: u64,
 }
 
 impl RoaringBitmap {
@@ -162,6 +262,64 @@ impl RoaringBitmap {
         ((key as u32) << 16) | (low as u32)
     }
 
+    /// Resolves range bounds into an inclusive `(start, end)` pair, or `None`
+    /// if the range is empty
+    fn resolve_range<R: std::ops::RangeBounds<u32>>(range: &R) -> Option<(u32, u32)> {
+        use std::ops::Bound::*;
+
+        let start = match range.start_bound() {
+            Included(&s) => s,
+            Excluded(&s) => s.checked_add(1)?,
+            Unbounded => 0,
+        };
+
+        let end = match range.end_bound() {
+            Included(&e) => e,
+            Excluded(&e) => e.checked_sub(1)?,
+            Unbounded => u32::MAX,
+        };
+
+        (start <= end).then_some((start, end))
+    }
+
+    /// Splits an inclusive range into `(key, start, end)` slices, one per
+    /// container key it covers
+    fn key_ranges(start: u32, end: u32) -> impl Iterator<Item = (u16, u16, u16)> {
+        let (start_key, start_low) = Self::split(start);
+        let (end_key, end_low) = Self::split(end);
+
+        (start_key..=end_key).map(move |key| {
+            let low = if key == start_key { start_low } else { 0 };
+            let high = if key == end_key { end_low } else { u16::MAX };
+            (key, low, high)
+        })
+    }
+
+    /// Yields the pairs of containers stored under the same key in both bitmaps
+    fn shared_containers<'a>(
+        &'a self,
+        other: &'a RoaringBitmap,
+    ) -> impl Iterator<Item = (&'a Container, &'a Container)> {
+        let (mut i, mut j) = (0, 0);
+        std::iter::from_fn(move || {
+            while i < self.containers.len() && j < other.containers.len() {
+                let (key_a, container_a) = &self.containers[i];
+                let (key_b, container_b) = &other.containers[j];
+
+                match key_a.cmp(key_b) {
+                    std::cmp::Ordering::Less => i += 1,
+                    std::cmp::Ordering::Greater => j += 1,
+                    std::cmp::Ordering::Equal => {
+                        i += 1;
+                        j += 1;
+                        return Some((container_a, container_b));
+                    }
+                }
+            }
+            None
+        })
+    }
+
     // Construction
 
     /// Creates an empty roaring bitmap
@@ -289,6 +447,318 @@ impl RoaringBitmap {
         self.containers.is_empty()
     }
 
+    /// Returns the number of elements less than or equal to `value`
+    ///
+    /// # Performance
+    ///
+    /// - **Time**: O(n) over the n containers before `value`, each adding its
+    ///   length: O(1) for Array and Bitmap containers, O(runs) for Run
+    ///   containers, which do not cache their cardinality. Only the container
+    ///   holding `value` is searched (binary search for Array, word popcounts
+    ///   for Bitmap, O(runs) for Run)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let mut bm = RoaringBitmap::new();
+    /// bm.extend_sparse([10, 20, 30, 100_000]);
+    ///
+    /// assert_eq!(bm.rank(5), 0);
+    /// assert_eq!(bm.rank(10), 1);
+    /// assert_eq!(bm.rank(25), 2);
+    /// assert_eq!(bm.rank(u32::MAX), 4);
+    /// ```
+    pub fn rank(&self, value: u32) -> u64 {
+        let (key, low) = Self::split(value);
+
+        // Containers entirely below `value` count in full
+        let (below, partial) = match self.containers.binary_search_by_key(&key, |(k, _)| *k) {
+            Ok(index) => (index, self.containers[index].1.rank(low)),
+            Err(index) => (index, 0),
+        };
+
+        self.containers[..below]
+            .iter()
+            .map(|(_, container)| container.len())
+            .sum::<u64>()
+            + partial
+    }
+
+    /// Returns the `k`-th smallest element (0-based), or `None` if `k >= len()`
+    ///
+    /// `select` is the inverse of `rank`: for every element `x`,
+    /// `select(rank(x) - 1) == Some(x)`.
+    ///
+    /// # Performance
+    ///
+    /// - **Time**: O(n) where n = number of containers skipped, with each Run
+    ///   container costing O(runs) to measure, plus an in-container lookup
+    ///   (O(1) for Array, O(1024) word scan for Bitmap, O(runs) for Run)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let mut bm = RoaringBitmap::new();
+    /// bm.extend_sparse([10, 20, 30, 100_000]);
+    ///
+    /// assert_eq!(bm.select(0), Some(10));
+    /// assert_eq!(bm.select(3), Some(100_000));
+    /// assert_eq!(bm.select(4), None);
+    /// ```
+    pub fn select(&self, k: u64) -> Option<u32> {
+        let mut remaining = k;
+
+        for (key, container) in &self.containers {
+            let len = container.len();
+            if remaining < len {
+                return Some(Self::combine(*key, container.select(remaining as u16)));
+            }
+            remaining -= len;
+        }
+
+        None
+    }
+
+    /// Returns the number of elements within `range`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let mut bm = RoaringBitmap::new();
+    /// bm.extend_consecutive(0..200_000);
+    ///
+    /// assert_eq!(bm.range_cardinality(1000..2000), 1000);
+    /// assert_eq!(bm.range_cardinality(150_000..), 50_000);
+    /// assert_eq!(bm.range_cardinality(..=9), 10);
+    /// ```
+    ///
+    /// # Performance
+    ///
+    /// Binary searches the container keys for the range ends. Only the first
+    /// and last containers are clipped (via `rank`); containers fully inside the
+    /// range contribute their cached lengths.
+    pub fn range_cardinality<R: std::ops::RangeBounds<u32>>(&self, range: R) -> u64 {
+        let Some((start, end)) = Self::resolve_range(&range) else {
+            return 0;
+        };
+        let (start_key, start_low) = Self::split(start);
+        let (end_key, end_low) = Self::split(end);
+
+        let first = self.containers.partition_point(|(k, _)| *k < start_key);
+        let last = self.containers.partition_point(|(k, _)| *k <= end_key);
+
+        self.containers[first..last]
+            .iter()
+            .map(|(key, container)| {
+                if *key != start_key && *key != end_key {
+                    return container.len();
+                }
+
+                let high = if *key == end_key { end_low } else { u16::MAX };
+                let below = match (*key == start_key).then_some(start_low) {
+                    Some(low) if low > 0 => container.rank(low - 1),
+                    _ => 0,
+                };
+                container.rank(high) - below
+            })
+            .sum()
+    }
+
+    /// Returns `true` if every value in `range` is in the bitmap
+    ///
+    /// An empty range is trivially contained.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let mut bm = RoaringBitmap::new();
+    /// bm.extend_consecutive(100..200_000);
+    /// bm.remove(150_000);
+    ///
+    /// assert!(bm.contains_range(100..150_000));
+    /// assert!(!bm.contains_range(100..=150_000));
+    /// assert!(!bm.contains_range(99..1000));
+    /// assert!(bm.contains_range(5..5));
+    /// ```
+    ///
+    /// # Performance
+    ///
+    /// Every key in the range must have a container, so the walk stops at the
+    /// first missing key. Inside a container the check is a binary search for
+    /// Array and Run, and a masked comparison of the covered words for Bitmap.
+    pub fn contains_range<R: std::ops::RangeBounds<u32>>(&self, range: R) -> bool {
+        let Some((start, end)) = Self::resolve_range(&range) else {
+            return true;
+        };
+        let (start_key, _) = Self::split(start);
+
+        let first = self.containers.partition_point(|(k, _)| *k < start_key);
+        let mut containers = self.containers[first..].iter();
+
+        Self::key_ranges(start, end).all(|(key, low, high)| {
+            matches!(
+                containers.next(),
+                Some((k, container)) if *k == key && container.contains_range(low, high)
+            )
+        })
+    }
+
+    /// Returns `true` if at least one value in `range` is in the bitmap
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let mut bm = RoaringBitmap::new();
+    /// bm.extend_sparse([10, 100_000]);
+    ///
+    /// assert!(bm.intersects_range(0..=10));
+    /// assert!(!bm.intersects_range(11..100_000));
+    /// assert!(bm.intersects_range(11..));
+    /// ```
+    ///
+    /// # Performance
+    ///
+    /// A single `next_set_at_or_after` lookup from the start of the range.
+    pub fn intersects_range<R: std::ops::RangeBounds<u32>>(&self, range: R) -> bool {
+        Self::resolve_range(&range).is_some_and(|(start, end)| {
+            self.next_set_at_or_after(start)
+                .is_some_and(|value| value <= end)
+        })
+    }
+
+    // Ordered Navigation
+
+    /// Returns the smallest element, or `None` if the bitmap is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let mut bm = RoaringBitmap::new();
+    /// assert_eq!(bm.min(), None);
+    /// bm.extend_sparse([42, 7, 100_000]);
+    /// assert_eq!(bm.min(), Some(7));
+    /// ```
+    pub fn min(&self) -> Option<u32> {
+        self.containers
+            .first()
+            .map(|(key, container)| Self::combine(*key, container.min()))
+    }
+
+    /// Returns the largest element, or `None` if the bitmap is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let mut bm = RoaringBitmap::new();
+    /// assert_eq!(bm.max(), None);
+    /// bm.extend_sparse([42, 7, 100_000]);
+    /// assert_eq!(bm.max(), Some(100_000));
+    /// ```
+    pub fn max(&self) -> Option<u32> {
+        self.containers
+            .last()
+            .map(|(key, container)| Self::combine(*key, container.max()))
+    }
+
+    /// Returns the smallest element greater than or equal to `value`
+    ///
+    /// # Performance
+    ///
+    /// - **Time**: O(log n) binary search over container keys, then an
+    ///   in-container lookup (binary search for Array and Run, word scan with
+    ///   `trailing_zeros` for Bitmap)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let mut bm = RoaringBitmap::new();
+    /// bm.extend_sparse([10, 20, 100_000]);
+    ///
+    /// assert_eq!(bm.next_set_at_or_after(10), Some(10));
+    /// assert_eq!(bm.next_set_at_or_after(11), Some(20));
+    /// assert_eq!(bm.next_set_at_or_after(21), Some(100_000));
+    /// assert_eq!(bm.next_set_at_or_after(100_001), None);
+    /// ```
+    ///
+    /// # Use Cases
+    ///
+    /// - **Pagination**: Resume from the last id of the previous page
+    /// - **Skipping**: Jump over gaps while merging sorted id lists
+    pub fn next_set_at_or_after(&self, value: u32) -> Option<u32> {
+        let (key, low) = Self::split(value);
+
+        let next_index = match self.containers.binary_search_by_key(&key, |(k, _)| *k) {
+            Ok(index) => {
+                if let Some(found) = self.containers[index].1.next_at_or_after(low) {
+                    return Some(Self::combine(key, found));
+                }
+                index + 1
+            }
+            Err(index) => index,
+        };
+
+        // Every value in a later container is greater than `value`
+        self.containers
+            .get(next_index)
+            .map(|(k, container)| Self::combine(*k, container.min()))
+    }
+
+    /// Returns the largest element less than or equal to `value`
+    ///
+    /// # Performance
+    ///
+    /// - **Time**: O(log n) binary search over container keys, then an
+    ///   in-container lookup (binary search for Array and Run, word scan with
+    ///   `leading_zeros` for Bitmap)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let mut bm = RoaringBitmap::new();
+    /// bm.extend_sparse([10, 20, 100_000]);
+    ///
+    /// assert_eq!(bm.prev_set_at_or_before(9), None);
+    /// assert_eq!(bm.prev_set_at_or_before(10), Some(10));
+    /// assert_eq!(bm.prev_set_at_or_before(99_999), Some(20));
+    /// assert_eq!(bm.prev_set_at_or_before(u32::MAX), Some(100_000));
+    /// ```
+    pub fn prev_set_at_or_before(&self, value: u32) -> Option<u32> {
+        let (key, low) = Self::split(value);
+
+        let prev_end = match self.containers.binary_search_by_key(&key, |(k, _)| *k) {
+            Ok(index) => {
+                if let Some(found) = self.containers[index].1.prev_at_or_before(low) {
+                    return Some(Self::combine(key, found));
+                }
+                index
+            }
+            Err(index) => index,
+        };
+
+        // Every value in an earlier container is less than `value`
+        self.containers[..prev_end]
+            .last()
+            .map(|(k, container)| Self::combine(*k, container.max()))
+    }
+
     // Deletion
 
     /// Removes a single element from the bitmap, returns `true` if the element was present
@@ -747,50 +1217,205 @@ impl RoaringBitmap {
         }
     }
 
-    // In-place Set Operations
+    // Set Predicates and Cardinalities
+    //
+    // These answer set questions without materializing a result bitmap:
+    // matching containers are compared pairwise and no containers are allocated.
 
-    /// Computes the union in-place, modifying this bitmap to include all elements from `other`.
+    /// Returns true if every value of this bitmap is also in `other`
     ///
-    /// This is an in-place operation that avoids allocating a new bitmap. It's more efficient
-    /// than `self = self.union(other)` when you want to modify an existing bitmap.
+    /// Stops at the first container of `self` that is missing from `other` or
+    /// not contained in its counterpart.
     ///
-    /// # Performance
+    /// # Examples
     ///
-    /// - **Time**: O(n + m) where n, m = number of containers in each bitmap
-    /// - **Space**: No new bitmap allocation (modifies in-place)
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let small: RoaringBitmap = [1, 2].into_iter().collect();
+    /// let large: RoaringBitmap = [1, 2, 3].into_iter().collect();
+    /// assert!(small.is_subset(&large));
+    /// assert!(!large.is_subset(&small));
+    /// assert!(RoaringBitmap::new().is_subset(&small));
+    /// ```
+    pub fn is_subset(&self, other: &RoaringBitmap) -> bool {
+        if self.containers.len() > other.containers.len() {
+            return false;
+        }
+
+        let mut j = 0;
+        for (key, container) in &self.containers {
+            while j < other.containers.len() && other.containers[j].0 < *key {
+                j += 1;
+            }
+            match other.containers.get(j) {
+                Some((other_key, other_container)) if other_key == key => {
+                    if !container.is_subset(other_container) {
+                        return false;
+                    }
+                    j += 1;
+                }
+                _ => return false,
+            }
+        }
+
+        true
+    }
+
+    /// Returns true if every value of `other` is also in this bitmap
     ///
     /// # Examples
     ///
     /// ```
     /// use roaring_bitmap::RoaringBitmap;
     ///
-    /// let mut a = RoaringBitmap::new();
-    /// let mut b = RoaringBitmap::new();
-    /// a.insert(1);
-    /// a.insert(2);
-    /// b.insert(2);
-    /// b.insert(3);
-    ///
-    /// a.union_with(&b);  // Modifies 'a' in-place
-    /// assert_eq!(a.len(), 3);
-    /// assert!(a.contains(1));
-    /// assert!(a.contains(2));
-    /// assert!(a.contains(3));
+    /// let large: RoaringBitmap = (0..100).collect();
+    /// let small: RoaringBitmap = [10, 20, 30].into_iter().collect();
+    /// assert!(large.is_superset(&small));
+    /// assert!(!small.is_superset(&large));
     /// ```
+    pub fn is_superset(&self, other: &RoaringBitmap) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Returns true if the bitmaps have no values in common
     ///
-    /// # Common Workflows
+    /// Stops at the first shared value.
+    ///
+    /// # Examples
     ///
-    /// **Building up a union incrementally:**
     /// ```
     /// use roaring_bitmap::RoaringBitmap;
     ///
-    /// let mut result = RoaringBitmap::new();
-    /// # let bitmaps = vec![RoaringBitmap::new(); 3];
-    /// for bitmap in &bitmaps {
-    ///     result.union_with(bitmap);  // Zero intermediate allocations
-    /// }
+    /// let a: RoaringBitmap = [1, 2].into_iter().collect();
+    /// let b: RoaringBitmap = [3, 4].into_iter().collect();
+    /// let c: RoaringBitmap = [2, 5].into_iter().collect();
+    /// assert!(a.is_disjoint(&b));
+    /// assert!(!a.is_disjoint(&c));
     /// ```
-    pub fn union_with(&mut self, other: &RoaringBitmap) {
+    pub fn is_disjoint(&self, other: &RoaringBitmap) -> bool {
+        !self.shared_containers(other).any(|(a, b)| a.intersects(b))
+    }
+
+    /// Returns the number of values in both bitmaps, without building the
+    /// intersection
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let a: RoaringBitmap = (0..10).collect();
+    /// let b: RoaringBitmap = (5..20).collect();
+    /// assert_eq!(a.intersection_len(&b), 5);
+    /// assert_eq!(a.intersection_len(&b), a.intersection(&b).len());
+    /// ```
+    pub fn intersection_len(&self, other: &RoaringBitmap) -> u64 {
+        self.shared_containers(other)
+            .map(|(a, b)| a.intersection_len(b))
+            .sum()
+    }
+
+    /// Returns the number of values in either bitmap, without building the union
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let a: RoaringBitmap = (0..10).collect();
+    /// let b: RoaringBitmap = (5..20).collect();
+    /// assert_eq!(a.union_len(&b), 20);
+    /// ```
+    pub fn union_len(&self, other: &RoaringBitmap) -> u64 {
+        self.len() + other.len() - self.intersection_len(other)
+    }
+
+    /// Returns the number of values in this bitmap but not in `other`, without
+    /// building the difference
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let a: RoaringBitmap = (0..10).collect();
+    /// let b: RoaringBitmap = (5..20).collect();
+    /// assert_eq!(a.difference_len(&b), 5);
+    /// assert_eq!(b.difference_len(&a), 10);
+    /// ```
+    pub fn difference_len(&self, other: &RoaringBitmap) -> u64 {
+        self.len() - self.intersection_len(other)
+    }
+
+    /// Returns the Jaccard similarity `|A ∩ B| / |A ∪ B|`, between 0.0 and 1.0
+    ///
+    /// Two empty bitmaps are identical sets, so their index is 1.0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let a: RoaringBitmap = (0..10).collect();
+    /// let b: RoaringBitmap = (5..15).collect();
+    /// assert_eq!(a.jaccard_index(&b), 5.0 / 15.0);
+    /// assert_eq!(RoaringBitmap::new().jaccard_index(&RoaringBitmap::new()), 1.0);
+    /// ```
+    pub fn jaccard_index(&self, other: &RoaringBitmap) -> f64 {
+        let intersection = self.intersection_len(other);
+        let union = self.len() + other.len() - intersection;
+        if union == 0 {
+            1.0
+        } else {
+            intersection as f64 / union as f64
+        }
+    }
+
+    // In-place Set Operations
+
+    /// Computes the union in-place, modifying this bitmap to include all elements from `other`.
+    ///
+    /// This is an in-place operation that avoids allocating a new bitmap. It's more efficient
+    /// than `self = self.union(other)` when you want to modify an existing bitmap.
+    ///
+    /// # Performance
+    ///
+    /// - **Time**: O(n + m) where n, m = number of containers in each bitmap
+    /// - **Space**: No new bitmap allocation (modifies in-place)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let mut a = RoaringBitmap::new();
+    /// let mut b = RoaringBitmap::new();
+    /// a.insert(1);
+    /// a.insert(2);
+    /// b.insert(2);
+    /// b.insert(3);
+    ///
+    /// a.union_with(&b);  // Modifies 'a' in-place
+    /// assert_eq!(a.len(), 3);
+    /// assert!(a.contains(1));
+    /// assert!(a.contains(2));
+    /// assert!(a.contains(3));
+    /// ```
+    ///
+    /// # Common Workflows
+    ///
+    /// **Building up a union incrementally:**
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let mut result = RoaringBitmap::new();
+    /// # let bitmaps = vec![RoaringBitmap::new(); 3];
+    /// for bitmap in &bitmaps {
+    ///     result.union_with(bitmap);  // Zero intermediate allocations
+    /// }
+    /// ```
+    pub fn union_with(&mut self, other: &RoaringBitmap) {
         let mut result_containers = Vec::new();
         let mut i = 0;
         let mut j = 0;
@@ -1059,6 +1684,366 @@ impl RoaringBitmap {
         self.containers = result_containers;
     }
 
+    // Multi-way Set Operations
+
+    /// Computes the union of any number of bitmaps in one pass.
+    ///
+    /// Containers are grouped by key across all inputs and each group is
+    /// accumulated into a single scratch bitmap, with cardinality counted once
+    /// per key instead of after every pairwise merge. Keys held by only one
+    /// input are copied as-is.
+    ///
+    /// # Performance
+    ///
+    /// - **Time**: O(c log c) to group the c input containers, plus one word
+    ///   pass per contributing container
+    /// - **Space**: One scratch bitmap (8KB) reused across keys
+    ///
+    /// Prefer this over folding `union_with` when combining more than a
+    /// handful of bitmaps.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let tags: Vec<RoaringBitmap> = (0..3u32)
+    ///     .map(|tag| (0..4).map(|i| i * 10 + tag).collect())
+    ///     .collect();
+    ///
+    /// let any = RoaringBitmap::union_many(&tags);
+    /// assert_eq!(any.len(), 12);
+    /// assert!(any.contains(31));
+    /// ```
+    pub fn union_many<'a, I>(bitmaps: I) -> RoaringBitmap
+    where
+        I: IntoIterator<Item = &'a RoaringBitmap>,
+    {
+        let mut scratch = BitmapContainer::new();
+        let containers = Self::grouped_containers(bitmaps)
+            .chunk_by(|(a, _), (b, _)| a == b)
+            .map(|group| {
+                let key = group[0].0;
+                let container = match group {
+                    [(_, only)] => (*only).clone(),
+                    _ => {
+                        for (_, container) in group {
+                            scratch.union_lazy(container);
+                        }
+                        Container::from_scratch(&mut scratch)
+                            .expect("union of non-empty containers is non-empty")
+                    }
+                };
+                (key, container)
+            })
+            .collect();
+
+        RoaringBitmap { containers }
+    }
+
+    /// Computes the intersection of any number of bitmaps in one pass.
+    ///
+    /// Only the keys of the bitmap with the fewest containers are visited, and
+    /// a key is abandoned as soon as one input lacks it or the running result
+    /// becomes empty. Within a key, containers are intersected smallest-first,
+    /// so an array container bounds the work to a membership check per value.
+    ///
+    /// An empty iterator yields an empty bitmap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let evens: RoaringBitmap = (0..100).step_by(2).collect();
+    /// let threes: RoaringBitmap = (0..100).step_by(3).collect();
+    /// let small: RoaringBitmap = (0..50).collect();
+    ///
+    /// let all = RoaringBitmap::intersection_many([&evens, &threes, &small]);
+    /// assert_eq!(all.iter().collect::<Vec<_>>(), vec![0, 6, 12, 18, 24, 30, 36, 42, 48]);
+    /// ```
+    pub fn intersection_many<'a, I>(bitmaps: I) -> RoaringBitmap
+    where
+        I: IntoIterator<Item = &'a RoaringBitmap>,
+    {
+        let mut bitmaps: Vec<&RoaringBitmap> = bitmaps.into_iter().collect();
+        bitmaps.sort_by_key(|bitmap| bitmap.containers.len());
+        let Some((smallest, rest)) = bitmaps.split_first() else {
+            return RoaringBitmap::new();
+        };
+
+        let mut scratch = BitmapContainer::new();
+        let mut group: Vec<&Container> = Vec::with_capacity(bitmaps.len());
+        let mut containers = Vec::new();
+
+        'keys: for (key, container) in &smallest.containers {
+            group.clear();
+            group.push(container);
+            for other in rest {
+                match other.containers.binary_search_by_key(key, |(k, _)| *k) {
+                    Ok(index) => group.push(&other.containers[index].1),
+                    Err(_) => continue 'keys,
+                }
+            }
+
+            group.sort_by_key(|container| container.len());
+            if let Some(result) = Container::intersection_group(&group, &mut scratch) {
+                containers.push((*key, result));
+            }
+        }
+
+        RoaringBitmap { containers }
+    }
+
+    /// Computes the symmetric difference of any number of bitmaps in one pass.
+    ///
+    /// The result holds every value present in an odd number of inputs.
+    /// Containers are grouped by key and folded into a scratch bitmap like
+    /// [`union_many`](Self::union_many); keys whose values all cancel out are
+    /// dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let a: RoaringBitmap = [1, 2, 3].into_iter().collect();
+    /// let b: RoaringBitmap = [2, 3, 4].into_iter().collect();
+    /// let c: RoaringBitmap = [3, 4, 5].into_iter().collect();
+    ///
+    /// let odd = RoaringBitmap::xor_many([&a, &b, &c]);
+    /// assert_eq!(odd.iter().collect::<Vec<_>>(), vec![1, 3, 5]);
+    /// ```
+    pub fn xor_many<'a, I>(bitmaps: I) -> RoaringBitmap
+    where
+        I: IntoIterator<Item = &'a RoaringBitmap>,
+    {
+        let mut scratch = BitmapContainer::new();
+        let containers = Self::grouped_containers(bitmaps)
+            .chunk_by(|(a, _), (b, _)| a == b)
+            .filter_map(|group| {
+                let key = group[0].0;
+                let container = match group {
+                    [(_, only)] => (*only).clone(),
+                    _ => {
+                        for (_, container) in group {
+                            scratch.symmetric_difference_lazy(container);
+                        }
+                        Container::from_scratch(&mut scratch)?
+                    }
+                };
+                Some((key, container))
+            })
+            .collect();
+
+        RoaringBitmap { containers }
+    }
+
+    /// Collects the containers of every bitmap, sorted by key so that equal
+    /// keys sit next to each other
+    fn grouped_containers<'a, I>(bitmaps: I) -> Vec<(u16, &'a Container)>
+    where
+        I: IntoIterator<Item = &'a RoaringBitmap>,
+    {
+        let mut all: Vec<(u16, &Container)> = bitmaps
+            .into_iter()
+            .flat_map(|bitmap| bitmap.containers.iter().map(|(key, c)| (*key, c)))
+            .collect();
+        all.sort_by_key(|(key, _)| *key);
+        all
+    }
+
+    // Threshold Queries
+
+    /// Returns the values present in at least `k` of the given bitmaps.
+    ///
+    /// With `k == 1` this is the union and with `k` equal to the number of
+    /// inputs it is the intersection; values anywhere in between come from a
+    /// single pass per container key instead of a composition of pairwise
+    /// unions and intersections. A `k` of 0 is treated as 1.
+    ///
+    /// # Performance
+    ///
+    /// Keys held by fewer than `k` inputs are skipped outright. Keys with a
+    /// Bitmap container are counted 64 values at a time with bit-sliced
+    /// counters; keys holding only Array and Run containers are merge-counted
+    /// over their sorted values and runs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let segments: Vec<RoaringBitmap> = vec![
+    ///     [1, 2, 3].into_iter().collect(),
+    ///     [2, 3, 4].into_iter().collect(),
+    ///     [3, 4, 5].into_iter().collect(),
+    /// ];
+    ///
+    /// let at_least_two = RoaringBitmap::threshold(&segments, 2);
+    /// assert_eq!(at_least_two.iter().collect::<Vec<_>>(), vec![2, 3, 4]);
+    /// ```
+    pub fn threshold<'a, I>(bitmaps: I, k: usize) -> RoaringBitmap
+    where
+        I: IntoIterator<Item = &'a RoaringBitmap>,
+    {
+        Self::threshold_weighted(bitmaps.into_iter().map(|bitmap| (bitmap, 1)), k as u64)
+    }
+
+    /// Returns the values whose inputs' weights sum to at least `threshold`.
+    ///
+    /// Each input is a `(bitmap, weight)` pair, and a value scores the sum of
+    /// the weights of the bitmaps containing it. [`threshold`](Self::threshold)
+    /// is the special case where every weight is 1. Inputs with a weight of 0
+    /// never contribute, and a `threshold` of 0 is treated as 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let strong: RoaringBitmap = [1, 2].into_iter().collect();
+    /// let weak: RoaringBitmap = [2, 3].into_iter().collect();
+    /// let weaker: RoaringBitmap = [3, 4].into_iter().collect();
+    ///
+    /// let scored = RoaringBitmap::threshold_weighted([(&strong, 5), (&weak, 2), (&weaker, 1)], 3);
+    /// assert_eq!(scored.iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    /// ```
+    pub fn threshold_weighted<'a, I>(inputs: I, threshold: u64) -> RoaringBitmap
+    where
+        I: IntoIterator<Item = (&'a RoaringBitmap, u32)>,
+    {
+        let threshold = threshold.max(1);
+        let mut all: Vec<(u16, &Container, u32)> = inputs
+            .into_iter()
+            .filter(|(_, weight)| *weight > 0)
+            .flat_map(|(bitmap, weight)| {
+                bitmap
+                    .containers
+                    .iter()
+                    .map(move |(key, container)| (*key, container, weight))
+            })
+            .collect();
+        all.sort_by_key(|(key, _, _)| *key);
+
+        let mut scratch = BitmapContainer::new();
+        let mut counter = SlicedCounter::default();
+        let mut group = Vec::new();
+        let mut containers = Vec::new();
+
+        for chunk in all.chunk_by(|(a, _, _), (b, _, _)| a == b) {
+            group.clear();
+            group.extend(
+                chunk
+                    .iter()
+                    .map(|(_, container, weight)| (*container, *weight)),
+            );
+            if let Some(container) =
+                Container::threshold_group(&group, threshold, &mut scratch, &mut counter)
+            {
+                containers.push((chunk[0].0, container));
+            }
+        }
+
+        RoaringBitmap { containers }
+    }
+
+    // Range Complement
+
+    /// Toggles every value in `range`: present values are removed and
+    /// missing ones are inserted
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let mut bm = RoaringBitmap::new();
+    /// bm.extend_sparse([1, 3, 100_000]);
+    ///
+    /// bm.flip(0..5);
+    /// assert_eq!(bm.iter().collect::<Vec<_>>(), vec![0, 2, 4, 100_000]);
+    ///
+    /// // Flipping twice restores the original values
+    /// bm.flip(0..5);
+    /// assert_eq!(bm.iter().collect::<Vec<_>>(), vec![1, 3, 100_000]);
+    /// ```
+    ///
+    /// # Performance
+    ///
+    /// Works container by container without building a helper bitmap:
+    /// - Bitmap containers XOR the range's word masks in place
+    /// - Run and Array containers invert their runs over the range
+    /// - Keys inside the range with no container become a single run
+    pub fn flip<R: std::ops::RangeBounds<u32>>(&mut self, range: R) {
+        let Some((start, end)) = Self::resolve_range(&range) else {
+            return;
+        };
+        let (start_key, _) = Self::split(start);
+        let (end_key, _) = Self::split(end);
+
+        let first = self.containers.partition_point(|(k, _)| *k < start_key);
+        let last = self.containers.partition_point(|(k, _)| *k <= end_key);
+
+        let mut existing = self.containers.drain(first..last).peekable();
+        let mut flipped = Vec::new();
+        for (key, low, high) in Self::key_ranges(start, end) {
+            let container = match existing.next_if(|(k, _)| *k == key) {
+                Some((_, container)) => container.flip_range(low, high),
+                None => Some(Container::Run(RunContainer {
+                    runs: vec![(low, high - low)],
+                })),
+            };
+            if let Some(container) = container {
+                flipped.push((key, container));
+            }
+        }
+        drop(existing);
+
+        self.containers.splice(first..first, flipped);
+    }
+
+    /// Returns the values in `range` that are not in the bitmap
+    ///
+    /// This is `flip(range)` restricted to the range, computed without
+    /// modifying or cloning the bitmap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let mut seen = RoaringBitmap::new();
+    /// seen.extend_sparse([2, 3, 7]);
+    ///
+    /// let missing = seen.complement_within(0..10);
+    /// assert_eq!(missing.iter().collect::<Vec<_>>(), vec![0, 1, 4, 5, 6, 8, 9]);
+    /// assert!(seen.complement_within(2..4).is_empty());
+    /// ```
+    pub fn complement_within<R: std::ops::RangeBounds<u32>>(&self, range: R) -> RoaringBitmap {
+        let Some((start, end)) = Self::resolve_range(&range) else {
+            return RoaringBitmap::new();
+        };
+        let (start_key, _) = Self::split(start);
+
+        let first = self.containers.partition_point(|(k, _)| *k < start_key);
+        let mut existing = self.containers[first..].iter().peekable();
+        let mut containers = Vec::new();
+        for (key, low, high) in Self::key_ranges(start, end) {
+            let container = match existing.next_if(|(k, _)| *k == key) {
+                Some((_, container)) => container.complement_range(low, high),
+                None => Some(Container::Run(RunContainer {
+                    runs: vec![(low, high - low)],
+                })),
+            };
+            if let Some(container) = container {
+                containers.push((key, container));
+            }
+        }
+
+        RoaringBitmap { containers }
+    }
+
     // Iteration
 
     /// Returns an iterator over elements in sorted order
@@ -1076,26 +2061,65 @@ impl RoaringBitmap {
     /// assert_eq!(values, vec![1, 2, 3]); // Always sorted
     /// ```
     pub fn iter(&self) -> Iter<'_> {
-        let (bitmap_word_index, bitmap_current_word) = if !self.containers.is_empty() {
-            match &self.containers[0].1 {
-                Container::Bitmap(bm) => (0, bm.bits[0]),
-                _ => (0, 0),
-            }
-        } else {
-            (0, 0)
-        };
-
         Iter {
-            bitmap: self,
-            container_index: 0,
-            value_index: 0,
-            bitmap_word_index,
-            bitmap_current_word,
-            bitmap_bit_position: 0,
-            run_offset: 0,
+            containers: self.containers.iter(),
+            front: None,
+            back: None,
+            remaining: self.len(),
         }
     }
 
+    /// Returns an iterator over the elements within `range`, in sorted order
+    ///
+    /// The returned iterator is clipped to the range up front, so it supports
+    /// `next_back`, `len` and `advance_to` just like `iter()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let mut bm = RoaringBitmap::new();
+    /// bm.extend_sparse([1, 5, 10, 70_000, 200_000]);
+    ///
+    /// let window: Vec<u32> = bm.range(5..70_000).collect();
+    /// assert_eq!(window, vec![5, 10]);
+    ///
+    /// let tail: Vec<u32> = bm.range(10..=200_000).rev().collect();
+    /// assert_eq!(tail, vec![200_000, 70_000, 10]);
+    /// ```
+    ///
+    /// # Use Cases
+    ///
+    /// - Paging through ids in `[a, b)`
+    /// - Restricting a result set to a time window
+    pub fn range<R: std::ops::RangeBounds<u32>>(&self, range: R) -> Iter<'_> {
+        let Some((start, end)) = Self::resolve_range(&range) else {
+            return Iter {
+                containers: [].iter(),
+                front: None,
+                back: None,
+                remaining: 0,
+            };
+        };
+        let (start_key, _) = Self::split(start);
+        let (end_key, _) = Self::split(end);
+
+        let first = self.containers.partition_point(|(k, _)| *k < start_key);
+        let last = self.containers.partition_point(|(k, _)| *k <= end_key);
+        let containers = &self.containers[first..last];
+
+        let mut iter = Iter {
+            containers: containers.iter(),
+            front: None,
+            back: None,
+            remaining: containers.iter().map(|(_, c)| c.len()).sum(),
+        };
+        iter.advance_to(start);
+        iter.retreat_to(end);
+        iter
+    }
+
     // Optimization
 
     /// Optimizes container storage by converting between Array, Bitmap, and Run containers
@@ -1294,13 +2318,11 @@ impl RoaringBitmap {
                         // For consecutive values, Run is almost always better
                         // Run: 1 run * 4 bytes = 4 bytes
                         // Array: num_values * 2 bytes
-                        let mut runs = Vec::new();
-                        runs.push((low_start, (num_values - 1) as u16)); // Store length-1
+                        let runs = vec![(low_start, (num_values - 1) as u16)]; // Store length-1
                         Container::Run(RunContainer { runs })
                     } else {
                         // Smaller range - still create as Run for consecutive data
-                        let mut runs = Vec::new();
-                        runs.push((low_start, (num_values - 1) as u16)); // Store length-1
+                        let runs = vec![(low_start, (num_values - 1) as u16)]; // Store length-1
                         Container::Run(RunContainer { runs })
                     };
 
@@ -1413,6 +2435,31 @@ impl RoaringBitmap {
         }
     }
 
+    /// Merges strictly ascending low values sharing one key into the bitmap
+    ///
+    /// Builds the container in one go, using the same Array/Bitmap threshold as
+    /// `insert`, then unions it with any existing container for the key.
+    fn merge_sorted_chunk(&mut self, key: u16, values: Vec<u16>) {
+        if values.is_empty() {
+            return;
+        }
+
+        let array = ArrayContainer { values };
+        let container = if array.len() as usize >= ARRAY_TO_BITMAP_THRESHOLD {
+            Container::Bitmap(BitmapContainer::from_array(&array))
+        } else {
+            Container::Array(array)
+        };
+
+        match self.containers.binary_search_by_key(&key, |(k, _)| *k) {
+            Ok(index) => {
+                let existing = &mut self.containers[index].1;
+                *existing = existing.union(&container);
+            }
+            Err(index) => self.containers.insert(index, (key, container)),
+        }
+    }
+
     // Memory Usage
 
     /// Returns the total memory usage in bytes, including all Rust overheads.
@@ -1530,17 +2577,255 @@ impl RoaringBitmap {
         }
     }
 
-    // Test/Debug helpers
+    // Serialization
 
-    /// Returns the container type for a given key (for testing purposes)
-    /// Returns None if container doesn't exist
+    /// Returns the number of bytes `serialize_into` will write for this bitmap.
     ///
-    /// **Note**: This method is intended for testing and debugging only.
-    /// It exposes internal implementation details.
-    #[doc(hidden)]
-    pub fn container_type(&self, key: u16) -> Option<&'static str> {
-        self.containers
-            .binary_search_by_key(&key, |(k, _)| *k)
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let mut bm = RoaringBitmap::new();
+    /// bm.extend_sparse([1, 2, 3]);
+    ///
+    /// let mut bytes = Vec::new();
+    /// bm.serialize_into(&mut bytes).unwrap();
+    /// assert_eq!(bytes.len(), bm.serialized_size());
+    /// ```
+    pub fn serialized_size(&self) -> usize {
+        let count = self.containers.len();
+        let has_run = self.has_run_containers();
+
+        let mut size = if has_run {
+            // Cookie (with embedded count) + run flag bitset
+            4 + count.div_ceil(8)
+        } else {
+            // Cookie + container count
+            4 + 4
+        };
+
+        // Descriptive header: key + (cardinality - 1) per container
+        size += count * 4;
+
+        // Offset header
+        if !has_run || count >= NO_OFFSET_THRESHOLD {
+            size += count * 4;
+        }
+
+        for (_, container) in &self.containers {
+            size += container.serialized_data_size();
+        }
+
+        size
+    }
+
+    /// Writes the bitmap in the portable Roaring serialization format.
+    ///
+    /// The output is byte-compatible with CRoaring (`roaring_bitmap_portable_serialize`)
+    /// and Java Roaring (`RoaringBitmap.serialize`), so bitmaps can be exchanged
+    /// between implementations.
+    ///
+    /// # Format
+    ///
+    /// All integers are little-endian:
+    /// - Cookie: `12346` followed by a `u32` container count, or (when Run containers
+    ///   are present) `12347 | (count - 1) << 16` followed by a bitset marking Run containers
+    /// - Descriptive header: `(key: u16, cardinality - 1: u16)` per container
+    /// - Offset header: `u32` byte offset per container (omitted when Run containers are
+    ///   present and there are fewer than 4 containers)
+    /// - Container data: Array as sorted `u16`s (cardinality ≤ 4096), Bitmap as
+    ///   1024 `u64` words, Run as a `u16` run count followed by `(start, length - 1)` pairs
+    ///
+    /// Array and Bitmap containers are written by cardinality rather than by their
+    /// in-memory type, as the format requires.
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O error produced by `writer`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let mut bm = RoaringBitmap::new();
+    /// bm.extend_consecutive(0..100_000);
+    /// bm.insert(1_000_000);
+    ///
+    /// let mut bytes = Vec::new();
+    /// bm.serialize_into(&mut bytes).unwrap();
+    ///
+    /// let restored = RoaringBitmap::deserialize_from(&bytes[..]).unwrap();
+    /// assert_eq!(restored.len(), bm.len());
+    /// assert!(restored.contains(1_000_000));
+    /// ```
+    pub fn serialize_into<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let count = self.containers.len();
+        let has_run = self.has_run_containers();
+
+        // Cookie
+        let mut header_size;
+        if has_run {
+            // count >= 1 here, since at least one Run container exists
+            let cookie = (SERIAL_COOKIE as u32) | (((count - 1) as u32) << 16);
+            writer.write_all(&cookie.to_le_bytes())?;
+
+            let mut run_flags = vec![0u8; count.div_ceil(8)];
+            for (index, (_, container)) in self.containers.iter().enumerate() {
+                if let Container::Run(_) = container {
+                    run_flags[index / 8] |= 1 << (index % 8);
+                }
+            }
+            writer.write_all(&run_flags)?;
+            header_size = 4 + run_flags.len();
+        } else {
+            writer.write_all(&SERIAL_COOKIE_NO_RUNCONTAINER.to_le_bytes())?;
+            writer.write_all(&(count as u32).to_le_bytes())?;
+            header_size = 8;
+        }
+
+        // Descriptive header
+        for (key, container) in &self.containers {
+            let cardinality_minus_one = (container.len() - 1) as u16;
+            writer.write_all(&key.to_le_bytes())?;
+            writer.write_all(&cardinality_minus_one.to_le_bytes())?;
+        }
+        header_size += count * 4;
+
+        // Offset header
+        if !has_run || count >= NO_OFFSET_THRESHOLD {
+            let mut offset = header_size + count * 4;
+            for (_, container) in &self.containers {
+                writer.write_all(&(offset as u32).to_le_bytes())?;
+                offset += container.serialized_data_size();
+            }
+        }
+
+        // Container data
+        for (_, container) in &self.containers {
+            container.serialize_data(&mut writer)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads a bitmap written in the portable Roaring serialization format.
+    ///
+    /// Accepts output from `serialize_into` as well as from CRoaring and Java Roaring.
+    /// See [`RoaringBitmap::serialize_into`] for a description of the format.
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O error produced by `reader` (including `UnexpectedEof` for
+    /// truncated input), or an error of kind `InvalidData` when the bytes do not
+    /// describe a valid bitmap (unknown cookie, unsorted keys, a cardinality that
+    /// does not match the container data, overlapping runs, ...).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// // {1, 2, 3} as written by CRoaring
+    /// let bytes: [u8; 22] = [
+    ///     0x3A, 0x30, 0, 0, 1, 0, 0, 0, // cookie, container count
+    ///     0, 0, 2, 0,                   // key 0, cardinality 3
+    ///     16, 0, 0, 0,                  // offset of container data
+    ///     1, 0, 2, 0, 3, 0,             // array values
+    /// ];
+    ///
+    /// let bm = RoaringBitmap::deserialize_from(&bytes[..]).unwrap();
+    /// assert_eq!(bm.iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    /// ```
+    pub fn deserialize_from<R: Read>(mut reader: R) -> io::Result<RoaringBitmap> {
+        let cookie = read_u32(&mut reader)?;
+
+        // Cookie determines container count and which containers are Runs
+        let (count, run_flags) = if cookie as u16 == SERIAL_COOKIE {
+            let count = (cookie >> 16) as usize + 1;
+            let mut run_flags = vec![0u8; count.div_ceil(8)];
+            reader.read_exact(&mut run_flags)?;
+            (count, Some(run_flags))
+        } else if cookie == SERIAL_COOKIE_NO_RUNCONTAINER {
+            let count = read_u32(&mut reader)? as usize;
+            if count > 1 << 16 {
+                return Err(invalid_data("container count exceeds 65536"));
+            }
+            (count, None)
+        } else {
+            return Err(invalid_data("unknown cookie"));
+        };
+
+        // Descriptive header
+        let mut descriptors = Vec::with_capacity(count);
+        for _ in 0..count {
+            let key = read_u16(&mut reader)?;
+            let cardinality = read_u16(&mut reader)? as u64 + 1;
+            if let Some(&(prev_key, _)) = descriptors.last() {
+                if key <= prev_key {
+                    return Err(invalid_data("container keys are not strictly increasing"));
+                }
+            }
+            descriptors.push((key, cardinality));
+        }
+
+        // Offset header: data is read sequentially, so offsets are not needed
+        let has_offsets = match &run_flags {
+            Some(_) => count >= NO_OFFSET_THRESHOLD,
+            None => true,
+        };
+        if has_offsets {
+            for _ in 0..count {
+                read_u32(&mut reader)?;
+            }
+        }
+
+        // Container data
+        let mut containers = Vec::with_capacity(count);
+        for (index, (key, cardinality)) in descriptors.into_iter().enumerate() {
+            let is_run = run_flags
+                .as_ref()
+                .is_some_and(|flags| flags[index / 8] & (1 << (index % 8)) != 0);
+
+            let container = if is_run {
+                Container::Run(RunContainer::deserialize_from(&mut reader, cardinality)?)
+            } else if cardinality <= SERIAL_MAX_ARRAY_CARDINALITY {
+                let array = ArrayContainer::deserialize_from(&mut reader, cardinality)?;
+                // Keep the in-memory Array → Bitmap convention
+                if array.len() as usize >= ARRAY_TO_BITMAP_THRESHOLD {
+                    Container::Bitmap(BitmapContainer::from_array(&array))
+                } else {
+                    Container::Array(array)
+                }
+            } else {
+                Container::Bitmap(BitmapContainer::deserialize_from(&mut reader, cardinality)?)
+            };
+
+            containers.push((key, container));
+        }
+
+        Ok(RoaringBitmap { containers })
+    }
+
+    /// Returns true if any container uses the Run representation
+    fn has_run_containers(&self) -> bool {
+        self.containers
+            .iter()
+            .any(|(_, container)| matches!(container, Container::Run(_)))
+    }
+
+    // Test/Debug helpers
+
+    /// Returns the container type for a given key (for testing purposes)
+    /// Returns None if container doesn't exist
+    ///
+    /// **Note**: This method is intended for testing and debugging only.
+    /// It exposes internal implementation details.
+    #[doc(hidden)]
+    pub fn container_type(&self, key: u16) -> Option<&'static str> {
+        self.containers
+            .binary_search_by_key(&key, |(k, _)| *k)
             .ok()
             .map(|index| match &self.containers[index].1 {
                 Container::Array(_) => "Array",
@@ -1677,6 +2962,343 @@ impl Container {
         }
     }
 
+    /// Returns a double-ended cursor over the container's values
+    fn iter(&self) -> ContainerIter<'_> {
+        match self {
+            Container::Array(array) => ContainerIter::Array {
+                values: array.values.iter(),
+            },
+            Container::Bitmap(bitmap) => ContainerIter::Bitmap {
+                bits: &bitmap.bits,
+                cursor: BitmapCursor::new(&bitmap.bits, bitmap.cardinality),
+            },
+            Container::Run(run) => ContainerIter::Run {
+                runs: &run.runs,
+                cursor: RunCursor::new(&run.runs),
+            },
+        }
+    }
+
+    /// Checks whether two containers hold the same values, regardless of type
+    fn same_values(&self, other: &Container) -> bool {
+        match (self, other) {
+            (Container::Array(a), Container::Array(b)) => a.values == b.values,
+            (Container::Bitmap(a), Container::Bitmap(b)) => a.bits == b.bits,
+            (Container::Run(a), Container::Run(b)) => a.runs == b.runs,
+            _ => self.len() == other.len() && self.iter().eq(other.iter()),
+        }
+    }
+
+    /// Calls `f(start, end)` for each maximal run of consecutive values, in
+    /// order, whatever the container type
+    ///
+    /// Gives every container a canonical form that costs O(runs) for Run,
+    /// O(n) for Array and O(1024 + runs) for Bitmap containers.
+    fn for_each_run(&self, mut f: impl FnMut(u16, u16)) {
+        match self {
+            Container::Array(array) => {
+                let mut values = array.values.iter().copied();
+                if let Some(first) = values.next() {
+                    let (mut start, mut end) = (first, first);
+                    for value in values {
+                        if value != end + 1 {
+                            f(start, end);
+                            start = value;
+                        }
+                        end = value;
+                    }
+                    f(start, end);
+                }
+            }
+            Container::Bitmap(bitmap) => bitmap.for_each_run(f),
+            Container::Run(run) => {
+                for &(start, length) in &run.runs {
+                    f(start, start + length);
+                }
+            }
+        }
+    }
+
+    /// Returns the number of values less than or equal to `value`
+    fn rank(&self, value: u16) -> u64 {
+        match self {
+            Container::Array(array) => array.rank(value),
+            Container::Bitmap(bitmap) => bitmap.rank(value),
+            Container::Run(run) => run.rank(value),
+        }
+    }
+
+    /// Returns the `k`-th smallest value (0-based)
+    ///
+    /// Caller must ensure `k < len()`.
+    fn select(&self, k: u16) -> u16 {
+        match self {
+            Container::Array(array) => array.select(k),
+            Container::Bitmap(bitmap) => bitmap.select(k),
+            Container::Run(run) => run.select(k),
+        }
+    }
+
+    /// Returns the smallest value (container must be non-empty)
+    fn min(&self) -> u16 {
+        match self {
+            Container::Array(array) => array.values[0],
+            Container::Bitmap(bitmap) => bitmap.min(),
+            Container::Run(run) => run.runs[0].0,
+        }
+    }
+
+    /// Returns the largest value (container must be non-empty)
+    fn max(&self) -> u16 {
+        match self {
+            Container::Array(array) => array.values[array.values.len() - 1],
+            Container::Bitmap(bitmap) => bitmap.max(),
+            Container::Run(run) => {
+                let (start, length) = run.runs[run.runs.len() - 1];
+                start + length
+            }
+        }
+    }
+
+    /// Returns the smallest value greater than or equal to `value`
+    fn next_at_or_after(&self, value: u16) -> Option<u16> {
+        match self {
+            Container::Array(array) => array.next_at_or_after(value),
+            Container::Bitmap(bitmap) => bitmap.next_at_or_after(value),
+            Container::Run(run) => run.next_at_or_after(value),
+        }
+    }
+
+    /// Returns the largest value less than or equal to `value`
+    fn prev_at_or_before(&self, value: u16) -> Option<u16> {
+        match self {
+            Container::Array(array) => array.prev_at_or_before(value),
+            Container::Bitmap(bitmap) => bitmap.prev_at_or_before(value),
+            Container::Run(run) => run.prev_at_or_before(value),
+        }
+    }
+
+    /// Checks whether every value in `[start, end]` is present
+    fn contains_range(&self, start: u16, end: u16) -> bool {
+        match self {
+            // Sorted and unique, so `start` and `end` sitting exactly
+            // `end - start` positions apart means nothing is missing between
+            Container::Array(array) => {
+                let index = array.values.partition_point(|&value| value < start);
+                array.values.get(index) == Some(&start)
+                    && array.values.get(index + (end - start) as usize) == Some(&end)
+            }
+            Container::Bitmap(bitmap) => BitmapContainer::range_masks(start, end)
+                .all(|(index, mask)| bitmap.bits[index] & mask == mask),
+            Container::Run(run) => {
+                let index = run
+                    .runs
+                    .partition_point(|&(run_start, _)| run_start <= start);
+                index > 0 && RunContainer::bounds(run.runs[index - 1]).1 >= end
+            }
+        }
+    }
+
+    /// Flips every value in `[start, end]` (None if the result is empty)
+    fn flip_range(self, start: u16, end: u16) -> Option<Container> {
+        match self {
+            Container::Bitmap(mut bitmap) => {
+                bitmap.update_range(start, end, |word, mask| word ^ mask);
+                Container::from_bitmap(bitmap)
+            }
+            Container::Run(run) => run
+                .invert_range(start, end, true)
+                .map(RunContainer::into_container),
+            Container::Array(array) => RunContainer::from_array(&array)
+                .invert_range(start, end, true)
+                .map(RunContainer::into_container),
+        }
+    }
+
+    /// Returns the values in `[start, end]` missing from this container (None
+    /// if there are none)
+    fn complement_range(&self, start: u16, end: u16) -> Option<Container> {
+        match self {
+            Container::Bitmap(bitmap) => {
+                let mut result = BitmapContainer::new();
+                for (index, mask) in BitmapContainer::range_masks(start, end) {
+                    result.bits[index] = !bitmap.bits[index] & mask;
+                }
+                result.recount();
+                Container::from_bitmap(result)
+            }
+            Container::Run(run) => run
+                .invert_range(start, end, false)
+                .map(RunContainer::into_container),
+            Container::Array(array) => RunContainer::from_array(array)
+                .invert_range(start, end, false)
+                .map(RunContainer::into_container),
+        }
+    }
+
+    /// Wraps a bitmap result, converting it to an Array when small (None if empty)
+    fn from_bitmap(bitmap: BitmapContainer) -> Option<Container> {
+        if bitmap.is_empty() {
+            None
+        } else if bitmap.len() < ARRAY_TO_BITMAP_THRESHOLD as u64 {
+            Some(Container::Array(bitmap.to_array()))
+        } else {
+            Some(Container::Bitmap(bitmap))
+        }
+    }
+
+    /// Finishes a lazily accumulated scratch bitmap: counts its values and
+    /// runs once, picks the container type with a single `optimize` pass, and
+    /// leaves the scratch cleared for the next key (None if empty)
+    fn from_scratch(scratch: &mut BitmapContainer) -> Option<Container> {
+        scratch.recount();
+        let mut container = if scratch.is_empty() {
+            return None;
+        } else if scratch.len() < ARRAY_TO_BITMAP_THRESHOLD as u64 {
+            // Small results are copied out so the scratch allocation is reused
+            let array = scratch.to_array();
+            scratch.bits.fill(0);
+            scratch.cardinality = 0;
+            Container::Array(array)
+        } else if 2 + scratch.count_runs() * 4 <= 1024 * 8 {
+            // Run-shaped results are read out the same way
+            let mut runs = Vec::new();
+            scratch.for_each_run(|start, end| runs.push((start, end - start)));
+            scratch.bits.fill(0);
+            scratch.cardinality = 0;
+            Container::Run(RunContainer { runs })
+        } else {
+            Container::Bitmap(std::mem::replace(scratch, BitmapContainer::new()))
+        };
+        container.optimize();
+        Some(container)
+    }
+
+    /// Returns the intersection of containers sorted by ascending cardinality
+    /// (None if empty), stopping as soon as the running result is empty
+    fn intersection_group(
+        group: &[&Container],
+        scratch: &mut BitmapContainer,
+    ) -> Option<Container> {
+        // The result is a subset of the smallest array, so filter its values
+        // instead of touching whole bitmaps
+        let smallest_array = group.iter().enumerate().find_map(|(i, c)| match c {
+            Container::Array(array) => Some((i, array)),
+            _ => None,
+        });
+        if let Some((index, array)) = smallest_array {
+            let mut values = array.values.clone();
+            for (_, other) in group.iter().enumerate().filter(|(i, _)| *i != index) {
+                values.retain(|&value| other.contains(value));
+                if values.is_empty() {
+                    return None;
+                }
+            }
+            let mut container = Container::Array(ArrayContainer { values });
+            container.optimize();
+            return Some(container);
+        }
+
+        let (first, rest) = group.split_first()?;
+        scratch.union_lazy(first);
+        for container in rest {
+            if !scratch.intersect_lazy(container) {
+                scratch.bits.fill(0);
+                return None;
+            }
+        }
+        Container::from_scratch(scratch)
+    }
+
+    /// Returns the values of one key whose containers' weights sum to at least
+    /// `threshold` (None if empty)
+    fn threshold_group(
+        group: &[(&Container, u32)],
+        threshold: u64,
+        scratch: &mut BitmapContainer,
+        counter: &mut SlicedCounter,
+    ) -> Option<Container> {
+        let total: u64 = group.iter().map(|&(_, weight)| u64::from(weight)).sum();
+        let lightest = group.iter().map(|&(_, weight)| u64::from(weight)).min()?;
+        if total < threshold {
+            return None;
+        }
+
+        // Any one container reaches the threshold on its own: a union
+        if lightest >= threshold {
+            if let [(only, _)] = group {
+                return Some((*only).clone());
+            }
+            for (container, _) in group {
+                scratch.union_lazy(container);
+            }
+            return Container::from_scratch(scratch);
+        }
+
+        // Missing even the lightest container falls short: an intersection
+        if total - lightest < threshold {
+            let mut containers: Vec<&Container> = group.iter().map(|&(c, _)| c).collect();
+            containers.sort_by_key(|container| container.len());
+            return Container::intersection_group(&containers, scratch);
+        }
+
+        if group.iter().any(|(c, _)| matches!(c, Container::Bitmap(_))) {
+            counter.reset(total);
+            for &(container, weight) in group {
+                counter.add(container, weight);
+            }
+            counter.at_least(threshold, scratch);
+            Container::from_scratch(scratch)
+        } else {
+            Container::threshold_sweep(group, threshold)
+        }
+    }
+
+    /// Merge-counts Array and Run containers: every value or run adds its
+    /// container's weight where it starts and takes it back just past its end,
+    /// so one sweep over the sorted boundaries yields the qualifying runs
+    fn threshold_sweep(group: &[(&Container, u32)], threshold: u64) -> Option<Container> {
+        let mut events: Vec<(u32, bool, u64)> = Vec::new();
+        for &(container, weight) in group {
+            let weight = u64::from(weight);
+            let mut push = |start: u16, end: u16| {
+                events.push((u32::from(start), true, weight));
+                events.push((u32::from(end) + 1, false, weight));
+            };
+            match container {
+                Container::Array(array) => array.values.iter().for_each(|&v| push(v, v)),
+                Container::Run(run) => run
+                    .runs
+                    .iter()
+                    .for_each(|&run| push(run.0, RunContainer::bounds(run).1)),
+                Container::Bitmap(_) => unreachable!("bitmaps are counted by SlicedCounter"),
+            }
+        }
+        events.sort_unstable_by_key(|&(position, _, _)| position);
+
+        let mut runs = Vec::new();
+        let mut score = 0u64;
+        let mut i = 0;
+        while i < events.len() {
+            let position = events[i].0;
+            while i < events.len() && events[i].0 == position {
+                let (_, starts, weight) = events[i];
+                if starts {
+                    score += weight;
+                } else {
+                    score -= weight;
+                }
+                i += 1;
+            }
+            // A positive score means some run is still open, so an end follows
+            if score >= threshold {
+                RunContainer::push_merged(&mut runs, position as u16, (events[i].0 - 1) as u16);
+            }
+        }
+
+        RunContainer::non_empty(runs).map(RunContainer::into_container)
+    }
+
     /// Returns the union of two containers
     fn union(&self, other: &Container) -> Container {
         match (self, other) {
@@ -1705,18 +3327,15 @@ impl Container {
                 Container::Bitmap(result)
             }
             (Container::Run(a), Container::Run(b)) => Container::Run(a.union(b)),
-            // Run + Array/Bitmap: convert run to array and delegate
+            // Run + Array: the array is small, so merge it in as runs
             (Container::Run(r), Container::Array(a)) | (Container::Array(a), Container::Run(r)) => {
-                let run_array = r.to_array();
-                Container::Array(run_array.union(a))
+                r.union(&RunContainer::from_array(a)).into_container()
             }
+            // Run + Bitmap: fill the runs' word ranges
             (Container::Run(r), Container::Bitmap(b))
             | (Container::Bitmap(b), Container::Run(r)) => {
-                let run_array = r.to_array();
                 let mut result = b.clone();
-                for &value in &run_array.values {
-                    result.insert(value);
-                }
+                result.set_runs(r);
                 Container::Bitmap(result)
             }
         }
@@ -1765,26 +3384,15 @@ impl Container {
                 }
             }
             (Container::Run(a), Container::Run(b)) => a.intersection(b).map(Container::Run),
-            // Run + Array/Bitmap: convert run to array and delegate
+            // Run + Array: keep the array values that fall inside a run
             (Container::Run(r), Container::Array(a)) | (Container::Array(a), Container::Run(r)) => {
-                let run_array = r.to_array();
-                run_array.intersection(a).map(Container::Array)
+                r.filter_array(a, true).map(Container::Array)
             }
+            // Run + Bitmap: mask the bitmap with the runs' word ranges
             (Container::Run(r), Container::Bitmap(b))
-            | (Container::Bitmap(b), Container::Run(r)) => {
-                let run_array = r.to_array();
-                let mut values = Vec::new();
-                for &value in &run_array.values {
-                    if b.contains(value) {
-                        values.push(value);
-                    }
-                }
-                if values.is_empty() {
-                    None
-                } else {
-                    Some(Container::Array(ArrayContainer { values }))
-                }
-            }
+            | (Container::Bitmap(b), Container::Run(r)) => BitmapContainer::from_run(r)
+                .intersection(b)
+                .and_then(Container::from_bitmap),
         }
     }
 
@@ -1831,42 +3439,23 @@ impl Container {
                 }
             }
             (Container::Run(a), Container::Run(b)) => a.difference(b).map(Container::Run),
-            // Run + Array/Bitmap: convert run to array and delegate
-            (Container::Run(r), Container::Array(a)) => {
-                let run_array = r.to_array();
-                run_array.difference(a).map(Container::Array)
-            }
+            // Run - Array: cut the array values out of the runs
+            (Container::Run(r), Container::Array(a)) => r
+                .difference(&RunContainer::from_array(a))
+                .map(RunContainer::into_container),
+            // Array - Run: keep the array values outside every run
             (Container::Array(a), Container::Run(r)) => {
-                let run_array = r.to_array();
-                a.difference(&run_array).map(Container::Array)
-            }
-            (Container::Run(r), Container::Bitmap(b)) => {
-                let run_array = r.to_array();
-                let mut values = Vec::new();
-                for &value in &run_array.values {
-                    if !b.contains(value) {
-                        values.push(value);
-                    }
-                }
-                if values.is_empty() {
-                    None
-                } else {
-                    Some(Container::Array(ArrayContainer { values }))
-                }
+                r.filter_array(a, false).map(Container::Array)
             }
+            // Run - Bitmap: expand the runs into words and mask out the bitmap
+            (Container::Run(r), Container::Bitmap(b)) => BitmapContainer::from_run(r)
+                .difference(b)
+                .and_then(Container::from_bitmap),
+            // Bitmap - Run: clear the runs' word ranges
             (Container::Bitmap(b), Container::Run(r)) => {
-                let run_array = r.to_array();
                 let mut result = b.clone();
-                for &value in &run_array.values {
-                    result.remove(value);
-                }
-                if result.is_empty() {
-                    None
-                } else if result.len() < ARRAY_TO_BITMAP_THRESHOLD as u64 {
-                    Some(Container::Array(result.to_array()))
-                } else {
-                    Some(Container::Bitmap(result))
-                }
+                result.clear_runs(r);
+                Container::from_bitmap(result)
             }
         }
     }
@@ -1900,22 +3489,120 @@ impl Container {
                 })
             }
             (Container::Run(a), Container::Run(b)) => a.symmetric_difference(b).map(Container::Run),
-            // Run + Array/Bitmap: convert run to array and delegate
+            // Run + Array: the array is small, so toggle it in as runs
             (Container::Run(r), Container::Array(a)) | (Container::Array(a), Container::Run(r)) => {
-                let run_array = r.to_array();
-                run_array.symmetric_difference(a).map(Container::Array)
+                r.symmetric_difference(&RunContainer::from_array(a))
+                    .map(RunContainer::into_container)
             }
+            // Run + Bitmap: flip the runs' word ranges
             (Container::Run(r), Container::Bitmap(b))
             | (Container::Bitmap(b), Container::Run(r)) => {
-                let run_array = r.to_array();
-                let a_bitmap = BitmapContainer::from_array(&run_array);
-                a_bitmap.symmetric_difference(b).map(|bitmap| {
-                    if bitmap.len() < ARRAY_TO_BITMAP_THRESHOLD as u64 {
-                        Container::Array(bitmap.to_array())
-                    } else {
-                        Container::Bitmap(bitmap)
-                    }
-                })
+                let mut result = b.clone();
+                result.flip_runs(r);
+                Container::from_bitmap(result)
+            }
+        }
+    }
+
+    // Cardinality-only set operations
+
+    /// Returns the number of values in both containers, without building the
+    /// intersection
+    fn intersection_len(&self, other: &Container) -> u64 {
+        match (self, other) {
+            (Container::Array(a), Container::Array(b)) => a
+                .contains_each(b.values.iter().copied())
+                .filter(|&found| found)
+                .count() as u64,
+            (Container::Array(a), Container::Bitmap(b))
+            | (Container::Bitmap(b), Container::Array(a)) => {
+                a.values.iter().filter(|&&value| b.contains(value)).count() as u64
+            }
+            (Container::Bitmap(a), Container::Bitmap(b)) => a
+                .bits
+                .iter()
+                .zip(b.bits.iter())
+                .map(|(x, y)| (x & y).count_ones() as u64)
+                .sum(),
+            (Container::Run(a), Container::Run(b)) => a.intersection_len(b),
+            (Container::Run(r), Container::Array(a)) | (Container::Array(a), Container::Run(r)) => {
+                r.contains_each(a.values.iter().copied())
+                    .filter(|&found| found)
+                    .count() as u64
+            }
+            (Container::Run(r), Container::Bitmap(b))
+            | (Container::Bitmap(b), Container::Run(r)) => r
+                .runs
+                .iter()
+                .map(|&(start, length)| b.count_range(start, start + length))
+                .sum(),
+        }
+    }
+
+    /// Checks whether the containers share at least one value, stopping at
+    /// the first common value
+    fn intersects(&self, other: &Container) -> bool {
+        match (self, other) {
+            (Container::Array(a), Container::Array(b)) => {
+                a.contains_each(b.values.iter().copied()).any(|found| found)
+            }
+            (Container::Array(a), Container::Bitmap(b))
+            | (Container::Bitmap(b), Container::Array(a)) => {
+                a.values.iter().any(|&value| b.contains(value))
+            }
+            (Container::Bitmap(a), Container::Bitmap(b)) => {
+                a.bits.iter().zip(b.bits.iter()).any(|(x, y)| x & y != 0)
+            }
+            (Container::Run(a), Container::Run(b)) => a.intersects(b),
+            (Container::Run(r), Container::Array(a)) | (Container::Array(a), Container::Run(r)) => {
+                r.contains_each(a.values.iter().copied()).any(|found| found)
+            }
+            (Container::Run(r), Container::Bitmap(b))
+            | (Container::Bitmap(b), Container::Run(r)) => r
+                .runs
+                .iter()
+                .any(|&(start, length)| b.count_range(start, start + length) > 0),
+        }
+    }
+
+    /// Checks whether every value of this container is in `other`, stopping
+    /// at the first missing value
+    fn is_subset(&self, other: &Container) -> bool {
+        if self.len() > other.len() {
+            return false;
+        }
+
+        match (self, other) {
+            (Container::Array(a), Container::Array(b)) => {
+                b.contains_each(a.values.iter().copied()).all(|found| found)
+            }
+            (Container::Array(a), Container::Bitmap(b)) => {
+                a.values.iter().all(|&value| b.contains(value))
+            }
+            (Container::Array(a), Container::Run(r)) => {
+                r.contains_each(a.values.iter().copied()).all(|found| found)
+            }
+            (Container::Bitmap(a), Container::Bitmap(b)) => {
+                a.bits.iter().zip(b.bits.iter()).all(|(x, y)| x & !y == 0)
+            }
+            (Container::Run(a), Container::Run(b)) => a.is_subset(b),
+            // Every run must be fully set in the bitmap
+            (Container::Run(r), Container::Bitmap(b)) => r
+                .runs
+                .iter()
+                .all(|&(start, length)| b.count_range(start, start + length) == length as u64 + 1),
+            // The runs must cover every bit of the bitmap
+            (Container::Bitmap(b), Container::Run(r)) => {
+                let covered: u64 = r
+                    .runs
+                    .iter()
+                    .map(|&(start, length)| b.count_range(start, start + length))
+                    .sum();
+                covered == b.len()
+            }
+            // Only reachable for unusually small Bitmap/Run containers
+            (Container::Bitmap(_) | Container::Run(_), Container::Array(a)) => {
+                a.contains_each(self.iter()).all(|found| found)
             }
         }
     }
@@ -1931,6 +3618,35 @@ impl Container {
         }
     }
 
+    // Serialization
+
+    /// Returns the size in bytes of this container's data in the portable format
+    fn serialized_data_size(&self) -> usize {
+        match self {
+            Container::Run(run) => 2 + run.runs.len() * 4,
+            _ if self.len() <= SERIAL_MAX_ARRAY_CARDINALITY => self.len() as usize * 2,
+            _ => 1024 * 8,
+        }
+    }
+
+    /// Writes this container's data in the portable format.
+    ///
+    /// The layout of non-Run containers is chosen by cardinality, so a small
+    /// Bitmap is written as an Array and a large Array is written as a Bitmap.
+    fn serialize_data<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        match self {
+            Container::Run(run) => run.serialize_into(writer),
+            Container::Array(array) if array.len() <= SERIAL_MAX_ARRAY_CARDINALITY => {
+                array.serialize_into(writer)
+            }
+            Container::Array(array) => BitmapContainer::from_array(array).serialize_into(writer),
+            Container::Bitmap(bitmap) if bitmap.len() <= SERIAL_MAX_ARRAY_CARDINALITY => {
+                bitmap.to_array().serialize_into(writer)
+            }
+            Container::Bitmap(bitmap) => bitmap.serialize_into(writer),
+        }
+    }
+
     // Optimization helpers
 
     /// Optimizes this container by converting to the most efficient type
@@ -2073,6 +3789,43 @@ impl ArrayContainer {
         self.values.is_empty()
     }
 
+    /// Returns the number of values less than or equal to `value` (binary search)
+    fn rank(&self, value: u16) -> u64 {
+        self.values.partition_point(|&v| v <= value) as u64
+    }
+
+    /// Returns the `k`-th smallest value (0-based)
+    fn select(&self, k: u16) -> u16 {
+        self.values[k as usize]
+    }
+
+    /// Returns the smallest value greater than or equal to `value` (binary search)
+    fn next_at_or_after(&self, value: u16) -> Option<u16> {
+        let index = self.values.partition_point(|&v| v < value);
+        self.values.get(index).copied()
+    }
+
+    /// Returns the largest value less than or equal to `value` (binary search)
+    fn prev_at_or_before(&self, value: u16) -> Option<u16> {
+        let index = self.values.partition_point(|&v| v <= value);
+        index.checked_sub(1).map(|i| self.values[i])
+    }
+
+    /// Yields, for each value of an ascending sequence, whether it is in this
+    /// array, walking both sequences once
+    fn contains_each<'a>(
+        &'a self,
+        values: impl Iterator<Item = u16> + 'a,
+    ) -> impl Iterator<Item = bool> + 'a {
+        let mut i = 0;
+        values.map(move |value| {
+            while i < self.values.len() && self.values[i] < value {
+                i += 1;
+            }
+            self.values.get(i) == Some(&value)
+        })
+    }
+
     /// Returns the union of two array containers
     fn union(&self, other: &ArrayContainer) -> ArrayContainer {
         let mut result = Vec::with_capacity(self.values.len() + other.values.len());
@@ -2107,27 +3860,12 @@ impl ArrayContainer {
 
     /// Returns the intersection of two array containers (None if empty)
     fn intersection(&self, other: &ArrayContainer) -> Option<ArrayContainer> {
-        let mut result = Vec::new();
-        let mut i = 0;
-        let mut j = 0;
-
-        // Find common elements in two sorted arrays
-        while i < self.values.len() && j < other.values.len() {
-            match self.values[i].cmp(&other.values[j]) {
-                std::cmp::Ordering::Less => i += 1,
-                std::cmp::Ordering::Equal => {
-                    result.push(self.values[i]);
-                    i += 1;
-                    j += 1;
-                }
-                std::cmp::Ordering::Greater => j += 1,
-            }
-        }
+        let values = kernels::intersect_arrays(&self.values, &other.values);
 
-        if result.is_empty() {
+        if values.is_empty() {
             None
         } else {
-            Some(ArrayContainer { values: result })
+            Some(ArrayContainer { values })
         }
     }
 
@@ -2168,6 +3906,34 @@ impl ArrayContainer {
         self.values.capacity() * std::mem::size_of::<u16>()
     }
 
+    /// Writes the values as little-endian u16s (portable format Array layout)
+    fn serialize_into<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let mut bytes = Vec::with_capacity(self.values.len() * 2);
+        for &value in &self.values {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        writer.write_all(&bytes)
+    }
+
+    /// Reads `cardinality` values in the portable format Array layout
+    fn deserialize_from<R: Read>(reader: &mut R, cardinality: u64) -> io::Result<Self> {
+        let mut bytes = vec![0u8; cardinality as usize * 2];
+        reader.read_exact(&mut bytes)?;
+
+        let values: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
+            .collect();
+
+        if values.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(invalid_data(
+                "array container values are not strictly increasing",
+            ));
+        }
+
+        Ok(ArrayContainer { values })
+    }
+
     /// Returns the symmetric difference of two array containers (None if empty)
     fn symmetric_difference(&self, other: &ArrayContainer) -> Option<ArrayContainer> {
         let mut result = Vec::new();
@@ -2223,6 +3989,205 @@ impl BitmapContainer {
         bitmap
     }
 
+    /// Creates a bitmap container from a run container, filling whole words
+    /// per run instead of setting bits one at a time
+    fn from_run(run: &RunContainer) -> Self {
+        let mut bitmap = Self::new();
+        bitmap.set_runs(run);
+        bitmap
+    }
+
+    /// Applies `op(word, mask)` to every word overlapping `[start, end]`,
+    /// where `mask` selects the bits of the word inside the range, keeping the
+    /// cardinality in sync
+    fn update_range(&mut self, start: u16, end: u16, op: impl Fn(u64, u64) -> u64) {
+        for (index, mask) in Self::range_masks(start, end) {
+            let old = self.bits[index];
+            let new = op(old, mask);
+            self.cardinality = self.cardinality + new.count_ones() as u64 - old.count_ones() as u64;
+            self.bits[index] = new;
+        }
+    }
+
+    /// Counts the values in `[start, end]` without touching words outside it
+    fn count_range(&self, start: u16, end: u16) -> u64 {
+        Self::range_masks(start, end)
+            .map(|(index, mask)| (self.bits[index] & mask).count_ones() as u64)
+            .sum()
+    }
+
+    /// Yields `(word_index, mask)` for every word overlapping `[start, end]`,
+    /// where `mask` selects the bits of the word inside the range
+    fn range_masks(start: u16, end: u16) -> impl Iterator<Item = (usize, u64)> {
+        let (first, first_bit) = Self::position(start);
+        let (last, last_bit) = Self::position(end);
+
+        (first..=last).map(move |index| {
+            let low = if index == first { first_bit } else { 0 };
+            let high = if index == last { last_bit } else { 63 };
+            (index, (u64::MAX << low) & (u64::MAX >> (63 - high)))
+        })
+    }
+
+    /// Sets every value covered by the runs
+    fn set_runs(&mut self, run: &RunContainer) {
+        for &(start, length) in &run.runs {
+            self.update_range(start, start + length, |word, mask| word | mask);
+        }
+    }
+
+    /// Clears every value covered by the runs
+    fn clear_runs(&mut self, run: &RunContainer) {
+        for &(start, length) in &run.runs {
+            self.update_range(start, start + length, |word, mask| word & !mask);
+        }
+    }
+
+    /// Flips every value covered by the runs
+    fn flip_runs(&mut self, run: &RunContainer) {
+        for &(start, length) in &run.runs {
+            self.update_range(start, start + length, |word, mask| word ^ mask);
+        }
+    }
+
+    /// Calls `f(start, end)` for each maximal run of set bits, in order
+    fn for_each_run(&self, mut f: impl FnMut(u16, u16)) {
+        let mut run_start: Option<u32> = None;
+        for (index, &word) in self.bits.iter().enumerate() {
+            let base = index as u32 * 64;
+            let mut bit = 0u32;
+            while bit < 64 {
+                let rest = word >> bit;
+                match run_start {
+                    None => {
+                        if rest == 0 {
+                            break;
+                        }
+                        bit += rest.trailing_zeros();
+                        run_start = Some(base + bit);
+                    }
+                    Some(start) => {
+                        // Zeros shifted in from the top end the count at bit 63
+                        bit += (!rest).trailing_zeros();
+                        if bit < 64 {
+                            f(start as u16, (base + bit - 1) as u16);
+                            run_start = None;
+                        }
+                    }
+                }
+            }
+        }
+        if let Some(start) = run_start {
+            f(start as u16, u16::MAX);
+        }
+    }
+
+    /// Counts the runs of set bits a word at a time, without decoding them
+    fn count_runs(&self) -> usize {
+        let mut runs = 0;
+        let mut carry = 0;
+        for &word in self.bits.iter() {
+            // A run starts at every set bit whose lower neighbour is clear
+            runs += (word & !((word << 1) | carry)).count_ones() as usize;
+            carry = word >> 63;
+        }
+        runs
+    }
+
+    /// Recomputes the cardinality after lazy updates
+    fn recount(&mut self) {
+        self.cardinality = kernels::popcount(&self.bits);
+    }
+
+    /// ORs a container into the words, leaving the cardinality stale until
+    /// `recount`
+    fn union_lazy(&mut self, container: &Container) {
+        match container {
+            Container::Array(array) => {
+                for &value in &array.values {
+                    let (index, bit) = Self::position(value);
+                    self.bits[index] |= 1u64 << bit;
+                }
+            }
+            Container::Bitmap(bitmap) => {
+                for (word, other) in self.bits.iter_mut().zip(bitmap.bits.iter()) {
+                    *word |= other;
+                }
+            }
+            Container::Run(run) => {
+                for &(start, length) in &run.runs {
+                    for (index, mask) in Self::range_masks(start, start + length) {
+                        self.bits[index] |= mask;
+                    }
+                }
+            }
+        }
+    }
+
+    /// XORs a container into the words, leaving the cardinality stale until
+    /// `recount`
+    fn symmetric_difference_lazy(&mut self, container: &Container) {
+        match container {
+            Container::Array(array) => {
+                for &value in &array.values {
+                    let (index, bit) = Self::position(value);
+                    self.bits[index] ^= 1u64 << bit;
+                }
+            }
+            Container::Bitmap(bitmap) => {
+                for (word, other) in self.bits.iter_mut().zip(bitmap.bits.iter()) {
+                    *word ^= other;
+                }
+            }
+            Container::Run(run) => {
+                for &(start, length) in &run.runs {
+                    for (index, mask) in Self::range_masks(start, start + length) {
+                        self.bits[index] ^= mask;
+                    }
+                }
+            }
+        }
+    }
+
+    /// ANDs a container into the words, leaving the cardinality stale until
+    /// `recount`. Returns false once no bits remain.
+    fn intersect_lazy(&mut self, container: &Container) -> bool {
+        match container {
+            Container::Array(array) => {
+                let mut kept = [0u64; 1024];
+                for &value in &array.values {
+                    let (index, bit) = Self::position(value);
+                    kept[index] |= self.bits[index] & (1u64 << bit);
+                }
+                *self.bits = kept;
+            }
+            Container::Bitmap(bitmap) => {
+                for (word, other) in self.bits.iter_mut().zip(bitmap.bits.iter()) {
+                    *word &= other;
+                }
+            }
+            Container::Run(run) => {
+                // Clear the gaps between runs, including before the first
+                // and after the last
+                let mut next = 0u32;
+                for &(start, length) in &run.runs {
+                    if u32::from(start) > next {
+                        for (index, mask) in Self::range_masks(next as u16, start - 1) {
+                            self.bits[index] &= !mask;
+                        }
+                    }
+                    next = u32::from(start) + u32::from(length) + 1;
+                }
+                if next <= u32::from(u16::MAX) {
+                    for (index, mask) in Self::range_masks(next as u16, u16::MAX) {
+                        self.bits[index] &= !mask;
+                    }
+                }
+            }
+        }
+        self.bits.iter().any(|&word| word != 0)
+    }
+
     /// Helper: calculates which u64 and which bit within it for a value
     #[inline]
     fn position(value: u16) -> (usize, usize) {
@@ -2286,12 +4251,121 @@ impl BitmapContainer {
         self.cardinality == 0
     }
 
+    /// Returns the number of values less than or equal to `value` (popcount over words)
+    fn rank(&self, value: u16) -> u64 {
+        let (index, bit) = Self::position(value);
+
+        let full_words: u64 = self.bits[..index]
+            .iter()
+            .map(|w| w.count_ones() as u64)
+            .sum();
+
+        // Keep bits 0..=bit of the word containing value
+        let mask = u64::MAX >> (63 - bit);
+        full_words + (self.bits[index] & mask).count_ones() as u64
+    }
+
+    /// Returns the `k`-th smallest value (0-based)
+    fn select(&self, k: u16) -> u16 {
+        let mut remaining = k as u32;
+
+        for (index, &word) in self.bits.iter().enumerate() {
+            let ones = word.count_ones();
+            if remaining < ones {
+                // Clear the `remaining` lowest set bits, then take the next one
+                let mut word = word;
+                for _ in 0..remaining {
+                    word &= word - 1;
+                }
+                return (index * 64) as u16 + word.trailing_zeros() as u16;
+            }
+            remaining -= ones;
+        }
+
+        unreachable!("select index out of bounds for bitmap container")
+    }
+
+    /// Returns the smallest value (container must be non-empty)
+    fn min(&self) -> u16 {
+        self.next_at_or_after(0)
+            .expect("bitmap container must be non-empty")
+    }
+
+    /// Returns the largest value (container must be non-empty)
+    fn max(&self) -> u16 {
+        self.prev_at_or_before(u16::MAX)
+            .expect("bitmap container must be non-empty")
+    }
+
+    /// Returns the smallest value greater than or equal to `value`
+    fn next_at_or_after(&self, value: u16) -> Option<u16> {
+        let (index, bit) = Self::position(value);
+
+        // Drop bits below `value` in its own word
+        let word = self.bits[index] & (u64::MAX << bit);
+        if word != 0 {
+            return Some((index * 64) as u16 + word.trailing_zeros() as u16);
+        }
+
+        self.bits[index + 1..]
+            .iter()
+            .position(|&w| w != 0)
+            .map(|offset| {
+                let next = index + 1 + offset;
+                (next * 64) as u16 + self.bits[next].trailing_zeros() as u16
+            })
+    }
+
+    /// Returns the largest value less than or equal to `value`
+    fn prev_at_or_before(&self, value: u16) -> Option<u16> {
+        let (index, bit) = Self::position(value);
+
+        // Drop bits above `value` in its own word
+        let word = self.bits[index] & (u64::MAX >> (63 - bit));
+        if word != 0 {
+            return Some((index * 64) as u16 + 63 - word.leading_zeros() as u16);
+        }
+
+        self.bits[..index]
+            .iter()
+            .rposition(|&w| w != 0)
+            .map(|prev| (prev * 64) as u16 + 63 - self.bits[prev].leading_zeros() as u16)
+    }
+
     /// Returns the heap memory used by this container in bytes
     fn heap_memory(&self) -> usize {
         // Box<[u64; 1024]> allocates 1024 * 8 bytes on heap
         1024 * std::mem::size_of::<u64>()
     }
 
+    /// Writes the 1024 words as little-endian u64s (portable format Bitmap layout)
+    fn serialize_into<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let mut bytes = Vec::with_capacity(1024 * 8);
+        for &word in self.bits.iter() {
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+        writer.write_all(&bytes)
+    }
+
+    /// Reads 1024 words in the portable format Bitmap layout, verifying that
+    /// the number of set bits matches `cardinality`
+    fn deserialize_from<R: Read>(reader: &mut R, cardinality: u64) -> io::Result<Self> {
+        let mut bytes = vec![0u8; 1024 * 8];
+        reader.read_exact(&mut bytes)?;
+
+        let mut bitmap = Self::new();
+        for (word, chunk) in bitmap.bits.iter_mut().zip(bytes.chunks_exact(8)) {
+            *word = u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+        bitmap.cardinality = kernels::popcount(&bitmap.bits);
+
+        if bitmap.cardinality != cardinality {
+            return Err(invalid_data("bitmap container cardinality mismatch"));
+        }
+
+        Ok(bitmap)
+    }
+
     /// Converts to array container (for when cardinality becomes small)
     fn to_array(&self) -> ArrayContainer {
         let mut values = Vec::with_capacity(self.cardinality as usize);
@@ -2314,12 +4388,8 @@ impl BitmapContainer {
     fn union(&self, other: &BitmapContainer) -> BitmapContainer {
         let mut result = Self::new();
 
-        for i in 0..1024 {
-            result.bits[i] = self.bits[i] | other.bits[i];
-        }
-
-        // Calculate cardinality
-        result.cardinality = result.bits.iter().map(|w| w.count_ones() as u64).sum();
+        result.cardinality =
+            kernels::combine_words(WordOp::Or, &self.bits, &other.bits, &mut result.bits);
 
         result
     }
@@ -2328,12 +4398,8 @@ impl BitmapContainer {
     fn intersection(&self, other: &BitmapContainer) -> Option<BitmapContainer> {
         let mut result = Self::new();
 
-        for i in 0..1024 {
-            result.bits[i] = self.bits[i] & other.bits[i];
-        }
-
-        // Calculate cardinality
-        result.cardinality = result.bits.iter().map(|w| w.count_ones() as u64).sum();
+        result.cardinality =
+            kernels::combine_words(WordOp::And, &self.bits, &other.bits, &mut result.bits);
 
         if result.cardinality == 0 {
             None
@@ -2346,12 +4412,8 @@ impl BitmapContainer {
     fn difference(&self, other: &BitmapContainer) -> Option<BitmapContainer> {
         let mut result = Self::new();
 
-        for i in 0..1024 {
-            result.bits[i] = self.bits[i] & !other.bits[i];
-        }
-
-        // Calculate cardinality
-        result.cardinality = result.bits.iter().map(|w| w.count_ones() as u64).sum();
+        result.cardinality =
+            kernels::combine_words(WordOp::AndNot, &self.bits, &other.bits, &mut result.bits);
 
         if result.cardinality == 0 {
             None
@@ -2364,12 +4426,8 @@ impl BitmapContainer {
     fn symmetric_difference(&self, other: &BitmapContainer) -> Option<BitmapContainer> {
         let mut result = Self::new();
 
-        for i in 0..1024 {
-            result.bits[i] = self.bits[i] ^ other.bits[i];
-        }
-
-        // Calculate cardinality
-        result.cardinality = result.bits.iter().map(|w| w.count_ones() as u64).sum();
+        result.cardinality =
+            kernels::combine_words(WordOp::Xor, &self.bits, &other.bits, &mut result.bits);
 
         if result.cardinality == 0 {
             None
@@ -2379,340 +4437,1538 @@ impl BitmapContainer {
     }
 }
 
-impl RunContainer {
-    /// Returns the heap memory used by this container in bytes
-    fn heap_memory(&self) -> usize {
-        // Vec heap allocation: capacity * element_size
-        self.runs.capacity() * std::mem::size_of::<(u16, u16)>()
-    }
+/// Per-value counters for one container key, stored bit-sliced: plane `j`
+/// holds bit `j` of every value's count, so adding a container is a
+/// ripple-carry across planes covering 64 values per word operation
+#[derive(Default)]
+struct SlicedCounter {
+    planes: Vec<Box<[u64; 1024]>>,
+}
 
-    /// Creates a run container from an array container
-    fn from_array(array: &ArrayContainer) -> Self {
-        if array.values.is_empty() {
-            return RunContainer { runs: Vec::new() };
+impl SlicedCounter {
+    /// Zeroes the counters and sizes them to hold counts up to `max`
+    fn reset(&mut self, max: u64) {
+        let bits = (u64::BITS - max.leading_zeros()) as usize;
+        self.planes.truncate(bits);
+        for plane in &mut self.planes {
+            plane.fill(0);
         }
+        self.planes.resize_with(bits, || Box::new([0u64; 1024]));
+    }
 
-        let mut runs = Vec::new();
-        let mut run_start = array.values[0];
-        let mut run_length = 1u16;
-
-        for i in 1..array.values.len() {
-            if array.values[i] == array.values[i - 1] + 1 {
-                // Continue current run (use saturating_add to prevent overflow with full container)
-                run_length = run_length.saturating_add(1);
-            } else {
-                // End current run, start new one (store length-1)
-                runs.push((run_start, run_length - 1));
-                run_start = array.values[i];
-                run_length = 1;
+    /// Adds `weight` to the count of every value in the container
+    fn add(&mut self, container: &Container, weight: u32) {
+        match container {
+            Container::Array(array) => {
+                // Gather the values sharing a word into one mask first
+                let mut values = array.values.iter().peekable();
+                while let Some(&value) = values.next() {
+                    let (index, bit) = BitmapContainer::position(value);
+                    let mut mask = 1u64 << bit;
+                    while let Some(&&next) = values.peek() {
+                        let (next_index, next_bit) = BitmapContainer::position(next);
+                        if next_index != index {
+                            break;
+                        }
+                        mask |= 1u64 << next_bit;
+                        values.next();
+                    }
+                    self.add_word(index, mask, weight);
+                }
             }
-        }
+            Container::Bitmap(bitmap) => {
+                for (index, &word) in bitmap.bits.iter().enumerate() {
+                    if word != 0 {
+                        self.add_word(index, word, weight);
+                    }
+                }
+            }
+            Container::Run(run) => {
+                for &(start, length) in &run.runs {
+                    for (index, mask) in BitmapContainer::range_masks(start, start + length) {
+                        self.add_word(index, mask, weight);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Adds `weight` to the counts of the values selected by `mask` in word
+    /// `index`
+    fn add_word(&mut self, index: usize, mask: u64, weight: u32) {
+        let weight = u64::from(weight);
+        let mut carry = 0u64;
+        for (j, plane) in self.planes.iter_mut().enumerate() {
+            if carry == 0 && weight >> j == 0 {
+                break;
+            }
+            let addend = if (weight >> j) & 1 == 1 { mask } else { 0 };
+            let word = plane[index];
+            plane[index] = word ^ addend ^ carry;
+            carry = (word & addend) | (carry & (word ^ addend));
+        }
+    }
+
+    /// Writes into `scratch` the values whose count is at least `threshold`,
+    /// comparing every word's 64 counts from the top plane down. Leaves the
+    /// scratch cardinality stale.
+    fn at_least(&self, threshold: u64, scratch: &mut BitmapContainer) {
+        for (index, out) in scratch.bits.iter_mut().enumerate() {
+            let (mut greater, mut equal) = (0u64, u64::MAX);
+            for (j, plane) in self.planes.iter().enumerate().rev() {
+                let word = plane[index];
+                if (threshold >> j) & 1 == 1 {
+                    equal &= word;
+                } else {
+                    greater |= equal & word;
+                    equal &= !word;
+                }
+            }
+            *out = greater | equal;
+        }
+    }
+}
+
+impl RunContainer {
+    /// Returns the heap memory used by this container in bytes
+    fn heap_memory(&self) -> usize {
+        // Vec heap allocation: capacity * element_size
+        self.runs.capacity() * std::mem::size_of::<(u16, u16)>()
+    }
+
+    /// Writes the run count followed by (start, length_minus_1) pairs
+    /// (portable format Run layout)
+    fn serialize_into<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let mut bytes = Vec::with_capacity(2 + self.runs.len() * 4);
+        bytes.extend_from_slice(&(self.runs.len() as u16).to_le_bytes());
+        for &(start, length) in &self.runs {
+            bytes.extend_from_slice(&start.to_le_bytes());
+            bytes.extend_from_slice(&length.to_le_bytes());
+        }
+        writer.write_all(&bytes)
+    }
+
+    /// Reads runs in the portable format Run layout, verifying that they are
+    /// sorted, non-overlapping, non-adjacent and add up to `cardinality`
+    fn deserialize_from<R: Read>(reader: &mut R, cardinality: u64) -> io::Result<Self> {
+        let num_runs = read_u16(reader)? as usize;
+        let mut bytes = vec![0u8; num_runs * 4];
+        reader.read_exact(&mut bytes)?;
+
+        let mut runs = Vec::with_capacity(num_runs);
+        let mut total = 0u64;
+        let mut next_allowed_start = 0u32;
+
+        for chunk in bytes.chunks_exact(4) {
+            let start = u16::from_le_bytes([chunk[0], chunk[1]]);
+            let length = u16::from_le_bytes([chunk[2], chunk[3]]);
+            // length is (actual_length - 1), so end = start + length
+            let end = start as u32 + length as u32;
+
+            if end > u16::MAX as u32 {
+                return Err(invalid_data("run extends past the end of the container"));
+            }
+            if (start as u32) < next_allowed_start {
+                return Err(invalid_data("runs are unsorted, overlapping or adjacent"));
+            }
+
+            next_allowed_start = end + 2;
+            total += length as u64 + 1;
+            runs.push((start, length));
+        }
+
+        if runs.is_empty() || total != cardinality {
+            return Err(invalid_data("run container cardinality mismatch"));
+        }
+
+        Ok(RunContainer { runs })
+    }
+
+    /// Creates a run container from an array container
+    fn from_array(array: &ArrayContainer) -> Self {
+        if array.values.is_empty() {
+            return RunContainer { runs: Vec::new() };
+        }
+
+        let mut runs = Vec::new();
+        let mut run_start = array.values[0];
+        let mut run_length = 1u16;
+
+        for i in 1..array.values.len() {
+            if array.values[i] == array.values[i - 1] + 1 {
+                // Continue current run (use saturating_add to prevent overflow with full container)
+                run_length = run_length.saturating_add(1);
+            } else {
+                // End current run, start new one (store length-1)
+                runs.push((run_start, run_length - 1));
+                run_start = array.values[i];
+                run_length = 1;
+            }
+        }
 
         // Add the last run (store length-1)
         runs.push((run_start, run_length - 1));
 
-        RunContainer { runs }
+        RunContainer { runs }
+    }
+
+    /// Converts run container to array container
+    fn to_array(&self) -> ArrayContainer {
+        let capacity: usize = self.runs.iter().map(|(_, len)| (*len as usize) + 1).sum();
+        let mut values = Vec::with_capacity(capacity);
+
+        for &(start, length) in &self.runs {
+            // length is (actual_length - 1), so iterate 0..=length to get all values
+            for offset in 0..=length {
+                values.push(start + offset);
+            }
+        }
+
+        ArrayContainer { values }
+    }
+
+    /// Inserts a value, maintaining run invariants
+    fn insert(&mut self, value: u16) -> bool {
+        // Find where this value should go
+        let pos = self.runs.binary_search_by_key(&value, |(start, _)| *start);
+
+        match pos {
+            Ok(_index) => {
+                // Value equals a run start, already present
+                false
+            }
+            Err(index) => {
+                // Check if value is within or adjacent to previous run
+                if index > 0 {
+                    let (start, length) = self.runs[index - 1];
+                    // length is (actual_length - 1), so run_end = start + length
+                    let run_end = start + length;
+
+                    if value <= run_end {
+                        // Value already in previous run
+                        return false;
+                    } else if value == run_end + 1 {
+                        // Extend previous run
+                        self.runs[index - 1].1 += 1;
+
+                        // Check if we can merge with next run
+                        if index < self.runs.len() {
+                            let (next_start, next_length) = self.runs[index];
+                            if value + 1 == next_start {
+                                // Merge runs (add next_length + 1 since length encoding is length_minus_1)
+                                self.runs[index - 1].1 += next_length + 1;
+                                self.runs.remove(index);
+                            }
+                        }
+
+                        return true;
+                    }
+                }
+
+                // Check if value is adjacent to next run
+                if index < self.runs.len() {
+                    let (start, _) = self.runs[index];
+                    if value + 1 == start {
+                        // Extend next run backwards
+                        self.runs[index].0 = value;
+                        self.runs[index].1 += 1;
+                        return true;
+                    }
+                }
+
+                // Insert new run of length 1 (store as 0 in length-1 encoding)
+                self.runs.insert(index, (value, 0));
+                true
+            }
+        }
+    }
+
+    /// Checks if a value exists
+    fn contains(&self, value: u16) -> bool {
+        for &(start, length) in &self.runs {
+            // length is (actual_length - 1), so end = start + length
+            let end = start + length;
+            if value >= start && value <= end {
+                return true;
+            }
+            if value < start {
+                return false; // Runs are sorted, no need to continue
+            }
+        }
+        false
+    }
+
+    /// Removes a value if present
+    fn remove(&mut self, value: u16) -> bool {
+        for i in 0..self.runs.len() {
+            let (start, length) = self.runs[i];
+            // length is (actual_length - 1), so end = start + length
+            let end = start + length;
+
+            if value >= start && value <= end {
+                // Value is in this run
+                if length == 0 {
+                    // Remove the entire run (length=0 means 1 value)
+                    self.runs.remove(i);
+                } else if value == start {
+                    // Remove from start of run
+                    self.runs[i] = (start + 1, length - 1);
+                } else if value == end {
+                    // Remove from end of run
+                    self.runs[i].1 -= 1;
+                } else {
+                    // Split the run
+                    // First run: [start, value-1], length = (value - start)
+                    // Second run: [value+1, end], length = (end - value - 1)
+                    let first_length = value - start - 1;
+                    let second_start = value + 1;
+                    let second_length = end - value - 1;
+
+                    self.runs[i] = (start, first_length);
+                    self.runs.insert(i + 1, (second_start, second_length));
+                }
+                return true;
+            }
+
+            if value < start {
+                return false; // Runs are sorted
+            }
+        }
+        false
+    }
+
+    /// Returns the number of values in the container
+    fn len(&self) -> u64 {
+        // length is (actual_length - 1), so add 1 to get actual length
+        self.runs
+            .iter()
+            .map(|(_, length)| (*length as u64) + 1)
+            .sum()
+    }
+
+    /// Checks if the container is empty
+    fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+
+    /// Returns the number of values less than or equal to `value` (prefix over runs)
+    fn rank(&self, value: u16) -> u64 {
+        let mut count = 0u64;
+
+        for &(start, length) in &self.runs {
+            if value < start {
+                break; // Runs are sorted
+            }
+            // length is (actual_length - 1), so end = start + length
+            let end = start + length;
+            if value <= end {
+                return count + (value - start) as u64 + 1;
+            }
+            count += length as u64 + 1;
+        }
+
+        count
+    }
+
+    /// Returns the `k`-th smallest value (0-based)
+    fn select(&self, k: u16) -> u16 {
+        let mut remaining = k as u32;
+
+        for &(start, length) in &self.runs {
+            if remaining <= length as u32 {
+                return start + remaining as u16;
+            }
+            // length is (actual_length - 1), so the run holds length + 1 values
+            remaining -= length as u32 + 1;
+        }
+
+        unreachable!("select index out of bounds for run container")
+    }
+
+    /// Returns the smallest value greater than or equal to `value`
+    fn next_at_or_after(&self, value: u16) -> Option<u16> {
+        // Number of runs starting at or before value
+        let index = self.runs.partition_point(|&(start, _)| start <= value);
+
+        if index > 0 {
+            let (start, length) = self.runs[index - 1];
+            // length is (actual_length - 1), so end = start + length
+            if value <= start + length {
+                return Some(value);
+            }
+        }
+
+        self.runs.get(index).map(|&(start, _)| start)
+    }
+
+    /// Returns the largest value less than or equal to `value`
+    fn prev_at_or_before(&self, value: u16) -> Option<u16> {
+        // Number of runs starting at or before value
+        let index = self.runs.partition_point(|&(start, _)| start <= value);
+
+        index.checked_sub(1).map(|i| {
+            let (start, length) = self.runs[i];
+            value.min(start + length)
+        })
+    }
+
+    /// Returns the union of two run containers
+    ///
+    /// Merges runs from both sides in start order, coalescing runs that
+    /// overlap or touch. O(r1 + r2), never materializes individual values.
+    fn union(&self, other: &RunContainer) -> RunContainer {
+        let mut runs = Vec::with_capacity(self.runs.len() + other.runs.len());
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.runs.len() || j < other.runs.len() {
+            let take_self =
+                j >= other.runs.len() || (i < self.runs.len() && self.runs[i].0 <= other.runs[j].0);
+            let (start, length) = if take_self {
+                i += 1;
+                self.runs[i - 1]
+            } else {
+                j += 1;
+                other.runs[j - 1]
+            };
+            Self::push_merged(&mut runs, start, start + length);
+        }
+
+        RunContainer { runs }
+    }
+
+    /// Returns the intersection of two run containers (None if empty)
+    ///
+    /// Walks both run lists once, emitting the overlap of each pair of runs
+    /// and advancing whichever run ends first.
+    fn intersection(&self, other: &RunContainer) -> Option<RunContainer> {
+        let mut runs = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.runs.len() && j < other.runs.len() {
+            let (start_a, end_a) = Self::bounds(self.runs[i]);
+            let (start_b, end_b) = Self::bounds(other.runs[j]);
+
+            let start = start_a.max(start_b);
+            let end = end_a.min(end_b);
+            if start <= end {
+                runs.push((start, end - start));
+            }
+
+            if end_a < end_b {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self::non_empty(runs)
+    }
+
+    /// Returns the difference of two run containers (self - other) (None if empty)
+    ///
+    /// Each run of `self` is cut by the runs of `other` that overlap it.
+    fn difference(&self, other: &RunContainer) -> Option<RunContainer> {
+        let mut runs = Vec::new();
+        let mut j = 0;
+
+        for &run in &self.runs {
+            let (start, end) = Self::bounds(run);
+            let mut start = start as u32;
+            let end = end as u32;
+
+            // Runs of other that end before this run can't cut it (or any later run)
+            while j < other.runs.len() && (Self::bounds(other.runs[j]).1 as u32) < start {
+                j += 1;
+            }
+
+            // A run of other may span several runs of self, so don't consume it here
+            let mut k = j;
+            while start <= end {
+                match other.runs.get(k).map(|&r| Self::bounds(r)) {
+                    Some((cut_start, cut_end)) if cut_start as u32 <= end => {
+                        if cut_start as u32 > start {
+                            runs.push((start as u16, (cut_start as u32 - 1 - start) as u16));
+                        }
+                        start = cut_end as u32 + 1;
+                        k += 1;
+                    }
+                    _ => {
+                        runs.push((start as u16, (end - start) as u16));
+                        break;
+                    }
+                }
+            }
+        }
+
+        Self::non_empty(runs)
+    }
+
+    /// Returns the symmetric difference of two run containers (None if empty)
+    ///
+    /// Each run `[start, end]` toggles membership at `start` and `end + 1`.
+    /// Merging the toggle points of both sides (equal points cancel out) and
+    /// pairing them up yields the runs of the result.
+    fn symmetric_difference(&self, other: &RunContainer) -> Option<RunContainer> {
+        let toggles = |runs: &[(u16, u16)]| -> Vec<u32> {
+            runs.iter()
+                .flat_map(|&(start, length)| [start as u32, start as u32 + length as u32 + 1])
+                .collect()
+        };
+        let (a, b) = (toggles(&self.runs), toggles(&other.runs));
+
+        let mut merged = Vec::with_capacity(a.len() + b.len());
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() && j < b.len() {
+            match a[i].cmp(&b[j]) {
+                std::cmp::Ordering::Less => {
+                    merged.push(a[i]);
+                    i += 1;
+                }
+                std::cmp::Ordering::Greater => {
+                    merged.push(b[j]);
+                    j += 1;
+                }
+                std::cmp::Ordering::Equal => {
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        merged.extend_from_slice(&a[i..]);
+        merged.extend_from_slice(&b[j..]);
+
+        let runs = merged
+            .chunks_exact(2)
+            .map(|pair| (pair[0] as u16, (pair[1] - 1 - pair[0]) as u16))
+            .collect();
+        Self::non_empty(runs)
+    }
+
+    /// Returns the number of values in both run containers
+    ///
+    /// Same walk as `intersection`, summing the overlaps instead of storing them.
+    fn intersection_len(&self, other: &RunContainer) -> u64 {
+        let mut count = 0;
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.runs.len() && j < other.runs.len() {
+            let (start_a, end_a) = Self::bounds(self.runs[i]);
+            let (start_b, end_b) = Self::bounds(other.runs[j]);
+
+            let start = start_a.max(start_b);
+            let end = end_a.min(end_b);
+            if start <= end {
+                count += (end - start) as u64 + 1;
+            }
+
+            if end_a < end_b {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        count
+    }
+
+    /// Checks whether two run containers overlap, stopping at the first overlap
+    fn intersects(&self, other: &RunContainer) -> bool {
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.runs.len() && j < other.runs.len() {
+            let (start_a, end_a) = Self::bounds(self.runs[i]);
+            let (start_b, end_b) = Self::bounds(other.runs[j]);
+
+            if start_a.max(start_b) <= end_a.min(end_b) {
+                return true;
+            }
+
+            if end_a < end_b {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        false
+    }
+
+    /// Checks whether every run of `self` is covered by the runs of `other`
+    ///
+    /// A run may be covered by several adjacent runs of `other`, so coverage
+    /// is followed run by run rather than assuming runs are maximal.
+    fn is_subset(&self, other: &RunContainer) -> bool {
+        let mut j = 0;
+
+        for &run in &self.runs {
+            let (start, end) = Self::bounds(run);
+            let mut next = start as u32;
+
+            while next <= end as u32 {
+                while j < other.runs.len() && (Self::bounds(other.runs[j]).1 as u32) < next {
+                    j += 1;
+                }
+                match other.runs.get(j).map(|&r| Self::bounds(r)) {
+                    Some((cover_start, cover_end)) if cover_start as u32 <= next => {
+                        next = cover_end as u32 + 1;
+                    }
+                    _ => return false,
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Yields, for each value of an ascending sequence, whether it falls
+    /// inside a run, walking both sequences once
+    fn contains_each<'a>(
+        &'a self,
+        values: impl Iterator<Item = u16> + 'a,
+    ) -> impl Iterator<Item = bool> + 'a {
+        let mut i = 0;
+        values.map(move |value| {
+            while i < self.runs.len() && Self::bounds(self.runs[i]).1 < value {
+                i += 1;
+            }
+            self.runs.get(i).is_some_and(|&(start, _)| start <= value)
+        })
+    }
+
+    /// Returns the array values that fall inside (`inside == true`) or outside
+    /// the runs, walking both sorted sequences once (None if empty)
+    fn filter_array(&self, array: &ArrayContainer, inside: bool) -> Option<ArrayContainer> {
+        let values: Vec<u16> = array
+            .values
+            .iter()
+            .zip(self.contains_each(array.values.iter().copied()))
+            .filter(|&(_, contained)| contained == inside)
+            .map(|(&value, _)| value)
+            .collect();
+
+        if values.is_empty() {
+            None
+        } else {
+            Some(ArrayContainer { values })
+        }
+    }
+
+    /// Inverts the runs over `[start, end]`, keeping the runs outside the range
+    /// when `outside` is true and dropping them otherwise (None if empty)
+    fn invert_range(&self, start: u16, end: u16, outside: bool) -> Option<RunContainer> {
+        let mut runs = Vec::with_capacity(self.runs.len() + 1);
+        // First value of the range not yet known to be covered by a run
+        let mut gap = start as u32;
+
+        for &run in &self.runs {
+            let (run_start, run_end) = Self::bounds(run);
+            if run_end < start || run_start > end {
+                if run_start > end && gap <= end as u32 {
+                    Self::push_merged(&mut runs, gap as u16, end);
+                    gap = end as u32 + 1;
+                }
+                if outside {
+                    Self::push_merged(&mut runs, run_start, run_end);
+                }
+                continue;
+            }
+
+            if outside && run_start < start {
+                Self::push_merged(&mut runs, run_start, start - 1);
+            }
+            if gap < run_start as u32 {
+                Self::push_merged(&mut runs, gap as u16, run_start - 1);
+            }
+            gap = run_end as u32 + 1;
+            if outside && run_end > end {
+                Self::push_merged(&mut runs, end + 1, run_end);
+            }
+        }
+
+        if gap <= end as u32 {
+            Self::push_merged(&mut runs, gap as u16, end);
+        }
+        Self::non_empty(runs)
+    }
+
+    /// Converts into whichever container type stores these values in the
+    /// fewest bytes, keeping the Array/Bitmap threshold invariant
+    fn into_container(self) -> Container {
+        let cardinality = self.len() as usize;
+        let run_bytes = 2 + self.runs.len() * 4;
+
+        if cardinality < ARRAY_TO_BITMAP_THRESHOLD && cardinality * 2 < run_bytes {
+            Container::Array(self.to_array())
+        } else if cardinality >= ARRAY_TO_BITMAP_THRESHOLD && 1024 * 8 < run_bytes {
+            Container::Bitmap(BitmapContainer::from_run(&self))
+        } else {
+            Container::Run(self)
+        }
+    }
+
+    /// Returns the inclusive `(start, end)` of a run
+    #[inline]
+    fn bounds((start, length): (u16, u16)) -> (u16, u16) {
+        (start, start + length)
+    }
+
+    /// Appends `[start, end]` to runs sorted by start, merging it into the
+    /// last run if they overlap or touch
+    fn push_merged(runs: &mut Vec<(u16, u16)>, start: u16, end: u16) {
+        if let Some(last) = runs.last_mut() {
+            let last_end = last.0 as u32 + last.1 as u32;
+            if start as u32 <= last_end + 1 {
+                if end as u32 > last_end {
+                    last.1 = end - last.0;
+                }
+                return;
+            }
+        }
+        runs.push((start, end - start));
+    }
+
+    /// Wraps runs into a container, or None if there are none
+    fn non_empty(runs: Vec<(u16, u16)>) -> Option<RunContainer> {
+        if runs.is_empty() {
+            None
+        } else {
+            Some(RunContainer { runs })
+        }
+    }
+}
+
+// Serialization helpers
+
+/// Reads a little-endian u16
+fn read_u16<R: Read>(reader: &mut R) -> io::Result<u16> {
+    let mut bytes = [0u8; 2];
+    reader.read_exact(&mut bytes)?;
+    Ok(u16::from_le_bytes(bytes))
+}
+
+/// Reads a little-endian u32
+fn read_u32<R: Read>(reader: &mut R) -> io::Result<u32> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+/// Creates an `InvalidData` error for malformed serialized input
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+// Iterator implementation
+
+impl Iter<'_> {
+    /// Advances the iterator so that the next value yielded from the front is
+    /// the smallest remaining value greater than or equal to `value`
+    ///
+    /// Does nothing if the front has already moved past `value`. Values that
+    /// were yielded from the back are never yielded again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let mut bm = RoaringBitmap::new();
+    /// bm.extend_sparse([1, 5, 70_000, 200_000]);
+    ///
+    /// let mut iter = bm.iter();
+    /// iter.advance_to(6);
+    /// assert_eq!(iter.next(), Some(70_000));
+    /// assert_eq!(iter.len(), 1);
+    /// ```
+    ///
+    /// # Performance
+    ///
+    /// - Containers entirely below `value` are skipped by key without being decoded
+    /// - Array: O(log n) binary search
+    /// - Bitmap: O(1024) popcount over skipped words
+    /// - Run: O(r) over skipped runs
+    ///
+    /// # Use Cases
+    ///
+    /// - Merge-joins and leapfrog intersections across posting lists
+    pub fn advance_to(&mut self, value: u32) {
+        let (key, low) = RoaringBitmap::split(value);
+
+        loop {
+            if let Some((front_key, cursor)) = &mut self.front {
+                match (*front_key).cmp(&key) {
+                    std::cmp::Ordering::Greater => return,
+                    std::cmp::Ordering::Equal => {
+                        self.remaining -= cursor.advance_to(low);
+                        return;
+                    }
+                    std::cmp::Ordering::Less => {
+                        self.remaining -= cursor.len();
+                        self.front = None;
+                    }
+                }
+            }
+
+            // Skip untouched containers entirely below the target key
+            let rest = self.containers.as_slice();
+            let skip = rest.partition_point(|(k, _)| *k < key);
+            self.remaining -= rest[..skip].iter().map(|(_, c)| c.len()).sum::<u64>();
+            self.containers = rest[skip..].iter();
+
+            match self.containers.next() {
+                Some((next_key, container)) => self.front = Some((*next_key, container.iter())),
+                None => {
+                    // Only the container held by the back end is left
+                    if let Some((back_key, cursor)) = &mut self.back {
+                        match (*back_key).cmp(&key) {
+                            std::cmp::Ordering::Greater => {}
+                            std::cmp::Ordering::Equal => {
+                                self.remaining -= cursor.advance_to(low);
+                            }
+                            std::cmp::Ordering::Less => {
+                                self.remaining -= cursor.len();
+                                self.back = None;
+                            }
+                        }
+                    }
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Moves the back end so that the next value yielded from the back is the
+    /// largest remaining value less than or equal to `value`
+    ///
+    /// Mirror of `advance_to`, used to clip range iterators.
+    fn retreat_to(&mut self, value: u32) {
+        let (key, low) = RoaringBitmap::split(value);
+
+        loop {
+            if let Some((back_key, cursor)) = &mut self.back {
+                match (*back_key).cmp(&key) {
+                    std::cmp::Ordering::Less => return,
+                    std::cmp::Ordering::Equal => {
+                        self.remaining -= cursor.retreat_to(low);
+                        return;
+                    }
+                    std::cmp::Ordering::Greater => {
+                        self.remaining -= cursor.len();
+                        self.back = None;
+                    }
+                }
+            }
+
+            // Skip untouched containers entirely above the target key
+            let rest = self.containers.as_slice();
+            let keep = rest.partition_point(|(k, _)| *k <= key);
+            self.remaining -= rest[keep..].iter().map(|(_, c)| c.len()).sum::<u64>();
+            self.containers = rest[..keep].iter();
+
+            match self.containers.next_back() {
+                Some((prev_key, container)) => self.back = Some((*prev_key, container.iter())),
+                None => {
+                    // Only the container held by the front end is left
+                    if let Some((front_key, cursor)) = &mut self.front {
+                        match (*front_key).cmp(&key) {
+                            std::cmp::Ordering::Less => {}
+                            std::cmp::Ordering::Equal => {
+                                self.remaining -= cursor.retreat_to(low);
+                            }
+                            std::cmp::Ordering::Greater => {
+                                self.remaining -= cursor.len();
+                                self.front = None;
+                            }
+                        }
+                    }
+                    return;
+                }
+            }
+        }
+    }
+}
+
+impl Iterator for Iter<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        loop {
+            if let Some((key, cursor)) = &mut self.front {
+                if let Some(low) = cursor.next() {
+                    self.remaining -= 1;
+                    return Some(RoaringBitmap::combine(*key, low));
+                }
+            }
+
+            match self.containers.next() {
+                Some((key, container)) => self.front = Some((*key, container.iter())),
+                None => {
+                    // Drain the container held by the back end
+                    let (key, cursor) = self.back.as_mut()?;
+                    let low = cursor.next()?;
+                    self.remaining -= 1;
+                    return Some(RoaringBitmap::combine(*key, low));
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining as usize;
+        (remaining, Some(remaining))
+    }
+
+    fn last(mut self) -> Option<u32> {
+        self.next_back()
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    fn next_back(&mut self) -> Option<u32> {
+        loop {
+            if let Some((key, cursor)) = &mut self.back {
+                if let Some(low) = cursor.next_back() {
+                    self.remaining -= 1;
+                    return Some(RoaringBitmap::combine(*key, low));
+                }
+            }
+
+            match self.containers.next_back() {
+                Some((key, container)) => self.back = Some((*key, container.iter())),
+                None => {
+                    // Drain the container held by the front end
+                    let (key, cursor) = self.front.as_mut()?;
+                    let low = cursor.next_back()?;
+                    self.remaining -= 1;
+                    return Some(RoaringBitmap::combine(*key, low));
+                }
+            }
+        }
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+impl FusedIterator for Iter<'_> {}
+
+impl Iterator for IntoIter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        loop {
+            if let Some((key, cursor)) = &mut self.front {
+                if let Some(low) = cursor.next() {
+                    self.remaining -= 1;
+                    return Some(RoaringBitmap::combine(*key, low));
+                }
+            }
+
+            match self.containers.next() {
+                Some((key, container)) => self.front = Some((key, container.into_iter())),
+                None => {
+                    // Drain the container held by the back end
+                    let (key, cursor) = self.back.as_mut()?;
+                    let low = cursor.next()?;
+                    self.remaining -= 1;
+                    return Some(RoaringBitmap::combine(*key, low));
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining as usize;
+        (remaining, Some(remaining))
+    }
+
+    fn last(mut self) -> Option<u32> {
+        self.next_back()
+    }
+}
+
+impl DoubleEndedIterator for IntoIter {
+    fn next_back(&mut self) -> Option<u32> {
+        loop {
+            if let Some((key, cursor)) = &mut self.back {
+                if let Some(low) = cursor.next_back() {
+                    self.remaining -= 1;
+                    return Some(RoaringBitmap::combine(*key, low));
+                }
+            }
+
+            match self.containers.next_back() {
+                Some((key, container)) => self.back = Some((key, container.into_iter())),
+                None => {
+                    // Drain the container held by the front end
+                    let (key, cursor) = self.front.as_mut()?;
+                    let low = cursor.next_back()?;
+                    self.remaining -= 1;
+                    return Some(RoaringBitmap::combine(*key, low));
+                }
+            }
+        }
+    }
+}
+
+impl ExactSizeIterator for IntoIter {}
+
+impl FusedIterator for IntoIter {}
+
+impl ContainerIter<'_> {
+    /// Returns the number of values left between the two ends
+    fn len(&self) -> u64 {
+        match self {
+            ContainerIter::Array { values } => values.len() as u64,
+            ContainerIter::Bitmap { cursor, .. } => cursor.len,
+            ContainerIter::Run { cursor, .. } => cursor.len,
+        }
+    }
+
+    /// Skips values below `low` from the front, returning how many were skipped
+    fn advance_to(&mut self, low: u16) -> u64 {
+        match self {
+            ContainerIter::Array { values } => {
+                let rest = values.as_slice();
+                let skip = rest.partition_point(|&v| v < low);
+                *values = rest[skip..].iter();
+                skip as u64
+            }
+            ContainerIter::Bitmap { bits, cursor } => cursor.advance_to(bits, low),
+            ContainerIter::Run { runs, cursor } => cursor.advance_to(runs, low),
+        }
+    }
+
+    /// Drops values above `high` from the back, returning how many were dropped
+    fn retreat_to(&mut self, high: u16) -> u64 {
+        match self {
+            ContainerIter::Array { values } => {
+                let rest = values.as_slice();
+                let keep = rest.partition_point(|&v| v <= high);
+                *values = rest[..keep].iter();
+                (rest.len() - keep) as u64
+            }
+            ContainerIter::Bitmap { bits, cursor } => cursor.retreat_to(bits, high),
+            ContainerIter::Run { runs, cursor } => cursor.retreat_to(runs, high),
+        }
+    }
+}
+
+impl Iterator for ContainerIter<'_> {
+    type Item = u16;
+
+    fn next(&mut self) -> Option<u16> {
+        match self {
+            ContainerIter::Array { values } => values.next().copied(),
+            ContainerIter::Bitmap { bits, cursor } => cursor.next(bits),
+            ContainerIter::Run { runs, cursor } => cursor.next(runs),
+        }
+    }
+}
+
+impl DoubleEndedIterator for ContainerIter<'_> {
+    fn next_back(&mut self) -> Option<u16> {
+        match self {
+            ContainerIter::Array { values } => values.next_back().copied(),
+            ContainerIter::Bitmap { bits, cursor } => cursor.next_back(bits),
+            ContainerIter::Run { runs, cursor } => cursor.next_back(runs),
+        }
+    }
+}
+
+impl IntoIterator for Container {
+    type Item = u16;
+    type IntoIter = ContainerIntoIter;
+
+    fn into_iter(self) -> ContainerIntoIter {
+        match self {
+            Container::Array(array) => ContainerIntoIter::Array {
+                values: array.values.into_iter(),
+            },
+            Container::Bitmap(bitmap) => ContainerIntoIter::Bitmap {
+                cursor: BitmapCursor::new(&bitmap.bits, bitmap.cardinality),
+                bits: bitmap.bits,
+            },
+            Container::Run(run) => ContainerIntoIter::Run {
+                cursor: RunCursor::new(&run.runs),
+                runs: run.runs,
+            },
+        }
+    }
+}
+
+impl Iterator for ContainerIntoIter {
+    type Item = u16;
+
+    fn next(&mut self) -> Option<u16> {
+        match self {
+            ContainerIntoIter::Array { values } => values.next(),
+            ContainerIntoIter::Bitmap { bits, cursor } => cursor.next(bits),
+            ContainerIntoIter::Run { runs, cursor } => cursor.next(runs),
+        }
+    }
+}
+
+impl DoubleEndedIterator for ContainerIntoIter {
+    fn next_back(&mut self) -> Option<u16> {
+        match self {
+            ContainerIntoIter::Array { values } => values.next_back(),
+            ContainerIntoIter::Bitmap { bits, cursor } => cursor.next_back(bits),
+            ContainerIntoIter::Run { runs, cursor } => cursor.next_back(runs),
+        }
+    }
+}
+
+impl BitmapCursor {
+    /// Positions both ends at the edges of a container with `len` values
+    fn new(bits: &[u64; 1024], len: u64) -> Self {
+        BitmapCursor {
+            front_index: 0,
+            front_word: bits[0],
+            back_index: 1023,
+            back_word: bits[1023],
+            len,
+        }
+    }
+
+    fn next(&mut self, bits: &[u64; 1024]) -> Option<u16> {
+        if self.len == 0 {
+            return None;
+        }
+
+        // A remaining value guarantees a set bit at or after the front
+        while self.front_word == 0 {
+            self.front_index += 1;
+            self.front_word = bits[self.front_index];
+        }
+
+        // Pop the lowest set bit
+        let bit = self.front_word.trailing_zeros() as usize;
+        self.front_word &= self.front_word - 1;
+        self.len -= 1;
+        Some((self.front_index * 64 + bit) as u16)
+    }
+
+    fn next_back(&mut self, bits: &[u64; 1024]) -> Option<u16> {
+        if self.len == 0 {
+            return None;
+        }
+
+        // A remaining value guarantees a set bit at or before the back
+        while self.back_word == 0 {
+            self.back_index -= 1;
+            self.back_word = bits[self.back_index];
+        }
+
+        // Pop the highest set bit
+        let bit = 63 - self.back_word.leading_zeros() as usize;
+        self.back_word &= !(1u64 << bit);
+        self.len -= 1;
+        Some((self.back_index * 64 + bit) as u16)
+    }
+
+    /// Skips values below `low` from the front, returning how many were skipped
+    fn advance_to(&mut self, bits: &[u64; 1024], low: u16) -> u64 {
+        let word_index = low as usize / 64;
+        if word_index < self.front_index {
+            return 0;
+        }
+
+        let mut skipped = 0u64;
+        if word_index > self.front_index {
+            skipped += self.front_word.count_ones() as u64;
+            skipped += bits[self.front_index + 1..word_index]
+                .iter()
+                .map(|word| word.count_ones() as u64)
+                .sum::<u64>();
+            self.front_index = word_index;
+            self.front_word = bits[word_index];
+        }
+
+        let below = self.front_word & ((1u64 << (low % 64)) - 1);
+        skipped += below.count_ones() as u64;
+        self.front_word &= !below;
+
+        // Skipping past the back end leaves nothing, so the count is capped
+        // at what was actually remaining
+        let skipped = skipped.min(self.len);
+        self.len -= skipped;
+        skipped
     }
 
-    /// Converts run container to array container
-    fn to_array(&self) -> ArrayContainer {
-        let capacity: usize = self.runs.iter().map(|(_, len)| (*len as usize) + 1).sum();
-        let mut values = Vec::with_capacity(capacity);
+    /// Drops values above `high` from the back, returning how many were dropped
+    fn retreat_to(&mut self, bits: &[u64; 1024], high: u16) -> u64 {
+        let word_index = high as usize / 64;
+        if word_index > self.back_index {
+            return 0;
+        }
 
-        for &(start, length) in &self.runs {
-            // length is (actual_length - 1), so iterate 0..=length to get all values
-            for offset in 0..=length {
-                values.push(start + offset);
-            }
+        let mut dropped = 0u64;
+        if word_index < self.back_index {
+            dropped += self.back_word.count_ones() as u64;
+            dropped += bits[word_index + 1..self.back_index]
+                .iter()
+                .map(|word| word.count_ones() as u64)
+                .sum::<u64>();
+            self.back_index = word_index;
+            self.back_word = bits[word_index];
         }
 
-        ArrayContainer { values }
+        let above = self.back_word & !(u64::MAX >> (63 - high % 64));
+        dropped += above.count_ones() as u64;
+        self.back_word &= !above;
+
+        let dropped = dropped.min(self.len);
+        self.len -= dropped;
+        dropped
     }
+}
 
-    /// Inserts a value, maintaining run invariants
-    fn insert(&mut self, value: u16) -> bool {
-        // Find where this value should go
-        let pos = self.runs.binary_search_by_key(&value, |(start, _)| *start);
+impl RunCursor {
+    /// Positions both ends at the edges of the runs
+    fn new(runs: &[(u16, u16)]) -> Self {
+        RunCursor {
+            front_run: 0,
+            front_offset: 0,
+            back_run: runs.len().saturating_sub(1),
+            back_offset: runs.last().map_or(0, |&(_, length)| length),
+            len: runs.iter().map(|&(_, length)| length as u64 + 1).sum(),
+        }
+    }
 
-        match pos {
-            Ok(_index) => {
-                // Value equals a run start, already present
-                false
-            }
-            Err(index) => {
-                // Check if value is within or adjacent to previous run
-                if index > 0 {
-                    let (start, length) = self.runs[index - 1];
-                    // length is (actual_length - 1), so run_end = start + length
-                    let run_end = start + length;
+    fn next(&mut self, runs: &[(u16, u16)]) -> Option<u16> {
+        if self.len == 0 {
+            return None;
+        }
 
-                    if value <= run_end {
-                        // Value already in previous run
-                        return false;
-                    } else if value == run_end + 1 {
-                        // Extend previous run
-                        self.runs[index - 1].1 += 1;
+        let (start, length) = runs[self.front_run];
+        let value = start + self.front_offset;
 
-                        // Check if we can merge with next run
-                        if index < self.runs.len() {
-                            let (next_start, next_length) = self.runs[index];
-                            if value + 1 == next_start {
-                                // Merge runs (add next_length + 1 since length encoding is length_minus_1)
-                                self.runs[index - 1].1 += next_length + 1;
-                                self.runs.remove(index);
-                            }
-                        }
+        // length is (actual_length - 1), so the last offset is `length`
+        if self.front_offset == length {
+            self.front_run += 1;
+            self.front_offset = 0;
+        } else {
+            self.front_offset += 1;
+        }
 
-                        return true;
-                    }
-                }
+        self.len -= 1;
+        Some(value)
+    }
 
-                // Check if value is adjacent to next run
-                if index < self.runs.len() {
-                    let (start, _) = self.runs[index];
-                    if value + 1 == start {
-                        // Extend next run backwards
-                        self.runs[index].0 = value;
-                        self.runs[index].1 += 1;
-                        return true;
-                    }
-                }
+    fn next_back(&mut self, runs: &[(u16, u16)]) -> Option<u16> {
+        if self.len == 0 {
+            return None;
+        }
 
-                // Insert new run of length 1 (store as 0 in length-1 encoding)
-                self.runs.insert(index, (value, 0));
-                true
-            }
+        let (start, _) = runs[self.back_run];
+        let value = start + self.back_offset;
+
+        if self.back_offset > 0 {
+            self.back_offset -= 1;
+        } else if self.back_run > 0 {
+            self.back_run -= 1;
+            self.back_offset = runs[self.back_run].1;
         }
+
+        self.len -= 1;
+        Some(value)
     }
 
-    /// Checks if a value exists
-    fn contains(&self, value: u16) -> bool {
-        for &(start, length) in &self.runs {
-            // length is (actual_length - 1), so end = start + length
-            let end = start + length;
-            if value >= start && value <= end {
-                return true;
+    /// Skips values below `low` from the front, returning how many were skipped
+    fn advance_to(&mut self, runs: &[(u16, u16)], low: u16) -> u64 {
+        let mut skipped = 0u64;
+        while let Some(&(start, length)) = runs.get(self.front_run) {
+            let current = start as u32 + self.front_offset as u32;
+            let end = start as u32 + length as u32;
+            if low as u32 <= current {
+                break;
             }
-            if value < start {
-                return false; // Runs are sorted, no need to continue
+            if low as u32 <= end {
+                skipped += (low as u32 - current) as u64;
+                self.front_offset = low - start;
+                break;
             }
+            skipped += (end - current + 1) as u64;
+            self.front_run += 1;
+            self.front_offset = 0;
         }
-        false
-    }
-
-    /// Removes a value if present
-    fn remove(&mut self, value: u16) -> bool {
-        for i in 0..self.runs.len() {
-            let (start, length) = self.runs[i];
-            // length is (actual_length - 1), so end = start + length
-            let end = start + length;
 
-            if value >= start && value <= end {
-                // Value is in this run
-                if length == 0 {
-                    // Remove the entire run (length=0 means 1 value)
-                    self.runs.remove(i);
-                } else if value == start {
-                    // Remove from start of run
-                    self.runs[i] = (start + 1, length - 1);
-                } else if value == end {
-                    // Remove from end of run
-                    self.runs[i].1 -= 1;
-                } else {
-                    // Split the run
-                    // First run: [start, value-1], length = (value - start)
-                    // Second run: [value+1, end], length = (end - value - 1)
-                    let first_length = value - start - 1;
-                    let second_start = value + 1;
-                    let second_length = end - value - 1;
+        let skipped = skipped.min(self.len);
+        self.len -= skipped;
+        skipped
+    }
 
-                    self.runs[i] = (start, first_length);
-                    self.runs.insert(i + 1, (second_start, second_length));
-                }
-                return true;
+    /// Drops values above `high` from the back, returning how many were dropped
+    fn retreat_to(&mut self, runs: &[(u16, u16)], high: u16) -> u64 {
+        let mut dropped = 0u64;
+        while let Some(&(start, _)) = runs.get(self.back_run) {
+            let current = start as u32 + self.back_offset as u32;
+            if high as u32 >= current {
+                break;
             }
-
-            if value < start {
-                return false; // Runs are sorted
+            if high >= start {
+                dropped += (current - high as u32) as u64;
+                self.back_offset = high - start;
+                break;
             }
+            dropped += self.back_offset as u64 + 1;
+            if self.back_run == 0 {
+                break;
+            }
+            self.back_run -= 1;
+            self.back_offset = runs[self.back_run].1;
         }
-        false
-    }
 
-    /// Returns the number of values in the container
-    fn len(&self) -> u64 {
-        // length is (actual_length - 1), so add 1 to get actual length
-        self.runs
-            .iter()
-            .map(|(_, length)| (*length as u64) + 1)
-            .sum()
+        let dropped = dropped.min(self.len);
+        self.len -= dropped;
+        dropped
     }
+}
 
-    /// Checks if the container is empty
-    fn is_empty(&self) -> bool {
-        self.runs.is_empty()
+// Collection trait implementations
+
+impl IntoIterator for RoaringBitmap {
+    type Item = u32;
+    type IntoIter = IntoIter;
+
+    /// Consumes the bitmap, yielding its elements in sorted order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let bm: RoaringBitmap = [3, 1, 70_000].into_iter().collect();
+    /// let values: Vec<u32> = bm.into_iter().collect();
+    /// assert_eq!(values, vec![1, 3, 70_000]);
+    /// ```
+    fn into_iter(self) -> IntoIter {
+        IntoIter {
+            remaining: self.len(),
+            containers: self.containers.into_iter(),
+            front: None,
+            back: None,
+        }
     }
+}
 
-    /// Returns the union of two run containers
-    fn union(&self, other: &RunContainer) -> RunContainer {
-        let array_self = self.to_array();
-        let array_other = other.to_array();
-        let union_array = array_self.union(&array_other);
-        RunContainer::from_array(&union_array)
+impl<'a> IntoIterator for &'a RoaringBitmap {
+    type Item = u32;
+    type IntoIter = Iter<'a>;
+
+    /// Iterates over the elements in sorted order, same as `iter()`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let bm: RoaringBitmap = (1..=3).collect();
+    /// let mut sum = 0;
+    /// for value in &bm {
+    ///     sum += value;
+    /// }
+    /// assert_eq!(sum, 6);
+    /// ```
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
     }
+}
 
-    /// Returns the intersection of two run containers (None if empty)
-    fn intersection(&self, other: &RunContainer) -> Option<RunContainer> {
-        let array_self = self.to_array();
-        let array_other = other.to_array();
-        array_self
-            .intersection(&array_other)
-            .map(|arr| RunContainer::from_array(&arr))
+impl FromIterator<u32> for RoaringBitmap {
+    /// Builds a bitmap from an iterator of values
+    ///
+    /// Sorted input is packed into containers in a single pass; see the
+    /// `Extend` implementation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let evens: RoaringBitmap = (0..10_000).filter(|x| x % 2 == 0).collect();
+    /// assert_eq!(evens.len(), 5000);
+    /// ```
+    fn from_iter<I: IntoIterator<Item = u32>>(values: I) -> Self {
+        let mut bitmap = RoaringBitmap::new();
+        bitmap.extend(values);
+        bitmap
     }
+}
 
-    /// Returns the difference of two run containers (self - other) (None if empty)
-    fn difference(&self, other: &RunContainer) -> Option<RunContainer> {
-        let array_self = self.to_array();
-        let array_other = other.to_array();
-        array_self
-            .difference(&array_other)
-            .map(|arr| RunContainer::from_array(&arr))
+impl Extend<u32> for RoaringBitmap {
+    /// Inserts every value from the iterator
+    ///
+    /// # Performance
+    ///
+    /// While the input is ascending (duplicates allowed), values are gathered
+    /// per 16-bit key and each container is built once and merged in, instead
+    /// of calling `insert` per value. The first out-of-order value switches the
+    /// rest of the input to regular `insert`s.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let mut bm = RoaringBitmap::new();
+    /// bm.extend(0..100_000);
+    /// bm.extend([5, 200_000, 3]);
+    /// assert_eq!(bm.len(), 100_001);
+    /// ```
+    fn extend<I: IntoIterator<Item = u32>>(&mut self, values: I) {
+        let mut values = values.into_iter();
+        let mut chunk_key = 0u16;
+        let mut chunk: Vec<u16> = Vec::new();
+        let mut previous: Option<u32> = None;
+
+        for value in values.by_ref() {
+            match previous {
+                Some(prev) if value == prev => continue,
+                Some(prev) if value < prev => {
+                    // Unsorted input: flush what was gathered and fall back
+                    self.merge_sorted_chunk(chunk_key, std::mem::take(&mut chunk));
+                    self.insert(value);
+                    break;
+                }
+                _ => {}
+            }
+            previous = Some(value);
+
+            let (key, low) = Self::split(value);
+            if key != chunk_key && !chunk.is_empty() {
+                self.merge_sorted_chunk(chunk_key, std::mem::take(&mut chunk));
+            }
+            chunk_key = key;
+            chunk.push(low);
+        }
+        self.merge_sorted_chunk(chunk_key, chunk);
+
+        for value in values {
+            self.insert(value);
+        }
     }
+}
 
-    /// Returns the symmetric difference of two run containers (None if empty)
-    fn symmetric_difference(&self, other: &RunContainer) -> Option<RunContainer> {
-        let array_self = self.to_array();
-        let array_other = other.to_array();
-        array_self
-            .symmetric_difference(&array_other)
-            .map(|arr| RunContainer::from_array(&arr))
+impl<'a> Extend<&'a u32> for RoaringBitmap {
+    /// Inserts every referenced value from the iterator
+    fn extend<I: IntoIterator<Item = &'a u32>>(&mut self, values: I) {
+        self.extend(values.into_iter().copied());
     }
 }
 
-// Iterator implementation
+// Comparison, hashing and formatting
 
-impl<'a> Iterator for Iter<'a> {
-    type Item = u32;
+impl PartialEq for RoaringBitmap {
+    /// Compares bitmaps as sets: equal values compare equal even when they
+    /// are stored in different container types
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let mut runs = RoaringBitmap::new();
+    /// runs.extend_consecutive(0..100);
+    /// let values: RoaringBitmap = (0..100).collect();
+    /// assert_eq!(runs, values);
+    /// ```
+    fn eq(&self, other: &RoaringBitmap) -> bool {
+        self.containers.len() == other.containers.len()
+            && self
+                .containers
+                .iter()
+                .zip(&other.containers)
+                .all(|((key_a, a), (key_b, b))| key_a == key_b && a.same_values(b))
+    }
+}
 
-    fn next(&mut self) -> Option<u32> {
-        loop {
-            // Check if we've exhausted all containers
-            if self.container_index >= self.bitmap.containers.len() {
-                return None;
-            }
+impl Eq for RoaringBitmap {}
 
-            // Get current container
-            let (key, container) = &self.bitmap.containers[self.container_index];
+impl Hash for RoaringBitmap {
+    /// Hashes the set of values independently of container types, consistent
+    /// with `PartialEq`
+    ///
+    /// Each container is fed to the hasher as its maximal runs of consecutive
+    /// values, so dense ranges hash in time proportional to the number of runs.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.containers.len());
+        for (key, container) in &self.containers {
+            key.hash(state);
+            container.for_each_run(|start, end| {
+                start.hash(state);
+                end.hash(state);
+            });
+        }
+    }
+}
 
-            match container {
-                Container::Array(array) => {
-                    // Array container: iterate through sorted values
-                    if self.value_index < array.values.len() {
-                        let low = array.values[self.value_index];
-                        self.value_index += 1;
-                        return Some(RoaringBitmap::combine(*key, low));
-                    } else {
-                        // Move to next container
-                        self.container_index += 1;
-                        self.value_index = 0;
-                        continue;
-                    }
-                }
-                Container::Bitmap(bitmap) => {
-                    // Bitmap container: scan for set bits
-                    loop {
-                        // If current word is exhausted or not yet loaded, load/move to next word
-                        if self.bitmap_current_word == 0 {
-                            // Check if we need to load the current word first (not yet loaded)
-                            // This happens when entering a bitmap container from a non-bitmap container
-                            if self.bitmap_bit_position == 0 && self.bitmap_word_index < 1024 {
-                                // Load the current word before moving to next
-                                self.bitmap_current_word = bitmap.bits[self.bitmap_word_index];
-                                if self.bitmap_current_word != 0 {
-                                    // Word has bits, process it
-                                    continue;
-                                }
-                            }
-                            // Move to next word
-                            self.bitmap_word_index += 1;
-                            if self.bitmap_word_index >= 1024 {
-                                // Exhausted this container, move to next
-                                self.container_index += 1;
-                                self.value_index = 0;
-
-                                // Reset bitmap state (will be reinitialized if next is also bitmap)
-                                self.bitmap_word_index = 0;
-                                self.bitmap_bit_position = 0;
-                                self.bitmap_current_word = 0;
-
-                                // Initialize for next container if it's a bitmap
-                                // Always start at bits[0], consistent with initial iterator behavior
-                                if self.container_index < self.bitmap.containers.len() {
-                                    if let Container::Bitmap(next_bm) =
-                                        &self.bitmap.containers[self.container_index].1
-                                    {
-                                        self.bitmap_current_word = next_bm.bits[0];
-                                    }
-                                }
-                                break;
-                            }
-                            self.bitmap_current_word = bitmap.bits[self.bitmap_word_index];
-                            self.bitmap_bit_position = 0;
-                            continue;
-                        }
+impl PartialOrd for RoaringBitmap {
+    /// Orders bitmaps lexicographically by their sorted values, the same way
+    /// `BTreeSet<u32>` is ordered
+    ///
+    /// `Ord` is deliberately not implemented: its by-value `min`/`max` would
+    /// take precedence over [`RoaringBitmap::min`] and [`RoaringBitmap::max`]
+    /// in method calls on owned bitmaps.
+    fn partial_cmp(&self, other: &RoaringBitmap) -> Option<std::cmp::Ordering> {
+        Some(self.iter().cmp(other.iter()))
+    }
+}
 
-                        // Find next set bit in current word
-                        while self.bitmap_bit_position < 64 {
-                            if (self.bitmap_current_word & (1u64 << self.bitmap_bit_position)) != 0
-                            {
-                                let low = (self.bitmap_word_index * 64
-                                    + self.bitmap_bit_position as usize)
-                                    as u16;
-                                self.bitmap_bit_position += 1;
-                                return Some(RoaringBitmap::combine(*key, low));
-                            }
-                            self.bitmap_bit_position += 1;
-                        }
+impl fmt::Debug for RoaringBitmap {
+    /// Prints the cardinality, the first few values and a summary of the
+    /// container types
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringBitmap;
+    ///
+    /// let bm: RoaringBitmap = [1, 2, 70_000].into_iter().collect();
+    /// assert_eq!(
+    ///     format!("{:?}", bm),
+    ///     "RoaringBitmap { len: 3, values: [1, 2, 70000], containers: 2 (Array: 2, Bitmap: 0, Run: 0) }"
+    /// );
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut counts = [0usize; 3];
+        for (_, container) in &self.containers {
+            let index = match container {
+                Container::Array(_) => 0,
+                Container::Bitmap(_) => 1,
+                Container::Run(_) => 2,
+            };
+            counts[index] += 1;
+        }
 
-                        // Current word exhausted
-                        self.bitmap_current_word = 0;
-                    }
-                }
-                Container::Run(run) => {
-                    // Run container: iterate through runs
-                    if self.value_index < run.runs.len() {
-                        let (start, length) = run.runs[self.value_index];
-
-                        // length is (actual_length - 1), so iterate 0..=length
-                        if self.run_offset <= length {
-                            let low = start + self.run_offset;
-
-                            // Check if this is the last value in the run
-                            if self.run_offset == length {
-                                // Move to next run
-                                self.value_index += 1;
-                                self.run_offset = 0;
-                            } else {
-                                // Increment offset for next value in run
-                                self.run_offset += 1;
-                            }
+        f.debug_struct("RoaringBitmap")
+            .field("len", &self.len())
+            .field("values", &DebugValues(self))
+            .field(
+                "containers",
+                &format_args!(
+                    "{} (Array: {}, Bitmap: {}, Run: {})",
+                    self.containers.len(),
+                    counts[0],
+                    counts[1],
+                    counts[2]
+                ),
+            )
+            .finish()
+    }
+}
 
-                            return Some(RoaringBitmap::combine(*key, low));
-                        } else {
-                            // Move to next run (shouldn't reach here normally)
-                            self.value_index += 1;
-                            self.run_offset = 0;
-                            continue;
-                        }
-                    } else {
-                        // Move to next container
-                        self.container_index += 1;
-                        self.value_index = 0;
-                        self.run_offset = 0;
-                        continue;
-                    }
-                }
-            }
+/// Debug helper listing the first `DEBUG_MAX_VALUES` values of a bitmap
+struct DebugValues<'a>(&'a RoaringBitmap);
+
+impl fmt::Debug for DebugValues<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut list = f.debug_list();
+        list.entries(self.0.iter().take(DEBUG_MAX_VALUES));
+        if self.0.len() > DEBUG_MAX_VALUES as u64 {
+            list.finish_non_exhaustive()
+        } else {
+            list.finish()
         }
     }
 }
@@ -2946,7 +6202,7 @@ impl BitOr<&RoaringBitmap> for RoaringBitmap {
     type Output = RoaringBitmap;
 
     fn bitor(self, rhs: &RoaringBitmap) -> RoaringBitmap {
-        (&self).union(rhs)
+        self.union(rhs)
     }
 }
 
@@ -2954,7 +6210,7 @@ impl BitAnd<&RoaringBitmap> for RoaringBitmap {
     type Output = RoaringBitmap;
 
     fn bitand(self, rhs: &RoaringBitmap) -> RoaringBitmap {
-        (&self).intersection(rhs)
+        self.intersection(rhs)
     }
 }
 
@@ -2962,7 +6218,7 @@ impl BitXor<&RoaringBitmap> for RoaringBitmap {
     type Output = RoaringBitmap;
 
     fn bitxor(self, rhs: &RoaringBitmap) -> RoaringBitmap {
-        (&self).symmetric_difference(rhs)
+        self.symmetric_difference(rhs)
     }
 }
 
@@ -2970,7 +6226,7 @@ impl Sub<&RoaringBitmap> for RoaringBitmap {
     type Output = RoaringBitmap;
 
     fn sub(self, rhs: &RoaringBitmap) -> RoaringBitmap {
-        (&self).difference(rhs)
+        self.difference(rhs)
     }
 }
 
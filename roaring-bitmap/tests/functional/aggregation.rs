@@ -0,0 +1,175 @@
+// Tests for the multi-way set operations (union_many, intersection_many,
+// xor_many), checked against pairwise folds and a counting reference
+
+use super::{bitmap_of, expect_bitmap, random_bitmap, Rng};
+use roaring_bitmap::RoaringBitmap;
+use std::collections::BTreeMap;
+
+/// Counts, for every value, how many of the inputs contain it
+fn occurrences(bitmaps: &[RoaringBitmap]) -> BTreeMap<u32, usize> {
+    let mut counts = BTreeMap::new();
+    for bitmap in bitmaps {
+        for value in bitmap {
+            *counts.entry(value).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+fn values_where(counts: &BTreeMap<u32, usize>, keep: impl Fn(usize) -> bool) -> Vec<u32> {
+    counts
+        .iter()
+        .filter(|(_, &count)| keep(count))
+        .map(|(&value, _)| value)
+        .collect()
+}
+
+// ============================================================================
+// Agreement with reference results
+// ============================================================================
+
+#[test]
+fn multi_way_operations_match_counting_reference() {
+    let mut rng = Rng(0x1234_5678_9ABC);
+    for round in 0..20 {
+        let count = 1 + round % 7;
+        let bitmaps: Vec<RoaringBitmap> = (0..count)
+            .map(|_| random_bitmap(&mut rng, 4, 65_536))
+            .collect();
+        let counts = occurrences(&bitmaps);
+
+        expect_bitmap(
+            &RoaringBitmap::union_many(&bitmaps),
+            &values_where(&counts, |_| true),
+        );
+        expect_bitmap(
+            &RoaringBitmap::intersection_many(&bitmaps),
+            &values_where(&counts, |c| c == bitmaps.len()),
+        );
+        expect_bitmap(
+            &RoaringBitmap::xor_many(&bitmaps),
+            &values_where(&counts, |c| c % 2 == 1),
+        );
+    }
+}
+
+#[test]
+fn multi_way_operations_match_pairwise_folds() {
+    let mut rng = Rng(0xDEAD_BEEF);
+    let bitmaps: Vec<RoaringBitmap> = (0..12)
+        .map(|_| random_bitmap(&mut rng, 4, 65_536))
+        .collect();
+
+    let mut union = RoaringBitmap::new();
+    let mut xor = RoaringBitmap::new();
+    let mut intersection = bitmaps[0].clone();
+    for bitmap in &bitmaps {
+        union.union_with(bitmap);
+        xor.symmetric_difference_with(bitmap);
+        intersection.intersect_with(bitmap);
+    }
+
+    assert_eq!(RoaringBitmap::union_many(&bitmaps), union);
+    assert_eq!(RoaringBitmap::xor_many(&bitmaps), xor);
+    assert_eq!(RoaringBitmap::intersection_many(&bitmaps), intersection);
+}
+
+#[test]
+fn many_small_inputs_union_into_one_bitmap() {
+    // 5,000 single-value "tag" bitmaps landing in the same few containers
+    let tags: Vec<RoaringBitmap> = (0..5000u32).map(|i| bitmap_of(&[i * 37])).collect();
+    let union = RoaringBitmap::union_many(&tags);
+    assert_eq!(union.len(), 5000);
+    assert_eq!(union, (0..5000u32).map(|i| i * 37).collect());
+}
+
+// ============================================================================
+// Edge cases
+// ============================================================================
+
+#[test]
+fn empty_and_single_inputs() {
+    let none: [&RoaringBitmap; 0] = [];
+    assert!(RoaringBitmap::union_many(none).is_empty());
+    assert!(RoaringBitmap::intersection_many(none).is_empty());
+    assert!(RoaringBitmap::xor_many(none).is_empty());
+
+    let only = bitmap_of(&[1, 70_000, 200_000]);
+    assert_eq!(RoaringBitmap::union_many([&only]), only);
+    assert_eq!(RoaringBitmap::intersection_many([&only]), only);
+    assert_eq!(RoaringBitmap::xor_many([&only]), only);
+
+    let empty = RoaringBitmap::new();
+    assert!(RoaringBitmap::intersection_many([&only, &empty]).is_empty());
+    assert_eq!(RoaringBitmap::union_many([&empty, &only]), only);
+}
+
+#[test]
+fn disjoint_keys_short_circuit_intersection() {
+    let low = bitmap_of(&[1, 2, 3]);
+    let high = bitmap_of(&[1 << 20, (1 << 20) + 1]);
+    let both = bitmap_of(&[1, 2, 3, 1 << 20]);
+    assert!(RoaringBitmap::intersection_many([&low, &high, &both]).is_empty());
+    expect_bitmap(&RoaringBitmap::intersection_many([&both, &low]), &[1, 2, 3]);
+}
+
+#[test]
+fn xor_drops_keys_that_cancel_out() {
+    let a = bitmap_of(&[5, 100_000]);
+    let b = bitmap_of(&[5, 100_001]);
+    let xor = RoaringBitmap::xor_many([&a, &b, &a, &b, &a]);
+    expect_bitmap(&xor, &[5, 100_000]);
+    assert_eq!(xor.container_type(1), Some("Array"));
+    assert!(RoaringBitmap::xor_many([&a, &a]).is_empty());
+}
+
+// ============================================================================
+// Result container types
+// ============================================================================
+
+#[test]
+fn results_are_optimized_once() {
+    let mut first_half = RoaringBitmap::new();
+    first_half.extend_consecutive(0..40_000);
+    let mut second_half = RoaringBitmap::new();
+    second_half.extend_consecutive(30_000..65_536);
+
+    // Run unions come back out of the scratch bitmap as Run
+    let union = RoaringBitmap::union_many([&first_half, &second_half]);
+    assert_eq!(union.len(), 65_536);
+    assert_eq!(union.container_type(0), Some("Run"));
+
+    // Run×Run×Bitmap goes through the scratch bitmap
+    let dense: RoaringBitmap = (20_000..50_000).collect();
+    let intersection = RoaringBitmap::intersection_many([&first_half, &second_half, &dense]);
+    expect_bitmap(&intersection, &(30_000..40_000).collect::<Vec<_>>());
+
+    // Dense scratch results are Run when run-shaped, Bitmap when fragmented,
+    // and sparse ones come out as Arrays
+    let evens: RoaringBitmap = (0..20_000).step_by(2).collect();
+    let odds: RoaringBitmap = (1..20_000).step_by(2).collect();
+    let xor = RoaringBitmap::xor_many([&evens, &odds, &dense]);
+    expect_bitmap(&xor, &(0..50_000).collect::<Vec<_>>());
+    assert_eq!(xor.container_type(0), Some("Run"));
+
+    let threes: RoaringBitmap = (0..20_000).step_by(3).collect();
+    let xor = RoaringBitmap::xor_many([&evens, &threes]);
+    let expected: Vec<u32> = (0..20_000)
+        .filter(|v| (v % 2 == 0) != (v % 3 == 0))
+        .collect();
+    expect_bitmap(&xor, &expected);
+    assert_eq!(xor.container_type(0), Some("Bitmap"));
+
+    let mut almost_evens = evens.clone();
+    almost_evens.insert(1);
+    almost_evens.insert(3);
+    let xor = RoaringBitmap::xor_many([&evens, &almost_evens]);
+    expect_bitmap(&xor, &[1, 3]);
+    assert_eq!(xor.container_type(0), Some("Array"));
+
+    // Intersections led by an array stay Arrays
+    let few: RoaringBitmap = (0..100).step_by(3).collect();
+    let intersection = RoaringBitmap::intersection_many([&evens, &first_half, &few]);
+    expect_bitmap(&intersection, &(0..100).step_by(6).collect::<Vec<_>>());
+    assert_eq!(intersection.container_type(0), Some("Array"));
+}
@@ -78,6 +78,7 @@ fn batch_remove_range_across_containers() {
 }
 
 #[test]
+#[allow(clippy::reversed_empty_ranges)]
 fn batch_remove_range_empty_range() {
     let mut bm = RoaringBitmap::new();
     bm.extend_consecutive(0..1000);
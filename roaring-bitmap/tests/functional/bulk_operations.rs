@@ -98,6 +98,7 @@ fn intermediate_extend_consecutive_across_containers() {
 }
 
 #[test]
+#[allow(clippy::reversed_empty_ranges)]
 fn intermediate_extend_consecutive_empty_range() {
     let mut bm = RoaringBitmap::new();
 
@@ -0,0 +1,195 @@
+use crate::functional::{bitmap_of, expect_bitmap};
+use roaring_bitmap::RoaringBitmap;
+
+/// Builds the reference bitmap one `insert` at a time
+fn inserted(values: &[u32]) -> RoaringBitmap {
+    let mut bm = RoaringBitmap::new();
+    for &value in values {
+        bm.insert(value);
+    }
+    bm
+}
+
+/// Asserts that two bitmaps hold the same values in the same container layout
+fn assert_same(actual: &RoaringBitmap, expected: &RoaringBitmap) {
+    assert_eq!(actual.len(), expected.len());
+    assert!(actual.iter().eq(expected.iter()));
+    assert_eq!(actual.container_stats(), expected.container_stats());
+}
+
+/// Bitmap with Array, Bitmap and Run containers
+fn mixed_bitmap() -> RoaringBitmap {
+    let mut bm = RoaringBitmap::new();
+    bm.extend_sparse([0, 5, 17, 1000, 65535]); // Array in container 0
+    for i in 0..10_000 {
+        bm.insert(65536 + i * 3); // Bitmap in container 1
+    }
+    bm.extend_consecutive(3 * 65536 + 10..3 * 65536 + 20); // Run in container 3
+    bm.insert(u32::MAX);
+    bm
+}
+
+// ============================================================================
+// FromIterator
+// ============================================================================
+
+#[test]
+fn collect_empty_iterator() {
+    let bm: RoaringBitmap = std::iter::empty().collect();
+    assert!(bm.is_empty());
+}
+
+#[test]
+fn collect_sorted_matches_insert() {
+    let values: Vec<u32> = (0..300_000).filter(|x| x % 7 == 0 || x % 11 == 0).collect();
+    let bm: RoaringBitmap = values.iter().copied().collect();
+    assert_same(&bm, &inserted(&values));
+}
+
+#[test]
+fn collect_sorted_picks_container_types() {
+    let sparse: RoaringBitmap = (0..4095).collect();
+    assert_eq!(sparse.container_type(0), Some("Array"));
+
+    let dense: RoaringBitmap = (0..4096).collect();
+    assert_eq!(dense.container_type(0), Some("Bitmap"));
+    assert_eq!(dense.len(), 4096);
+}
+
+#[test]
+fn collect_unsorted_matches_insert() {
+    let values = [70_000, 3, 1_000_000, 2, 65536, u32::MAX, 0, 3];
+    let bm: RoaringBitmap = values.iter().copied().collect();
+    assert_same(&bm, &inserted(&values));
+    expect_bitmap(&bm, &[0, 2, 3, 65536, 70_000, 1_000_000, u32::MAX]);
+}
+
+#[test]
+fn collect_sorted_with_duplicates() {
+    let bm: RoaringBitmap = [1, 1, 2, 2, 2, 70_000, 70_000].into_iter().collect();
+    expect_bitmap(&bm, &[1, 2, 70_000]);
+}
+
+#[test]
+fn collect_sorted_prefix_then_unsorted_tail() {
+    let mut values: Vec<u32> = (0..10_000).map(|i| i * 5).collect();
+    values.extend([7, 1, 100_000, 3]);
+    let bm: RoaringBitmap = values.iter().copied().collect();
+    assert_same(&bm, &inserted(&values));
+}
+
+#[test]
+fn collect_extremes() {
+    let bm: RoaringBitmap = [0, u32::MAX].into_iter().collect();
+    expect_bitmap(&bm, &[0, u32::MAX]);
+}
+
+// ============================================================================
+// Extend
+// ============================================================================
+
+#[test]
+fn extend_into_existing_containers() {
+    let mut bm = mixed_bitmap();
+    let additions: Vec<u32> = (0..5000)
+        .map(|i| i * 2)
+        .chain(65536..65600)
+        .chain(3 * 65536..3 * 65536 + 50)
+        .chain([5 * 65536])
+        .collect();
+
+    let mut expected = mixed_bitmap();
+    for &value in &additions {
+        expected.insert(value);
+    }
+
+    bm.extend(additions);
+    assert_eq!(bm.len(), expected.len());
+    assert!(bm.iter().eq(expected.iter()));
+}
+
+#[test]
+fn extend_by_reference() {
+    let values = vec![10, 20, 30, 70_000];
+    let mut bm = bitmap_of(&[1]);
+    bm.extend(&values);
+    bm.extend(values.iter().filter(|&&v| v > 15));
+    expect_bitmap(&bm, &[1, 10, 20, 30, 70_000]);
+}
+
+#[test]
+fn extend_with_empty_iterator_is_noop() {
+    let mut bm = bitmap_of(&[1, 2, 3]);
+    bm.extend(Vec::<u32>::new());
+    expect_bitmap(&bm, &[1, 2, 3]);
+}
+
+// ============================================================================
+// IntoIterator
+// ============================================================================
+
+#[test]
+fn into_iter_owned_matches_iter() {
+    let bm = mixed_bitmap();
+    let expected: Vec<u32> = bm.iter().collect();
+    let owned: Vec<u32> = bm.into_iter().collect();
+    assert_eq!(owned, expected);
+}
+
+#[test]
+fn into_iter_owned_is_double_ended_and_exact() {
+    let bm = mixed_bitmap();
+    let mut expected: Vec<u32> = bm.iter().collect();
+
+    let mut iter = bm.into_iter();
+    assert_eq!(iter.len(), expected.len());
+    assert_eq!(iter.next(), Some(0));
+    assert_eq!(iter.next_back(), Some(u32::MAX));
+    assert_eq!(iter.len(), expected.len() - 2);
+
+    let rest: Vec<u32> = iter.rev().collect();
+    expected.reverse();
+    assert_eq!(rest, expected[1..expected.len() - 1]);
+}
+
+#[test]
+fn into_iter_owned_ends_meet() {
+    let mut bm = RoaringBitmap::new();
+    for i in 0..5000 {
+        bm.insert(i * 2);
+    }
+    bm.extend_consecutive(65536..65546);
+
+    let expected: Vec<u32> = bm.iter().collect();
+    let mut iter = bm.into_iter();
+    let mut front = Vec::new();
+    let mut back = Vec::new();
+    while let Some(value) = iter.next() {
+        front.push(value);
+        match iter.next_back() {
+            Some(value) => back.push(value),
+            None => break,
+        }
+    }
+    back.reverse();
+    front.extend(back);
+    assert_eq!(front, expected);
+}
+
+#[test]
+fn into_iter_by_reference_in_for_loop() {
+    let bm = bitmap_of(&[3, 1, 2]);
+    let mut values = Vec::new();
+    for value in &bm {
+        values.push(value);
+    }
+    assert_eq!(values, vec![1, 2, 3]);
+}
+
+#[test]
+fn collect_round_trip_through_owned_iterator() {
+    let bm = mixed_bitmap();
+    let rebuilt: RoaringBitmap = bm.clone().into_iter().collect();
+    assert_eq!(rebuilt.len(), bm.len());
+    assert!(rebuilt.iter().eq(bm.iter()));
+}
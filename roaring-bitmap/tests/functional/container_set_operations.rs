@@ -1,8 +1,7 @@
-/// Tests for container-level set operations
-/// These tests specifically target different container type combinations
-/// to ensure all code paths in Container::union, intersection, difference, etc. are covered
+// Tests for container-level set operations
+// These tests specifically target different container type combinations
+// to ensure all code paths in Container::union, intersection, difference, etc. are covered
 
-use crate::functional::{bitmap_of, expect_bitmap};
 use roaring_bitmap::RoaringBitmap;
 
 // ============================================================================
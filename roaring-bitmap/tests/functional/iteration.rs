@@ -191,6 +191,7 @@ fn iterator_container_boundary_transitions() {
 // ============================================================================
 
 #[test]
+#[allow(clippy::double_ended_iterator_last)]
 fn iterator_large_bitmap_one_million_values() {
     let mut bm = RoaringBitmap::new();
 
@@ -231,10 +232,30 @@ fn iterator_large_consecutive_range() {
 }
 
 // ============================================================================
-// Size Hint Tests (Note: Current iterator implementation doesn't provide size_hint)
+// Size Hint Tests
 // ============================================================================
 
-// TODO: Add size_hint tests when/if size_hint is implemented in the iterator
+#[test]
+fn iterator_size_hint_is_exact() {
+    let bm = mixed_bitmap();
+    let mut iter = bm.iter();
+    let total = bm.len() as usize;
+    assert_eq!(iter.size_hint(), (total, Some(total)));
+    assert_eq!(iter.len(), total);
+
+    iter.next();
+    iter.next_back();
+    assert_eq!(iter.len(), total - 2);
+
+    let rest: Vec<u32> = iter.collect();
+    assert_eq!(rest.len(), total - 2);
+}
+
+#[test]
+fn iterator_len_on_empty_bitmap() {
+    let bm = RoaringBitmap::new();
+    assert_eq!(bm.iter().len(), 0);
+}
 
 #[test]
 fn iterator_count_method() {
@@ -260,6 +281,7 @@ fn iterator_collect_into_vec() {
 }
 
 #[test]
+#[allow(clippy::iter_nth_zero)]
 fn iterator_nth_method() {
     let bm = bitmap_of(&[10, 20, 30, 40, 50]);
     let mut iter = bm.iter();
@@ -272,6 +294,7 @@ fn iterator_nth_method() {
 }
 
 #[test]
+#[allow(clippy::double_ended_iterator_last)]
 fn iterator_last_method() {
     let bm = bitmap_of(&[1, 5, 10, 15, 20, 100]);
     assert_eq!(bm.iter().last(), Some(100));
@@ -432,6 +455,7 @@ fn iterator_mixed_container_types() {
 }
 
 #[test]
+#[allow(clippy::needless_range_loop)]
 fn iterator_with_empty_containers_between() {
     let mut bm = RoaringBitmap::new();
 
@@ -470,3 +494,228 @@ fn iterator_with_empty_containers_between() {
         assert_eq!(values[20 + i], (20 * 65536 + i) as u32);
     }
 }
+
+// ============================================================================
+// Double-Ended Iteration Tests
+// ============================================================================
+
+/// Bitmap with Array, Bitmap and Run containers
+fn mixed_bitmap() -> RoaringBitmap {
+    let mut bm = RoaringBitmap::new();
+    bm.extend_sparse([0, 5, 17, 1000, 65535]); // Array in container 0
+    for i in 0..10_000 {
+        bm.insert(65536 + i * 3); // Bitmap in container 1
+    }
+    bm.extend_consecutive(3 * 65536 + 10..3 * 65536 + 20); // Run in container 3
+    bm.extend_consecutive(3 * 65536 + 100..3 * 65536 + 300);
+    bm.insert(u32::MAX);
+
+    let stats: Vec<&str> = bm.container_stats().iter().map(|s| s.1).collect();
+    assert_eq!(stats, vec!["Array", "Bitmap", "Run", "Array"]);
+    bm
+}
+
+#[test]
+fn iterator_rev_matches_reversed_forward() {
+    let bm = mixed_bitmap();
+    let mut forward: Vec<u32> = bm.iter().collect();
+    forward.reverse();
+    let backward: Vec<u32> = bm.iter().rev().collect();
+    assert_eq!(backward, forward);
+}
+
+#[test]
+fn iterator_rev_each_container_type() {
+    let array = bitmap_of(&[3, 9, 27]);
+    assert_eq!(array.iter().rev().collect::<Vec<_>>(), vec![27, 9, 3]);
+
+    let mut bitmap = RoaringBitmap::new();
+    for i in 0..5000 {
+        bitmap.insert(i * 13);
+    }
+    assert_eq!(bitmap.container_type(0), Some("Bitmap"));
+    let expected: Vec<u32> = (0..5000).rev().map(|i| i * 13).collect();
+    assert_eq!(bitmap.iter().rev().collect::<Vec<_>>(), expected);
+
+    let mut run = RoaringBitmap::new();
+    run.extend_consecutive(0..3);
+    run.extend_consecutive(10..12);
+    assert_eq!(run.container_type(0), Some("Run"));
+    assert_eq!(run.iter().rev().collect::<Vec<_>>(), vec![11, 10, 2, 1, 0]);
+}
+
+#[test]
+fn iterator_alternating_ends_meet_exactly_once() {
+    let bm = mixed_bitmap();
+    let expected: Vec<u32> = bm.iter().collect();
+
+    let mut iter = bm.iter();
+    let mut front = Vec::new();
+    let mut back = Vec::new();
+    while let Some(value) = iter.next() {
+        front.push(value);
+        match iter.next_back() {
+            Some(value) => back.push(value),
+            None => break,
+        }
+    }
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+
+    back.reverse();
+    front.extend(back);
+    assert_eq!(front, expected);
+}
+
+#[test]
+fn iterator_ends_meet_inside_single_container() {
+    // Both ends work on the same bitmap word and the same run
+    let mut bitmap = RoaringBitmap::new();
+    for i in 0..5000 {
+        bitmap.insert(i * 2);
+    }
+    let mut iter = bitmap.iter();
+    for _ in 0..4998 {
+        iter.next();
+    }
+    assert_eq!(iter.next_back(), Some(9998));
+    assert_eq!(iter.next_back(), Some(9996));
+    assert_eq!(iter.next_back(), None);
+    assert_eq!(iter.next(), None);
+
+    let mut run = RoaringBitmap::new();
+    run.extend_consecutive(100..105);
+    let mut iter = run.iter();
+    assert_eq!(iter.next(), Some(100));
+    assert_eq!(iter.next_back(), Some(104));
+    assert_eq!(iter.next(), Some(101));
+    assert_eq!(iter.next_back(), Some(103));
+    assert_eq!(iter.next(), Some(102));
+    assert_eq!(iter.next_back(), None);
+    assert_eq!(iter.len(), 0);
+}
+
+#[test]
+fn iterator_last_value_from_back() {
+    let bm = mixed_bitmap();
+    assert_eq!(bm.iter().next_back(), Some(u32::MAX));
+    assert_eq!(RoaringBitmap::new().iter().next_back(), None);
+}
+
+// ============================================================================
+// Seeking Tests (advance_to)
+// ============================================================================
+
+#[test]
+fn iterator_advance_to_matches_filter() {
+    let bm = mixed_bitmap();
+    let values: Vec<u32> = bm.iter().collect();
+
+    let mut probes = vec![0, 1, 18, 65535, 65536, 65537, 2 * 65536, u32::MAX];
+    probes.extend(values.iter().step_by(501).copied());
+    probes.extend(values.iter().step_by(499).map(|v| v + 1));
+    probes.push(3 * 65536 + 50);
+    probes.push(3 * 65536 + 300);
+
+    for target in probes {
+        let mut iter = bm.iter();
+        iter.advance_to(target);
+        let expected: Vec<u32> = values.iter().copied().filter(|&v| v >= target).collect();
+        assert_eq!(
+            iter.len(),
+            expected.len(),
+            "len after advance_to({})",
+            target
+        );
+        assert_eq!(iter.collect::<Vec<_>>(), expected, "advance_to({})", target);
+    }
+}
+
+#[test]
+fn iterator_advance_to_never_moves_backwards() {
+    let bm = bitmap_of(&[1, 2, 3, 100, 200]);
+    let mut iter = bm.iter();
+    iter.advance_to(100);
+    assert_eq!(iter.next(), Some(100));
+    iter.advance_to(2);
+    assert_eq!(iter.next(), Some(200));
+}
+
+#[test]
+fn iterator_advance_to_past_end() {
+    let bm = mixed_bitmap();
+    let mut iter = bm.iter();
+    iter.advance_to(3 * 65536 + 300);
+    assert_eq!(iter.next(), Some(u32::MAX));
+    assert_eq!(iter.next(), None);
+
+    let small = bitmap_of(&[1, 2, 3]);
+    let mut iter = small.iter();
+    iter.advance_to(u32::MAX);
+    assert_eq!(iter.len(), 0);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn iterator_advance_to_respects_back_end() {
+    let bm = mixed_bitmap();
+
+    // Values already taken from the back must not come back after a seek
+    let mut iter = bm.iter();
+    let taken: Vec<u32> = (&mut iter).rev().take(150).collect();
+    let lowest_taken = *taken.last().unwrap();
+    iter.advance_to(3 * 65536 + 15);
+    let rest: Vec<u32> = iter.collect();
+    assert!(rest
+        .iter()
+        .all(|&v| v >= 3 * 65536 + 15 && v < lowest_taken));
+    assert_eq!(rest.len(), 5 + (lowest_taken - (3 * 65536 + 100)) as usize);
+
+    // Seeking past everything the back end left over empties the iterator
+    let mut bitmap = RoaringBitmap::new();
+    for i in 0..5000 {
+        bitmap.insert(i * 2);
+    }
+    let mut iter = bitmap.iter();
+    assert_eq!(iter.next_back(), Some(9998));
+    iter.advance_to(9997);
+    assert_eq!(iter.len(), 0);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn iterator_advance_to_merge_join() {
+    // Leapfrog intersection of two posting lists
+    let mut a = RoaringBitmap::new();
+    let mut b = RoaringBitmap::new();
+    for i in 0..20_000u32 {
+        a.insert(i * 6);
+        b.insert(i * 10);
+    }
+    b.extend_consecutive(500_000..510_000);
+    a.extend_consecutive(505_000..600_000);
+
+    let mut result = Vec::new();
+    let (mut ia, mut ib) = (a.iter(), b.iter());
+    let (mut va, mut vb) = (ia.next(), ib.next());
+    while let (Some(x), Some(y)) = (va, vb) {
+        match x.cmp(&y) {
+            std::cmp::Ordering::Equal => {
+                result.push(x);
+                va = ia.next();
+                vb = ib.next();
+            }
+            std::cmp::Ordering::Less => {
+                ia.advance_to(y);
+                va = ia.next();
+            }
+            std::cmp::Ordering::Greater => {
+                ib.advance_to(x);
+                vb = ib.next();
+            }
+        }
+    }
+
+    let expected: Vec<u32> = a.intersection(&b).iter().collect();
+    assert_eq!(result, expected);
+}
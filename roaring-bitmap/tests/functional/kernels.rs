@@ -0,0 +1,167 @@
+// Tests for the container set operation kernels: every kernel the CPU
+// supports is forced in turn and cross-checked against the scalar one
+
+use super::Rng;
+use roaring_bitmap::{Kernel, RoaringBitmap};
+use std::collections::BTreeSet;
+
+fn supported() -> Vec<Kernel> {
+    Kernel::ALL
+        .into_iter()
+        .filter(|kernel| kernel.is_supported())
+        .collect()
+}
+
+/// Builds a single-container bitmap of `len` random values within `span`
+fn random_values(rng: &mut Rng, len: usize, span: u32) -> RoaringBitmap {
+    let mut bm = RoaringBitmap::new();
+    while (bm.len() as usize) < len {
+        bm.insert(rng.below(span));
+    }
+    bm
+}
+
+fn reference(a: &RoaringBitmap, b: &RoaringBitmap) -> Vec<u32> {
+    let b: BTreeSet<u32> = b.iter().collect();
+    a.iter().filter(|value| b.contains(value)).collect()
+}
+
+// ============================================================================
+// Kernel selection
+// ============================================================================
+
+#[test]
+fn detected_kernel_is_supported_and_active_by_default() {
+    let detected = Kernel::detected();
+    assert!(detected.is_supported());
+    assert!(Kernel::Scalar.is_supported());
+    assert_eq!(Kernel::active(), detected);
+    assert_eq!(supported().last(), Some(&detected));
+}
+
+#[test]
+fn force_applies_to_the_closure_and_restores_afterwards() {
+    for kernel in supported() {
+        assert_eq!(kernel.force(Kernel::active), kernel);
+        assert_eq!(
+            Kernel::Scalar.force(|| kernel.force(Kernel::active)),
+            kernel
+        );
+        assert_eq!(Kernel::active(), Kernel::detected());
+    }
+
+    let panicked = std::panic::catch_unwind(|| Kernel::Scalar.force(|| panic!("inside")));
+    assert!(panicked.is_err());
+    assert_eq!(Kernel::active(), Kernel::detected());
+}
+
+// ============================================================================
+// Array intersections
+// ============================================================================
+
+#[test]
+fn array_intersections_agree_across_kernels() {
+    let mut rng = Rng(0x5EED_0001);
+    // Lengths around the 8-value SIMD block size, and spans dense enough
+    // for many matches
+    for (len_a, len_b, span) in [
+        (1, 1, 4),
+        (7, 9, 16),
+        (8, 8, 16),
+        (15, 17, 40),
+        (64, 64, 128),
+        (100, 250, 1000),
+        (1000, 1200, 4000),
+        (4000, 3000, 65_536),
+        (4095, 4095, 8192),
+    ] {
+        let a = random_values(&mut rng, len_a, span);
+        let b = random_values(&mut rng, len_b, span);
+        assert_eq!(a.container_type(0), Some("Array"));
+        let expected = reference(&a, &b);
+
+        for kernel in supported() {
+            let (ab, ba) = kernel.force(|| (&a & &b, &b & &a));
+            assert_eq!(ab.iter().collect::<Vec<_>>(), expected, "{kernel:?}");
+            assert_eq!(ab, ba, "{kernel:?}");
+        }
+    }
+}
+
+#[test]
+fn skewed_array_intersections_gallop() {
+    let mut rng = Rng(0x5EED_0002);
+    let large = random_values(&mut rng, 4000, 65_536);
+    let members: Vec<u32> = large.iter().step_by(500).collect();
+
+    for extra in [0usize, 1, 10, 100] {
+        let mut small: RoaringBitmap = members.iter().copied().collect();
+        small.extend((0..extra).map(|_| rng.below(65_536)));
+        let expected = reference(&small, &large);
+
+        for kernel in supported() {
+            let result = kernel.force(|| &small & &large);
+            assert_eq!(result.iter().collect::<Vec<_>>(), expected, "{kernel:?}");
+        }
+    }
+
+    // Edges: the first and last values, and values past the end
+    let edges: RoaringBitmap = [0, 1, 65_535].into_iter().collect();
+    let all: RoaringBitmap = (0..4000).collect();
+    let result = &edges & &all;
+    assert_eq!(result.iter().collect::<Vec<_>>(), vec![0, 1]);
+}
+
+// ============================================================================
+// Bitmap word operations and popcount
+// ============================================================================
+
+#[test]
+fn bitmap_operations_agree_across_kernels() {
+    let mut rng = Rng(0x5EED_0003);
+    for density in [4100usize, 20_000, 60_000] {
+        let a = random_values(&mut rng, density, 65_536);
+        let b = random_values(&mut rng, 5000, 65_536);
+        assert_eq!(a.container_type(0), Some("Bitmap"));
+        assert_eq!(b.container_type(0), Some("Bitmap"));
+
+        let expected = Kernel::Scalar.force(|| (&a | &b, &a & &b, &a - &b, &a ^ &b));
+        let set_a: BTreeSet<u32> = a.iter().collect();
+        let set_b: BTreeSet<u32> = b.iter().collect();
+        assert_eq!(expected.0.len() as usize, set_a.union(&set_b).count());
+        assert_eq!(
+            expected.1.len() as usize,
+            set_a.intersection(&set_b).count()
+        );
+        assert_eq!(expected.2.len() as usize, set_a.difference(&set_b).count());
+        assert_eq!(
+            expected.3.len() as usize,
+            set_a.symmetric_difference(&set_b).count()
+        );
+
+        for kernel in supported() {
+            let results = kernel.force(|| (&a | &b, &a & &b, &a - &b, &a ^ &b));
+            assert_eq!(results, expected, "{kernel:?}");
+            // Cardinalities come from the fused popcount
+            assert_eq!(results.0.len(), expected.0.iter().count() as u64);
+            assert_eq!(results.3.len(), expected.3.iter().count() as u64);
+        }
+    }
+}
+
+#[test]
+fn full_and_empty_words_are_counted_exactly() {
+    let full: RoaringBitmap = (0..65_536).collect();
+    let evens: RoaringBitmap = (0..65_536).step_by(2).collect();
+
+    for kernel in supported() {
+        kernel.force(|| {
+            assert_eq!((&full & &evens).len(), 32_768);
+            assert_eq!((&full ^ &evens).len(), 32_768);
+            assert_eq!((&full - &full).len(), 0);
+            assert_eq!((&evens | &full).len(), 65_536);
+            // Recounted through the scratch bitmap of the multi-way union
+            assert_eq!(RoaringBitmap::union_many([&evens, &full]).len(), 65_536);
+        });
+    }
+}
@@ -21,17 +21,79 @@ pub fn expect_bitmap(bitmap: &RoaringBitmap, expected: &[u32]) {
     }
 }
 
+/// Deterministic xorshift generator so the tests need no extra dependencies
+pub struct Rng(pub u64);
+
+impl Rng {
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Returns a value in `0..bound`
+    pub fn below(&mut self, bound: u32) -> u32 {
+        (self.next() % bound as u64) as u32
+    }
+}
+
+/// Builds a bitmap over keys `0..keys` mixing empty, Array, Bitmap and Run
+/// containers, with values drawn from the lowest `span` (more than 6,000)
+/// values of each container
+pub fn random_bitmap(rng: &mut Rng, keys: u32, span: u32) -> RoaringBitmap {
+    let mut bm = RoaringBitmap::new();
+    for key in 0..keys {
+        let base = key << 16;
+        match rng.next() >> 62 {
+            0 => {}
+            1 => {
+                for _ in 0..rng.below(3000) {
+                    bm.insert(base + rng.below(span));
+                }
+            }
+            2 => {
+                let mut added = 0;
+                while added < 6000 {
+                    added += bm.insert(base + rng.below(span)) as u32;
+                }
+            }
+            _ => {
+                for _ in 0..1 + rng.below(20) {
+                    let start = rng.below(span - 500);
+                    bm.extend_consecutive(base + start..base + start + 1 + rng.below(500));
+                }
+            }
+        }
+    }
+    bm
+}
+
 // Test modules
+mod aggregation;
 mod basic_operations;
 mod batch_removal;
 mod bulk_operations;
+mod collect;
 mod container_set_operations;
 mod containers;
 mod iteration;
+mod kernels;
 mod memory;
+mod navigation;
 mod operators;
 mod operators_owned;
 mod optimization;
+mod range;
+mod range_predicates;
+mod rank_select;
 mod regression;
+mod run_set_operations;
+mod serialization;
 mod set_operations;
 mod set_operations_inplace;
+mod set_predicates;
+mod threshold;
+mod traits;
+mod treemap;
+mod view;
@@ -0,0 +1,196 @@
+use crate::functional::bitmap_of;
+use roaring_bitmap::RoaringBitmap;
+
+/// Brute-force reference for next_set_at_or_after
+fn expected_next(values: &[u32], x: u32) -> Option<u32> {
+    values.iter().copied().find(|&v| v >= x)
+}
+
+/// Brute-force reference for prev_set_at_or_before
+fn expected_prev(values: &[u32], x: u32) -> Option<u32> {
+    values.iter().rev().copied().find(|&v| v <= x)
+}
+
+/// Checks navigation at, around and between every element of the bitmap
+fn check_navigation(bm: &RoaringBitmap) {
+    let values: Vec<u32> = bm.iter().collect();
+    assert_eq!(bm.min(), values.first().copied());
+    assert_eq!(bm.max(), values.last().copied());
+
+    let mut probes = vec![0, u32::MAX];
+    for &v in &values {
+        probes.push(v);
+        probes.push(v.saturating_sub(1));
+        probes.push(v.saturating_add(1));
+    }
+
+    for x in probes {
+        assert_eq!(
+            bm.next_set_at_or_after(x),
+            expected_next(&values, x),
+            "next({})",
+            x
+        );
+        assert_eq!(
+            bm.prev_set_at_or_before(x),
+            expected_prev(&values, x),
+            "prev({})",
+            x
+        );
+    }
+}
+
+// ============================================================================
+// Min and Max
+// ============================================================================
+
+#[test]
+fn navigation_empty_bitmap() {
+    let bm = RoaringBitmap::new();
+    assert_eq!(bm.min(), None);
+    assert_eq!(bm.max(), None);
+    assert_eq!(bm.next_set_at_or_after(0), None);
+    assert_eq!(bm.prev_set_at_or_before(u32::MAX), None);
+}
+
+#[test]
+fn navigation_min_max_single_value() {
+    let bm = bitmap_of(&[12345]);
+    assert_eq!(bm.min(), Some(12345));
+    assert_eq!(bm.max(), Some(12345));
+}
+
+#[test]
+fn navigation_min_max_extremes() {
+    let bm = bitmap_of(&[0, 70_000, u32::MAX]);
+    assert_eq!(bm.min(), Some(0));
+    assert_eq!(bm.max(), Some(u32::MAX));
+}
+
+#[test]
+fn navigation_min_max_each_container_type() {
+    let mut bitmap = RoaringBitmap::new();
+    for i in (100..20_000).step_by(3) {
+        bitmap.insert(i);
+    }
+    assert_eq!(bitmap.container_type(0), Some("Bitmap"));
+    assert_eq!(bitmap.min(), Some(100));
+    assert_eq!(bitmap.max(), Some(19_999));
+
+    let mut run = RoaringBitmap::new();
+    run.extend_consecutive(500..1500);
+    run.extend_consecutive(3000..3010);
+    assert_eq!(run.container_type(0), Some("Run"));
+    assert_eq!(run.min(), Some(500));
+    assert_eq!(run.max(), Some(3009));
+}
+
+// ============================================================================
+// Next / Previous
+// ============================================================================
+
+#[test]
+fn navigation_array_container() {
+    let bm = bitmap_of(&[3, 10, 64, 65, 1000, 65535]);
+    assert_eq!(bm.container_type(0), Some("Array"));
+    check_navigation(&bm);
+}
+
+#[test]
+fn navigation_bitmap_container() {
+    let mut bm = RoaringBitmap::new();
+    for i in 0..5_000 {
+        bm.insert(i * 13);
+    }
+    bm.insert(63);
+    bm.insert(64);
+    bm.insert(65535);
+    assert_eq!(bm.container_type(0), Some("Bitmap"));
+    check_navigation(&bm);
+}
+
+#[test]
+fn navigation_bitmap_container_long_gaps() {
+    // Word scans must cross many empty words
+    let mut bm = RoaringBitmap::new();
+    bm.extend_sparse([0, 1, 2, 3, 4]);
+    for i in 0..5_000 {
+        bm.insert(50_000 + i * 3);
+    }
+    assert_eq!(bm.container_type(0), Some("Bitmap"));
+
+    assert_eq!(bm.next_set_at_or_after(5), Some(50_000));
+    assert_eq!(bm.next_set_at_or_after(50_001), Some(50_003));
+    assert_eq!(bm.prev_set_at_or_before(49_999), Some(4));
+    assert_eq!(bm.prev_set_at_or_before(65_535), Some(50_000 + 4_999 * 3));
+    assert_eq!(bm.next_set_at_or_after(50_000 + 4_999 * 3 + 1), None);
+}
+
+#[test]
+fn navigation_run_container() {
+    let mut bm = RoaringBitmap::new();
+    bm.extend_consecutive(10..20);
+    bm.extend_consecutive(30..31);
+    bm.extend_consecutive(65000..65536);
+    assert_eq!(bm.container_type(0), Some("Run"));
+    check_navigation(&bm);
+
+    assert_eq!(bm.next_set_at_or_after(15), Some(15));
+    assert_eq!(bm.next_set_at_or_after(20), Some(30));
+    assert_eq!(bm.prev_set_at_or_before(29), Some(19));
+    assert_eq!(bm.prev_set_at_or_before(64_999), Some(30));
+}
+
+#[test]
+fn navigation_across_containers() {
+    let mut bm = RoaringBitmap::new();
+    bm.extend_sparse([5, 17, 1000]); // Array in container 0
+    for i in 0..5_000 {
+        bm.insert(2 * 65536 + i * 7); // Bitmap in container 2
+    }
+    bm.extend_consecutive(5 * 65536 + 10..5 * 65536 + 90); // Run in container 5
+    bm.insert(u32::MAX);
+
+    check_navigation(&bm);
+
+    // Missing containers are skipped in both directions
+    assert_eq!(bm.next_set_at_or_after(1001), Some(2 * 65536));
+    assert_eq!(bm.next_set_at_or_after(3 * 65536), Some(5 * 65536 + 10));
+    assert_eq!(bm.prev_set_at_or_before(2 * 65536 - 1), Some(1000));
+    assert_eq!(
+        bm.prev_set_at_or_before(5 * 65536 + 9),
+        Some(2 * 65536 + 4_999 * 7)
+    );
+    assert_eq!(bm.next_set_at_or_after(5 * 65536 + 90), Some(u32::MAX));
+}
+
+#[test]
+fn navigation_pagination() {
+    let mut bm = RoaringBitmap::new();
+    for i in 0..1000 {
+        bm.insert(i * 1_000);
+    }
+
+    // Page through ids ten at a time using next_set_at_or_after
+    let mut pages = Vec::new();
+    let mut cursor = bm.min();
+    while let Some(start) = cursor {
+        let mut page = Vec::new();
+        let mut next = Some(start);
+        while let Some(value) = next {
+            if page.len() == 10 {
+                break;
+            }
+            page.push(value);
+            next = value
+                .checked_add(1)
+                .and_then(|v| bm.next_set_at_or_after(v));
+        }
+        cursor = next;
+        pages.push(page);
+    }
+
+    assert_eq!(pages.len(), 100);
+    let flattened: Vec<u32> = pages.into_iter().flatten().collect();
+    assert!(flattened.iter().copied().eq(bm.iter()));
+}
@@ -1,9 +1,9 @@
-/// Tests for operator overloads that consume owned values
-/// These test the BitOr/BitAnd/BitXor/Sub implementations for:
-/// - RoaringBitmap op &RoaringBitmap
-/// - &RoaringBitmap op RoaringBitmap
-///
-/// This is separate from the regular operator tests which use references.
+// Tests for operator overloads that consume owned values
+// These test the BitOr/BitAnd/BitXor/Sub implementations for:
+// - RoaringBitmap op &RoaringBitmap
+// - &RoaringBitmap op RoaringBitmap
+//
+// This is separate from the regular operator tests which use references.
 
 use crate::functional::{bitmap_of, expect_bitmap};
 use roaring_bitmap::RoaringBitmap;
@@ -0,0 +1,169 @@
+use crate::functional::bitmap_of;
+use roaring_bitmap::RoaringBitmap;
+use std::ops::Bound;
+
+/// Bitmap with Array, Bitmap and Run containers and gaps between keys
+fn mixed_bitmap() -> RoaringBitmap {
+    let mut bm = RoaringBitmap::new();
+    bm.extend_sparse([0, 5, 17, 1000, 65535]); // Array in container 0
+    for i in 0..10_000 {
+        bm.insert(65536 + i * 3); // Bitmap in container 1
+    }
+    bm.extend_consecutive(3 * 65536 + 10..3 * 65536 + 20); // Run in container 3
+    bm.extend_consecutive(3 * 65536 + 100..3 * 65536 + 300);
+    bm.insert(u32::MAX);
+    bm
+}
+
+/// Checks range() and range_cardinality() against a filter over iter()
+fn check_range(bm: &RoaringBitmap, start: u32, end: u32) {
+    let expected: Vec<u32> = bm.iter().filter(|&v| v >= start && v <= end).collect();
+
+    let actual: Vec<u32> = bm.range(start..=end).collect();
+    assert_eq!(actual, expected, "range({}..={})", start, end);
+    assert_eq!(bm.range(start..=end).len(), expected.len());
+    assert_eq!(bm.range_cardinality(start..=end), expected.len() as u64);
+
+    let mut reversed: Vec<u32> = bm.range(start..=end).rev().collect();
+    reversed.reverse();
+    assert_eq!(reversed, expected, "rev range({}..={})", start, end);
+}
+
+// ============================================================================
+// Range Iteration
+// ============================================================================
+
+#[test]
+fn range_on_empty_bitmap() {
+    let bm = RoaringBitmap::new();
+    assert_eq!(bm.range(..).next(), None);
+    assert_eq!(bm.range_cardinality(..), 0);
+}
+
+#[test]
+fn range_full_matches_iter() {
+    let bm = mixed_bitmap();
+    assert!(bm.range(..).eq(bm.iter()));
+    assert_eq!(bm.range_cardinality(..), bm.len());
+}
+
+#[test]
+fn range_within_each_container_type() {
+    let bm = mixed_bitmap();
+    check_range(&bm, 1, 1000); // Array
+    check_range(&bm, 65536 + 100, 65536 + 200); // Bitmap
+    check_range(&bm, 65536 + 63, 65536 + 129); // Bitmap word edges
+    check_range(&bm, 3 * 65536 + 15, 3 * 65536 + 150); // Run
+    check_range(&bm, 3 * 65536 + 20, 3 * 65536 + 99); // Between runs
+}
+
+#[test]
+fn range_across_containers() {
+    let bm = mixed_bitmap();
+    check_range(&bm, 17, 65536 + 10);
+    check_range(&bm, 1000, 3 * 65536 + 12);
+    check_range(&bm, 2 * 65536, 3 * 65536 + 150); // Starts in a missing key
+    check_range(&bm, 65536 + 5, 2 * 65536 + 5); // Ends in a missing key
+    check_range(&bm, 4 * 65536, u32::MAX);
+}
+
+#[test]
+fn range_exhaustive_small_bitmap() {
+    let bm = bitmap_of(&[1, 3, 5, 64, 65, 127, 128, 65535, 65536, 65537]);
+    let probes = [
+        0, 1, 2, 3, 4, 5, 6, 63, 64, 65, 66, 127, 128, 129, 65535, 65536, 65537,
+    ];
+    for &start in &probes {
+        for &end in &probes {
+            if start <= end {
+                check_range(&bm, start, end);
+            }
+        }
+    }
+}
+
+#[test]
+fn range_bound_kinds() {
+    let bm = bitmap_of(&[0, 1, 2, 3, 4, 5, 6, u32::MAX]);
+
+    assert_eq!(bm.range(2..5).collect::<Vec<_>>(), vec![2, 3, 4]);
+    assert_eq!(bm.range(2..=5).collect::<Vec<_>>(), vec![2, 3, 4, 5]);
+    assert_eq!(bm.range(..2).collect::<Vec<_>>(), vec![0, 1]);
+    assert_eq!(bm.range(6..).collect::<Vec<_>>(), vec![6, u32::MAX]);
+    assert_eq!(
+        bm.range((Bound::Excluded(2), Bound::Excluded(5)))
+            .collect::<Vec<_>>(),
+        vec![3, 4]
+    );
+    assert_eq!(bm.range_cardinality(2..5), 3);
+    assert_eq!(bm.range_cardinality(..), 8);
+}
+
+#[test]
+#[allow(clippy::reversed_empty_ranges)]
+fn range_empty_ranges() {
+    let bm = bitmap_of(&[0, 1, 2, u32::MAX]);
+
+    assert_eq!(bm.range(0..0).next(), None);
+    assert_eq!(bm.range(5..2).next(), None);
+    assert_eq!(bm.range_cardinality(0..0), 0);
+    assert_eq!(bm.range_cardinality(5..2), 0);
+
+    // Excluded bounds at the ends of the u32 domain
+    let past_max = (Bound::Excluded(u32::MAX), Bound::Unbounded);
+    assert_eq!(bm.range(past_max).next(), None);
+    assert_eq!(bm.range_cardinality(past_max), 0);
+}
+
+#[test]
+fn range_iterator_can_seek() {
+    let bm = mixed_bitmap();
+    let mut iter = bm.range(10..3 * 65536 + 200);
+    iter.advance_to(65536 + 4);
+    assert_eq!(iter.next(), Some(65536 + 6));
+    iter.advance_to(3 * 65536 + 199);
+    assert_eq!(iter.next(), Some(3 * 65536 + 199));
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn range_pagination() {
+    let mut bm = RoaringBitmap::new();
+    for i in 0..1000 {
+        bm.insert(i * 997);
+    }
+
+    let page_size = 20_000;
+    let mut seen = Vec::new();
+    let mut start = 0u32;
+    while start < 1000 * 997 {
+        seen.extend(bm.range(start..start + page_size));
+        start += page_size;
+    }
+    assert!(seen.iter().copied().eq(bm.iter()));
+}
+
+// ============================================================================
+// Range Cardinality
+// ============================================================================
+
+#[test]
+fn range_cardinality_full_run_container() {
+    let mut bm = RoaringBitmap::new();
+    bm.extend_consecutive(0..10 * 65536);
+    assert_eq!(bm.range_cardinality(..), 10 * 65536);
+    assert_eq!(
+        bm.range_cardinality(100..5 * 65536 + 7),
+        5 * 65536 + 7 - 100
+    );
+    assert_eq!(bm.range_cardinality(65536..=65536), 1);
+}
+
+#[test]
+fn range_cardinality_matches_rank() {
+    let bm = mixed_bitmap();
+    for (start, end) in [(1, 70_000), (65536 + 7, 3 * 65536 + 250), (0, u32::MAX)] {
+        let expected = bm.rank(end) - if start > 0 { bm.rank(start - 1) } else { 0 };
+        assert_eq!(bm.range_cardinality(start..=end), expected);
+    }
+}
@@ -0,0 +1,217 @@
+// Tests for range flipping, complement and range predicates, checked against
+// a BTreeSet reference across Array, Bitmap and Run containers
+
+use super::{bitmap_of, expect_bitmap, random_bitmap, Rng};
+use roaring_bitmap::RoaringBitmap;
+use std::collections::BTreeSet;
+use std::ops::Bound;
+
+/// A random inclusive range within keys 0..4, often crossing container
+/// boundaries and sometimes a single value
+fn random_range(rng: &mut Rng) -> (u32, u32) {
+    let start = rng.below(4 << 16);
+    let len = match rng.next() >> 62 {
+        0 => 0,
+        1 => rng.below(64),
+        2 => rng.below(5000),
+        _ => rng.below(150_000),
+    };
+    (start, start.saturating_add(len).min((4 << 16) - 1))
+}
+
+// ============================================================================
+// flip
+// ============================================================================
+
+#[test]
+fn flip_matches_reference_across_container_types() {
+    let mut rng = Rng(0xF11F_0001);
+    for _ in 0..40 {
+        let mut bm = random_bitmap(&mut rng, 3, 65_536);
+        let mut reference: BTreeSet<u32> = bm.iter().collect();
+
+        for _ in 0..3 {
+            let (start, end) = random_range(&mut rng);
+            bm.flip(start..=end);
+            for value in start..=end {
+                if !reference.remove(&value) {
+                    reference.insert(value);
+                }
+            }
+            expect_bitmap(&bm, &reference.iter().copied().collect::<Vec<_>>());
+        }
+    }
+}
+
+#[test]
+fn flipping_twice_restores_the_bitmap() {
+    let mut rng = Rng(0xF11F_0002);
+    for _ in 0..20 {
+        let original = random_bitmap(&mut rng, 3, 65_536);
+        let (start, end) = random_range(&mut rng);
+
+        let mut bm = original.clone();
+        bm.flip(start..=end);
+        bm.flip(start..=end);
+        assert_eq!(bm, original);
+    }
+}
+
+#[test]
+fn flip_creates_and_drops_containers() {
+    // Missing keys become single runs
+    let mut bm = bitmap_of(&[5]);
+    bm.flip(65_000..200_000);
+    assert_eq!(bm.len(), 135_001);
+    assert_eq!(bm.container_type(1), Some("Run"));
+    assert_eq!(bm.container_type(2), Some("Run"));
+
+    // Flipping a full container away removes its key
+    bm.flip(65_536..131_072);
+    assert_eq!(bm.container_type(1), None);
+    assert_eq!(bm.len(), 1 + 536 + (200_000 - 131_072));
+
+    // A dense bitmap flipped to a few values comes back as an Array
+    let mut dense: RoaringBitmap = (0..10_000).collect();
+    assert_eq!(dense.container_type(0), Some("Bitmap"));
+    dense.flip(3..9998);
+    expect_bitmap(&dense, &[0, 1, 2, 9998, 9999]);
+    assert_eq!(dense.container_type(0), Some("Array"));
+
+    // Flipping the whole domain complements everything
+    let mut edges = bitmap_of(&[0, u32::MAX]);
+    edges.flip(..);
+    assert_eq!(edges.len(), (1u64 << 32) - 2);
+    assert!(!edges.contains(0) && !edges.contains(u32::MAX));
+    assert_eq!(edges.min(), Some(1));
+    assert_eq!(edges.max(), Some(u32::MAX - 1));
+}
+
+// ============================================================================
+// complement_within
+// ============================================================================
+
+#[test]
+fn complement_within_matches_reference_and_flip() {
+    let mut rng = Rng(0xF11F_0003);
+    for _ in 0..40 {
+        let bm = random_bitmap(&mut rng, 3, 65_536);
+        let before = bm.clone();
+        let (start, end) = random_range(&mut rng);
+
+        let complement = bm.complement_within(start..=end);
+        let expected: Vec<u32> = (start..=end).filter(|&v| !bm.contains(v)).collect();
+        expect_bitmap(&complement, &expected);
+        assert_eq!(bm, before);
+
+        let mut window = RoaringBitmap::new();
+        window.extend_consecutive(start..=end);
+        let mut flipped = bm.clone();
+        flipped.flip(start..=end);
+        assert_eq!(complement, &flipped & &window);
+    }
+}
+
+#[test]
+fn complement_within_edges() {
+    let bm = bitmap_of(&[2, 3, 7, 70_000]);
+
+    expect_bitmap(&bm.complement_within(0..10), &[0, 1, 4, 5, 6, 8, 9]);
+    assert!(bm.complement_within(2..=3).is_empty());
+    assert!(bm.complement_within(70_000..=70_000).is_empty());
+
+    // Missing containers are filled whole
+    let gap = bm.complement_within(65_536..=70_001);
+    assert_eq!(gap.len(), 70_001 - 65_536);
+    assert!(!gap.contains(70_000));
+    assert_eq!(gap.container_type(1), Some("Run"));
+
+    assert_eq!(RoaringBitmap::new().complement_within(..).len(), 1u64 << 32);
+}
+
+// ============================================================================
+// contains_range and intersects_range
+// ============================================================================
+
+#[test]
+fn range_predicates_match_reference() {
+    let mut rng = Rng(0xF11F_0004);
+    for _ in 0..40 {
+        let bm = random_bitmap(&mut rng, 3, 65_536);
+        let reference: BTreeSet<u32> = bm.iter().collect();
+
+        for _ in 0..10 {
+            let (start, end) = random_range(&mut rng);
+            assert_eq!(
+                bm.contains_range(start..=end),
+                (start..=end).all(|v| reference.contains(&v)),
+                "contains_range({start}..={end})"
+            );
+            assert_eq!(
+                bm.intersects_range(start..=end),
+                reference.range(start..=end).next().is_some(),
+                "intersects_range({start}..={end})"
+            );
+        }
+
+        // Ranges lying inside one of the bitmap's own runs
+        if let Some(value) = bm.iter().nth(bm.len() as usize / 2) {
+            let mut end = value;
+            while end < u32::MAX && bm.contains(end + 1) {
+                end += 1;
+            }
+            assert!(bm.contains_range(value..=end));
+            assert_eq!(bm.contains_range(value..=end + 1), end == u32::MAX);
+        }
+    }
+}
+
+#[test]
+fn range_predicates_per_container_type() {
+    let array = bitmap_of(&[10, 11, 12, 13, 20]);
+    let bitmap: RoaringBitmap = (0..10_000).filter(|v| v % 1000 != 999).collect();
+    let mut run = RoaringBitmap::new();
+    run.extend_consecutive(100..200);
+    run.extend_consecutive(300..65_536 + 50);
+    assert_eq!(array.container_type(0), Some("Array"));
+    assert_eq!(bitmap.container_type(0), Some("Bitmap"));
+    assert_eq!(run.container_type(0), Some("Run"));
+
+    assert!(array.contains_range(10..14));
+    assert!(!array.contains_range(10..15));
+    assert!(!array.contains_range(9..13));
+    assert!(array.intersects_range(14..=20));
+    assert!(!array.intersects_range(14..20));
+
+    assert!(bitmap.contains_range(0..999));
+    assert!(bitmap.contains_range(1000..1999));
+    assert!(!bitmap.contains_range(0..=999));
+    assert!(bitmap.intersects_range(999..=1000));
+    assert!(!bitmap.intersects_range(10_000..));
+
+    assert!(run.contains_range(100..200));
+    assert!(!run.contains_range(100..201));
+    assert!(run.contains_range(300..65_536 + 50));
+    assert!(!run.contains_range(300..65_536 + 51));
+    assert!(!run.intersects_range(200..300));
+    assert!(run.intersects_range(199..300));
+}
+
+#[test]
+fn empty_ranges_are_no_ops() {
+    let bm = bitmap_of(&[0, 5, 100_000]);
+    let empty = (Bound::Excluded(5), Bound::Excluded(6));
+
+    let mut flipped = bm.clone();
+    flipped.flip(5..5);
+    flipped.flip(empty);
+    flipped.flip(..0);
+    assert_eq!(flipped, bm);
+
+    assert!(bm.complement_within(empty).is_empty());
+    assert!(bm.complement_within(..0).is_empty());
+    assert!(bm.contains_range(empty));
+    assert!(RoaringBitmap::new().contains_range(7..7));
+    assert!(!bm.intersects_range(empty));
+    assert!(!bm.intersects_range(..0));
+}
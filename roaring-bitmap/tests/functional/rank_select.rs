@@ -0,0 +1,168 @@
+use crate::functional::bitmap_of;
+use roaring_bitmap::RoaringBitmap;
+
+/// Checks rank and select against a sorted list of the bitmap's values
+fn check_rank_select(bm: &RoaringBitmap) {
+    let values: Vec<u32> = bm.iter().collect();
+
+    for (k, &value) in values.iter().enumerate() {
+        assert_eq!(bm.select(k as u64), Some(value), "select({})", k);
+        assert_eq!(bm.rank(value), k as u64 + 1, "rank({})", value);
+        if value > 0 && !bm.contains(value - 1) {
+            assert_eq!(bm.rank(value - 1), k as u64, "rank({})", value - 1);
+        }
+    }
+
+    assert_eq!(bm.select(values.len() as u64), None);
+    assert_eq!(bm.rank(u32::MAX), bm.len());
+}
+
+// ============================================================================
+// Empty and Small Bitmaps
+// ============================================================================
+
+#[test]
+fn rank_select_empty_bitmap() {
+    let bm = RoaringBitmap::new();
+    assert_eq!(bm.rank(0), 0);
+    assert_eq!(bm.rank(u32::MAX), 0);
+    assert_eq!(bm.select(0), None);
+}
+
+#[test]
+fn rank_select_small_bitmap() {
+    let bm = bitmap_of(&[10, 20, 30]);
+
+    assert_eq!(bm.rank(0), 0);
+    assert_eq!(bm.rank(9), 0);
+    assert_eq!(bm.rank(10), 1);
+    assert_eq!(bm.rank(15), 1);
+    assert_eq!(bm.rank(20), 2);
+    assert_eq!(bm.rank(30), 3);
+    assert_eq!(bm.rank(1_000_000), 3);
+
+    assert_eq!(bm.select(0), Some(10));
+    assert_eq!(bm.select(1), Some(20));
+    assert_eq!(bm.select(2), Some(30));
+    assert_eq!(bm.select(3), None);
+    assert_eq!(bm.select(u64::MAX), None);
+}
+
+#[test]
+fn rank_select_extreme_values() {
+    let bm = bitmap_of(&[0, u32::MAX]);
+    assert_eq!(bm.rank(0), 1);
+    assert_eq!(bm.rank(u32::MAX - 1), 1);
+    assert_eq!(bm.rank(u32::MAX), 2);
+    assert_eq!(bm.select(0), Some(0));
+    assert_eq!(bm.select(1), Some(u32::MAX));
+}
+
+// ============================================================================
+// Container Types
+// ============================================================================
+
+#[test]
+fn rank_select_array_container() {
+    let bm = bitmap_of(&[1, 5, 9, 100, 1000, 65535]);
+    assert_eq!(bm.container_type(0), Some("Array"));
+    check_rank_select(&bm);
+}
+
+#[test]
+fn rank_select_bitmap_container() {
+    let mut bm = RoaringBitmap::new();
+    for i in (0..65536).step_by(3) {
+        bm.insert(i);
+    }
+    assert_eq!(bm.container_type(0), Some("Bitmap"));
+    check_rank_select(&bm);
+
+    // Values within and at the edges of words
+    assert_eq!(bm.rank(63), 22);
+    assert_eq!(bm.rank(64), 22);
+    assert_eq!(bm.rank(65535), 21846);
+}
+
+#[test]
+fn rank_select_run_container() {
+    let mut bm = RoaringBitmap::new();
+    bm.extend_consecutive(100..200);
+    bm.extend_consecutive(300..301);
+    bm.extend_consecutive(1000..5000);
+    assert_eq!(bm.container_type(0), Some("Run"));
+    check_rank_select(&bm);
+
+    assert_eq!(bm.rank(99), 0);
+    assert_eq!(bm.rank(150), 51);
+    assert_eq!(bm.rank(250), 100);
+    assert_eq!(bm.rank(300), 101);
+    assert_eq!(bm.select(100), Some(300));
+    assert_eq!(bm.select(101), Some(1000));
+}
+
+#[test]
+fn rank_select_full_run_container() {
+    let mut bm = RoaringBitmap::new();
+    bm.extend_consecutive(0..65536);
+    assert_eq!(bm.container_type(0), Some("Run"));
+
+    assert_eq!(bm.rank(0), 1);
+    assert_eq!(bm.rank(65535), 65536);
+    assert_eq!(bm.select(0), Some(0));
+    assert_eq!(bm.select(65535), Some(65535));
+    assert_eq!(bm.select(65536), None);
+}
+
+#[test]
+fn rank_select_full_bitmap_container() {
+    // Evens first so the container becomes a Bitmap rather than a Run
+    let mut bm = RoaringBitmap::new();
+    for i in (0..65536).step_by(2) {
+        bm.insert(i);
+    }
+    for i in (1..65536).step_by(2) {
+        bm.insert(i);
+    }
+    assert_eq!(bm.container_type(0), Some("Bitmap"));
+    assert_eq!(bm.len(), 65536);
+
+    assert_eq!(bm.rank(65535), 65536);
+    assert_eq!(bm.select(65535), Some(65535));
+}
+
+// ============================================================================
+// Multiple Containers
+// ============================================================================
+
+#[test]
+fn rank_select_across_containers() {
+    let mut bm = RoaringBitmap::new();
+    bm.extend_sparse([5, 17, 1000]); // Array in container 0
+    for i in 0..5_000 {
+        bm.insert(2 * 65536 + i * 7); // Bitmap in container 2
+    }
+    bm.extend_consecutive(5 * 65536 + 10..5 * 65536 + 90); // Run in container 5
+
+    check_rank_select(&bm);
+
+    // Value in a missing container counts everything before it
+    assert_eq!(bm.rank(65536 + 500), 3);
+    assert_eq!(bm.rank(4 * 65536), 5_003);
+    assert_eq!(bm.select(3), Some(2 * 65536));
+    assert_eq!(bm.select(5_003), Some(5 * 65536 + 10));
+}
+
+#[test]
+fn rank_select_after_removals() {
+    let mut bm = RoaringBitmap::new();
+    bm.extend_consecutive(0..10_000);
+    bm.remove_range(2_000..3_000);
+    bm.remove(5_000);
+
+    assert_eq!(bm.rank(2_500), 2_000);
+    assert_eq!(bm.rank(5_000), 4_000);
+    assert_eq!(bm.select(2_000), Some(3_000));
+    assert_eq!(bm.select(3_999), Some(4_999));
+    assert_eq!(bm.select(4_000), Some(5_001));
+}
@@ -0,0 +1,265 @@
+// Tests for the native Run container set algebra (Run×Run, Run×Array,
+// Run×Bitmap), checked against a BTreeSet reference
+
+use super::Rng;
+use roaring_bitmap::RoaringBitmap;
+use std::collections::BTreeSet;
+
+/// Builds a single Run container in key 0 from random runs
+fn random_runs(rng: &mut Rng, max_runs: u32, max_len: u32) -> RoaringBitmap {
+    let mut bm = RoaringBitmap::new();
+    let mut start = rng.below(64);
+    for _ in 0..=rng.below(max_runs) {
+        let len = 1 + rng.below(max_len);
+        let end = (start + len).min(65536);
+        bm.extend_consecutive(start..end);
+        // Gaps of 0 make runs touch so the merge logic gets exercised
+        start = end + rng.below(300);
+        if start >= 65536 {
+            break;
+        }
+    }
+    assert_eq!(bm.container_type(0), Some("Run"));
+    bm
+}
+
+/// Builds a single Array container in key 0
+fn random_array(rng: &mut Rng) -> RoaringBitmap {
+    let mut bm = RoaringBitmap::new();
+    for _ in 0..1 + rng.below(2000) {
+        bm.insert(rng.below(65536));
+    }
+    assert_eq!(bm.container_type(0), Some("Array"));
+    bm
+}
+
+/// Builds a single Bitmap container in key 0
+fn random_bitmap(rng: &mut Rng) -> RoaringBitmap {
+    let mut bm = RoaringBitmap::new();
+    while bm.len() < 5000 {
+        bm.insert(rng.below(65536));
+    }
+    assert_eq!(bm.container_type(0), Some("Bitmap"));
+    bm
+}
+
+fn set_of(bm: &RoaringBitmap) -> BTreeSet<u32> {
+    bm.iter().collect()
+}
+
+/// Asserts the result matches the reference and survives a serialization
+/// round trip, which rejects overlapping or adjacent runs
+fn assert_matches(result: &RoaringBitmap, expected: &BTreeSet<u32>, context: &str) {
+    assert_eq!(result.len(), expected.len() as u64, "{}", context);
+    assert!(result.iter().eq(expected.iter().copied()), "{}", context);
+
+    let mut bytes = Vec::new();
+    result.serialize_into(&mut bytes).unwrap();
+    let restored = RoaringBitmap::deserialize_from(&bytes[..]).unwrap();
+    assert!(restored.iter().eq(result.iter()), "{}", context);
+}
+
+/// Checks all four operations in both directions against BTreeSet
+fn check_all_operations(a: &RoaringBitmap, b: &RoaringBitmap) {
+    let (sa, sb) = (set_of(a), set_of(b));
+
+    assert_matches(&a.union(b), &sa.union(&sb).copied().collect(), "a | b");
+    assert_matches(&b.union(a), &sa.union(&sb).copied().collect(), "b | a");
+    assert_matches(
+        &a.intersection(b),
+        &sa.intersection(&sb).copied().collect(),
+        "a & b",
+    );
+    assert_matches(
+        &b.intersection(a),
+        &sa.intersection(&sb).copied().collect(),
+        "b & a",
+    );
+    assert_matches(
+        &a.difference(b),
+        &sa.difference(&sb).copied().collect(),
+        "a - b",
+    );
+    assert_matches(
+        &b.difference(a),
+        &sb.difference(&sa).copied().collect(),
+        "b - a",
+    );
+    assert_matches(
+        &a.symmetric_difference(b),
+        &sa.symmetric_difference(&sb).copied().collect(),
+        "a ^ b",
+    );
+    assert_matches(
+        &b.symmetric_difference(a),
+        &sa.symmetric_difference(&sb).copied().collect(),
+        "b ^ a",
+    );
+}
+
+// ============================================================================
+// Run × Run
+// ============================================================================
+
+#[test]
+fn run_run_operations_match_reference() {
+    let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+    for _ in 0..20 {
+        let a = random_runs(&mut rng, 40, 2000);
+        let b = random_runs(&mut rng, 40, 2000);
+        check_all_operations(&a, &b);
+    }
+}
+
+#[test]
+fn run_run_touching_runs_merge() {
+    let mut a = RoaringBitmap::new();
+    a.extend_consecutive(0..100);
+    a.extend_consecutive(200..300);
+    let mut b = RoaringBitmap::new();
+    b.extend_consecutive(100..200);
+
+    let union = a.union(&b);
+    assert_eq!(union.container_type(0), Some("Run"));
+    assert_eq!(union.len(), 300);
+    // A single merged run serializes to one run
+    assert_eq!(union.serialized_size(), 4 + 1 + 4 + 2 + 4);
+    check_all_operations(&a, &b);
+}
+
+#[test]
+fn run_run_full_container() {
+    let mut full = RoaringBitmap::new();
+    full.extend_consecutive(0..65536);
+    let mut rng = Rng(42);
+    let other = random_runs(&mut rng, 30, 3000);
+
+    check_all_operations(&full, &other);
+    assert!(full.symmetric_difference(&full).is_empty());
+    assert!(full.difference(&full).is_empty());
+    assert_eq!(full.intersection(&full).len(), 65536);
+}
+
+#[test]
+fn run_run_results_stay_runs() {
+    let mut a = RoaringBitmap::new();
+    let mut b = RoaringBitmap::new();
+    for key in 0..50u32 {
+        a.extend_consecutive(key * 65536..key * 65536 + 40_000);
+        b.extend_consecutive(key * 65536 + 20_000..key * 65536 + 60_000);
+    }
+
+    // Range-heavy operands keep compact Run results in every container
+    for result in [
+        a.union(&b),
+        a.intersection(&b),
+        a.difference(&b),
+        a.symmetric_difference(&b),
+    ] {
+        assert!(result.container_stats().iter().all(|s| s.1 == "Run"));
+        // Materialized values would need ~80KB per container
+        assert!(result.memory_usage() < 50 * 256);
+    }
+    assert_eq!(a.symmetric_difference(&b).len(), 50 * 40_000);
+}
+
+// ============================================================================
+// Run × Array
+// ============================================================================
+
+#[test]
+fn run_array_operations_match_reference() {
+    let mut rng = Rng(0xDEAD_BEEF);
+    for _ in 0..20 {
+        let run = random_runs(&mut rng, 20, 3000);
+        let array = random_array(&mut rng);
+        check_all_operations(&run, &array);
+    }
+}
+
+#[test]
+fn run_array_union_keeps_large_result_compact() {
+    let mut run = RoaringBitmap::new();
+    run.extend_consecutive(0..60_000);
+    let mut array = RoaringBitmap::new();
+    array.extend_sparse([10, 60_000, 60_001, 65_000]);
+
+    let union = run.union(&array);
+    assert_eq!(union.container_type(0), Some("Run"));
+    assert_eq!(union.len(), 60_003);
+
+    let xor = run.symmetric_difference(&array);
+    assert_eq!(xor.container_type(0), Some("Run"));
+    assert_eq!(xor.len(), 60_002);
+    assert!(!xor.contains(10));
+    assert!(xor.contains(65_000));
+}
+
+#[test]
+fn run_array_small_result_becomes_array() {
+    let mut run = RoaringBitmap::new();
+    for i in 0..100u32 {
+        run.extend_consecutive(i * 100..i * 100 + 2);
+    }
+    assert_eq!(run.container_type(0), Some("Run"));
+    let mut array = RoaringBitmap::new();
+    array.extend_sparse([0, 1, 100, 50_000]);
+
+    // Many tiny runs take more space than the values themselves
+    let xor = run.symmetric_difference(&array);
+    assert_eq!(xor.container_type(0), Some("Array"));
+    assert_eq!(xor.len(), 200 - 3 + 1);
+}
+
+// ============================================================================
+// Run × Bitmap
+// ============================================================================
+
+#[test]
+fn run_bitmap_operations_match_reference() {
+    let mut rng = Rng(0x1234_5678_9ABC);
+    for _ in 0..15 {
+        let run = random_runs(&mut rng, 30, 4000);
+        let bitmap = random_bitmap(&mut rng);
+        check_all_operations(&run, &bitmap);
+    }
+}
+
+#[test]
+fn run_bitmap_word_boundaries() {
+    let mut run = RoaringBitmap::new();
+    run.extend_consecutive(63..65);
+    run.extend_consecutive(127..=128);
+    run.extend_consecutive(1000..1064);
+    run.extend_consecutive(65472..65536);
+
+    let mut bitmap = RoaringBitmap::new();
+    for i in 0..32_768u32 {
+        bitmap.insert(i * 2);
+    }
+    check_all_operations(&run, &bitmap);
+}
+
+#[test]
+fn run_bitmap_result_types() {
+    let mut run = RoaringBitmap::new();
+    run.extend_consecutive(0..100);
+    let mut bitmap = RoaringBitmap::new();
+    for i in 0..10_000u32 {
+        bitmap.insert(i * 3);
+    }
+
+    assert_eq!(run.union(&bitmap).container_type(0), Some("Bitmap"));
+    assert_eq!(run.intersection(&bitmap).container_type(0), Some("Array"));
+    assert_eq!(run.difference(&bitmap).container_type(0), Some("Array"));
+    assert_eq!(bitmap.difference(&run).container_type(0), Some("Bitmap"));
+    assert_eq!(
+        bitmap.symmetric_difference(&run).container_type(0),
+        Some("Bitmap")
+    );
+
+    // Clearing a bitmap down to nothing drops the container
+    let mut full = RoaringBitmap::new();
+    full.extend_consecutive(0..65536);
+    assert!(bitmap.difference(&full).is_empty());
+}
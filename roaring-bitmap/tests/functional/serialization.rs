@@ -0,0 +1,295 @@
+use crate::functional::{bitmap_of, expect_bitmap};
+use roaring_bitmap::RoaringBitmap;
+use std::io::ErrorKind;
+
+/// Serializes and deserializes a bitmap, checking the reported size along the way
+fn round_trip(bm: &RoaringBitmap) -> RoaringBitmap {
+    let mut bytes = Vec::new();
+    bm.serialize_into(&mut bytes).unwrap();
+    assert_eq!(bytes.len(), bm.serialized_size());
+    RoaringBitmap::deserialize_from(&bytes[..]).unwrap()
+}
+
+/// Deserializes bytes that are expected to be rejected, returning the error kind
+fn deserialize_err(bytes: &[u8]) -> ErrorKind {
+    match RoaringBitmap::deserialize_from(bytes) {
+        Ok(_) => panic!("expected deserialization to fail"),
+        Err(err) => err.kind(),
+    }
+}
+
+fn serialize(bm: &RoaringBitmap) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bm.serialize_into(&mut bytes).unwrap();
+    bytes
+}
+
+// ============================================================================
+// Round Trips
+// ============================================================================
+
+#[test]
+fn serialize_empty_bitmap() {
+    let bm = RoaringBitmap::new();
+    let bytes = serialize(&bm);
+
+    // Cookie + zero container count
+    assert_eq!(bytes, vec![0x3A, 0x30, 0, 0, 0, 0, 0, 0]);
+
+    let restored = RoaringBitmap::deserialize_from(&bytes[..]).unwrap();
+    assert!(restored.is_empty());
+}
+
+#[test]
+fn serialize_round_trip_array_containers() {
+    let bm = bitmap_of(&[0, 1, 100, 65535, 65536, 1_000_000, u32::MAX]);
+    let restored = round_trip(&bm);
+    expect_bitmap(&restored, &[0, 1, 100, 65535, 65536, 1_000_000, u32::MAX]);
+}
+
+#[test]
+fn serialize_round_trip_bitmap_container() {
+    let mut bm = RoaringBitmap::new();
+    for i in (0..20_000).step_by(2) {
+        bm.insert(i);
+    }
+    assert_eq!(bm.container_type(0), Some("Bitmap"));
+
+    let restored = round_trip(&bm);
+    assert_eq!(restored.container_type(0), Some("Bitmap"));
+    assert_eq!(restored.len(), bm.len());
+    assert!(restored.iter().eq(bm.iter()));
+}
+
+#[test]
+fn serialize_round_trip_run_containers() {
+    let mut bm = RoaringBitmap::new();
+    bm.extend_consecutive(0..200_000);
+    bm.extend_consecutive(500_000..500_010);
+
+    let restored = round_trip(&bm);
+    assert_eq!(restored.container_type(0), Some("Run"));
+    assert_eq!(restored.len(), 200_010);
+    assert!(restored.iter().eq(bm.iter()));
+}
+
+#[test]
+fn serialize_round_trip_mixed_containers() {
+    let mut bm = RoaringBitmap::new();
+    bm.extend_sparse([5, 17, 1000]); // Array in container 0
+    for i in 0..10_000 {
+        bm.insert(65536 + i * 3); // Bitmap in container 1
+    }
+    bm.extend_consecutive(3 * 65536..4 * 65536); // Full Run in container 3
+    bm.extend_consecutive(10 * 65536 + 100..10 * 65536 + 200); // Run in container 10
+
+    let stats: Vec<&str> = bm.container_stats().iter().map(|s| s.1).collect();
+    assert_eq!(stats, vec!["Array", "Bitmap", "Run", "Run"]);
+
+    let restored = round_trip(&bm);
+    assert_eq!(restored.container_stats(), bm.container_stats());
+    assert!(restored.iter().eq(bm.iter()));
+}
+
+#[test]
+fn serialize_round_trip_many_run_containers_with_offsets() {
+    // With Run containers, the offset header appears only for >= 4 containers
+    let mut bm = RoaringBitmap::new();
+    for key in 0..10u32 {
+        bm.extend_consecutive(key * 65536..key * 65536 + 50);
+    }
+
+    let restored = round_trip(&bm);
+    assert_eq!(restored.len(), 500);
+    assert!(restored.iter().eq(bm.iter()));
+}
+
+#[test]
+fn serialize_bitmap_at_array_limit_uses_array_layout() {
+    // Cardinality 4096 is stored with the Array layout even though it is a
+    // Bitmap in memory
+    let mut bm = RoaringBitmap::new();
+    for i in 0..4096 {
+        bm.insert(i * 2);
+    }
+    assert_eq!(bm.container_type(0), Some("Bitmap"));
+
+    let bytes = serialize(&bm);
+    // cookie(4) + count(4) + header(4) + offset(4) + 4096 * 2
+    assert_eq!(bytes.len(), 16 + 4096 * 2);
+
+    let restored = RoaringBitmap::deserialize_from(&bytes[..]).unwrap();
+    assert_eq!(restored.len(), 4096);
+    assert!(restored.iter().eq(bm.iter()));
+}
+
+#[test]
+fn serialize_large_array_uses_bitmap_layout() {
+    // Fragmented Run optimized into an Array larger than 4096 values
+    let mut bm = RoaringBitmap::new();
+    bm.extend_consecutive(0..10_000);
+    for i in (0..10_000).step_by(2) {
+        bm.remove(i);
+    }
+    bm.optimize();
+    assert_eq!(bm.container_type(0), Some("Array"));
+    assert_eq!(bm.len(), 5000);
+
+    let bytes = serialize(&bm);
+    assert_eq!(bytes.len(), 16 + 8192);
+
+    let restored = RoaringBitmap::deserialize_from(&bytes[..]).unwrap();
+    assert_eq!(restored.container_type(0), Some("Bitmap"));
+    assert!(restored.iter().eq(bm.iter()));
+}
+
+#[test]
+fn serialize_into_writer_by_reference() {
+    let bm = bitmap_of(&[1, 2, 3]);
+    let mut buffer = std::io::Cursor::new(Vec::new());
+    bm.serialize_into(&mut buffer).unwrap();
+    bm.serialize_into(&mut buffer).unwrap();
+
+    // Two bitmaps back to back can be read sequentially
+    buffer.set_position(0);
+    let first = RoaringBitmap::deserialize_from(&mut buffer).unwrap();
+    let second = RoaringBitmap::deserialize_from(&mut buffer).unwrap();
+    expect_bitmap(&first, &[1, 2, 3]);
+    expect_bitmap(&second, &[1, 2, 3]);
+}
+
+// ============================================================================
+// Byte Compatibility (layouts produced by CRoaring / Java Roaring)
+// ============================================================================
+
+#[test]
+fn serialize_matches_portable_array_layout() {
+    let bm = bitmap_of(&[1, 2, 3]);
+    let expected = vec![
+        0x3A, 0x30, 0, 0, // cookie 12346
+        1, 0, 0, 0, // container count
+        0, 0, 2, 0, // key 0, cardinality - 1 = 2
+        16, 0, 0, 0, // offset of first container
+        1, 0, 2, 0, 3, 0, // values
+    ];
+    assert_eq!(serialize(&bm), expected);
+}
+
+#[test]
+fn serialize_matches_portable_run_layout() {
+    let mut bm = RoaringBitmap::new();
+    bm.extend_consecutive(0..10);
+    assert_eq!(bm.container_type(0), Some("Run"));
+
+    let expected = vec![
+        0x3B, 0x30, 0, 0,    // cookie 12347, (count - 1) = 0
+        0x01, // run flag bitset
+        0, 0, 9, 0, // key 0, cardinality - 1 = 9
+        1, 0, // one run (no offset header for < 4 containers)
+        0, 0, 9, 0, // start 0, length - 1 = 9
+    ];
+    assert_eq!(serialize(&bm), expected);
+}
+
+#[test]
+fn deserialize_portable_multi_container_bytes() {
+    // {7, 65536 + 1, 65536 + 2} split over two array containers
+    let bytes = vec![
+        0x3A, 0x30, 0, 0, // cookie
+        2, 0, 0, 0, // container count
+        0, 0, 0, 0, // key 0, cardinality 1
+        1, 0, 1, 0, // key 1, cardinality 2
+        24, 0, 0, 0, // offset container 0
+        26, 0, 0, 0, // offset container 1
+        7, 0, // container 0
+        1, 0, 2, 0, // container 1
+    ];
+    let bm = RoaringBitmap::deserialize_from(&bytes[..]).unwrap();
+    expect_bitmap(&bm, &[7, 65537, 65538]);
+}
+
+// ============================================================================
+// Invalid Input
+// ============================================================================
+
+#[test]
+fn deserialize_rejects_unknown_cookie() {
+    let bytes = [0xFF, 0xFF, 0, 0, 0, 0, 0, 0];
+    assert_eq!(deserialize_err(&bytes[..]), ErrorKind::InvalidData);
+}
+
+#[test]
+fn deserialize_rejects_truncated_input() {
+    let bm = bitmap_of(&[1, 2, 3, 100_000]);
+    let bytes = serialize(&bm);
+
+    for len in 0..bytes.len() {
+        assert_eq!(
+            deserialize_err(&bytes[..len]),
+            ErrorKind::UnexpectedEof,
+            "length {}",
+            len
+        );
+    }
+}
+
+#[test]
+fn deserialize_rejects_unsorted_keys() {
+    let bytes = vec![
+        0x3A, 0x30, 0, 0, 2, 0, 0, 0, //
+        1, 0, 0, 0, // key 1
+        0, 0, 0, 0, // key 0 (out of order)
+        24, 0, 0, 0, 26, 0, 0, 0, //
+        7, 0, 7, 0,
+    ];
+    assert_eq!(deserialize_err(&bytes[..]), ErrorKind::InvalidData);
+}
+
+#[test]
+fn deserialize_rejects_unsorted_array_values() {
+    let bytes = vec![
+        0x3A, 0x30, 0, 0, 1, 0, 0, 0, //
+        0, 0, 1, 0, // key 0, cardinality 2
+        16, 0, 0, 0, //
+        5, 0, 3, 0, // values out of order
+    ];
+    assert_eq!(deserialize_err(&bytes[..]), ErrorKind::InvalidData);
+}
+
+#[test]
+fn deserialize_rejects_overlapping_runs() {
+    let bytes = vec![
+        0x3B, 0x30, 0, 0, 0x01, //
+        0, 0, 9, 0, // key 0, cardinality 10
+        2, 0, // two runs
+        0, 0, 4, 0, // [0, 4]
+        3, 0, 4, 0, // [3, 7] overlaps
+    ];
+    assert_eq!(deserialize_err(&bytes[..]), ErrorKind::InvalidData);
+}
+
+#[test]
+fn deserialize_rejects_run_cardinality_mismatch() {
+    let bytes = vec![
+        0x3B, 0x30, 0, 0, 0x01, //
+        0, 0, 4, 0, // key 0, cardinality 5
+        1, 0, // one run
+        0, 0, 9, 0, // [0, 9] has 10 values
+    ];
+    assert_eq!(deserialize_err(&bytes[..]), ErrorKind::InvalidData);
+}
+
+#[test]
+fn deserialize_rejects_bitmap_cardinality_mismatch() {
+    let mut bm = RoaringBitmap::new();
+    for i in 0..5000 {
+        bm.insert(i * 2);
+    }
+    let mut bytes = serialize(&bm);
+
+    // Clear one bit in the bitmap data without updating the header
+    let data_start = 16;
+    bytes[data_start] &= !1;
+
+    assert_eq!(deserialize_err(&bytes[..]), ErrorKind::InvalidData);
+}
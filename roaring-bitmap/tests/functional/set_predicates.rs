@@ -0,0 +1,257 @@
+// Tests for subset/superset/disjointness predicates and cardinality-only set
+// operations, checked against a BTreeSet reference for every container pairing
+
+use crate::functional::{bitmap_of, Rng};
+use roaring_bitmap::RoaringBitmap;
+use std::collections::BTreeSet;
+
+/// Builds a single container of the requested type in key 0
+fn random_container(rng: &mut Rng, kind: &str) -> RoaringBitmap {
+    let mut bm = RoaringBitmap::new();
+    match kind {
+        "Array" => {
+            for _ in 0..1 + rng.below(2000) {
+                bm.insert(rng.below(65536));
+            }
+        }
+        "Bitmap" => {
+            while bm.len() < 5000 {
+                bm.insert(rng.below(65536));
+            }
+        }
+        _ => {
+            let mut start = rng.below(64);
+            while start < 65536 {
+                let end = (start + 1 + rng.below(2000)).min(65536);
+                bm.extend_consecutive(start..end);
+                start = end + rng.below(3000);
+            }
+        }
+    }
+    assert_eq!(bm.container_type(0), Some(kind));
+    bm
+}
+
+fn set_of(bm: &RoaringBitmap) -> BTreeSet<u32> {
+    bm.iter().collect()
+}
+
+/// Checks every predicate and cardinality in both directions against BTreeSet
+fn check_predicates(a: &RoaringBitmap, b: &RoaringBitmap) {
+    let (sa, sb) = (set_of(a), set_of(b));
+    let intersection = sa.intersection(&sb).count() as u64;
+    let union = sa.union(&sb).count() as u64;
+
+    assert_eq!(a.is_subset(b), sa.is_subset(&sb), "a ⊆ b");
+    assert_eq!(b.is_subset(a), sb.is_subset(&sa), "b ⊆ a");
+    assert_eq!(a.is_superset(b), sa.is_superset(&sb), "a ⊇ b");
+    assert_eq!(a.is_disjoint(b), sa.is_disjoint(&sb), "a ∩ b = ∅");
+    assert_eq!(b.is_disjoint(a), sa.is_disjoint(&sb), "b ∩ a = ∅");
+    assert_eq!(a.intersection_len(b), intersection, "|a ∩ b|");
+    assert_eq!(b.intersection_len(a), intersection, "|b ∩ a|");
+    assert_eq!(a.union_len(b), union, "|a ∪ b|");
+    assert_eq!(
+        a.difference_len(b),
+        sa.difference(&sb).count() as u64,
+        "|a - b|"
+    );
+    assert_eq!(
+        b.difference_len(a),
+        sb.difference(&sa).count() as u64,
+        "|b - a|"
+    );
+
+    let jaccard = if union == 0 {
+        1.0
+    } else {
+        intersection as f64 / union as f64
+    };
+    assert_eq!(a.jaccard_index(b), jaccard, "jaccard");
+    assert_eq!(b.jaccard_index(a), jaccard, "jaccard");
+}
+
+const KINDS: [&str; 3] = ["Array", "Bitmap", "Run"];
+
+// ============================================================================
+// Random pairs across container types
+// ============================================================================
+
+#[test]
+fn predicates_random_container_pairs() {
+    let mut rng = Rng(0x5EED_CAFE);
+    for kind_a in KINDS {
+        for kind_b in KINDS {
+            for _ in 0..10 {
+                let a = random_container(&mut rng, kind_a);
+                let b = random_container(&mut rng, kind_b);
+                check_predicates(&a, &b);
+            }
+        }
+    }
+}
+
+#[test]
+fn predicates_random_subsets() {
+    // The intersection is a subset of both sides, whatever container type
+    // each side and the intersection end up as
+    let mut rng = Rng(0xD15C_0123);
+    for kind_a in KINDS {
+        for kind_b in KINDS {
+            for _ in 0..5 {
+                let a = random_container(&mut rng, kind_a);
+                let b = random_container(&mut rng, kind_b);
+                let common = a.intersection(&b);
+                check_predicates(&common, &a);
+                check_predicates(&common, &b);
+                check_predicates(&a, &a.union(&b));
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Specialized container pairings
+// ============================================================================
+
+#[test]
+fn subset_run_within_bitmap() {
+    let mut rng = Rng(42);
+    let bitmap = random_container(&mut rng, "Bitmap");
+    let mut runs = RoaringBitmap::new();
+    runs.extend_consecutive(100..3000);
+    runs.extend_consecutive(40_000..41_000);
+
+    let superset = bitmap.union(&runs);
+    assert_eq!(superset.container_type(0), Some("Bitmap"));
+    assert!(runs.is_subset(&superset));
+    assert!(superset.is_superset(&runs));
+
+    let mut holed = superset.clone();
+    holed.remove(2999);
+    assert!(!runs.is_subset(&holed));
+    check_predicates(&runs, &holed);
+}
+
+#[test]
+fn subset_bitmap_within_run() {
+    let mut rng = Rng(7);
+    let bitmap = random_container(&mut rng, "Bitmap");
+    let mut full = RoaringBitmap::new();
+    full.extend_consecutive(0..65536);
+    assert!(bitmap.is_subset(&full));
+
+    let mut split = RoaringBitmap::new();
+    split.extend_consecutive(0..30_000);
+    split.extend_consecutive(30_001..65536);
+    assert_eq!(split.container_type(0), Some("Run"));
+    assert_eq!(bitmap.is_subset(&split), !bitmap.contains(30_000));
+    check_predicates(&bitmap, &split);
+}
+
+#[test]
+fn subset_run_covered_by_adjacent_runs() {
+    let mut inner = RoaringBitmap::new();
+    inner.extend_consecutive(10..50);
+
+    let mut outer = RoaringBitmap::new();
+    outer.extend_consecutive(0..20);
+    outer.extend_consecutive(20..30);
+    outer.extend_consecutive(30..60);
+    assert!(inner.is_subset(&outer));
+
+    outer.remove(29);
+    assert!(!inner.is_subset(&outer));
+    assert!(!inner.is_disjoint(&outer));
+    assert_eq!(inner.intersection_len(&outer), 39);
+}
+
+#[test]
+fn disjoint_interleaved_runs() {
+    let mut evens = RoaringBitmap::new();
+    let mut odds = RoaringBitmap::new();
+    for i in 0..100 {
+        evens.extend_consecutive(i * 20..i * 20 + 10);
+        odds.extend_consecutive(i * 20 + 10..i * 20 + 20);
+    }
+    assert!(evens.is_disjoint(&odds));
+    assert_eq!(evens.intersection_len(&odds), 0);
+    assert_eq!(evens.union_len(&odds), 2000);
+    assert_eq!(evens.jaccard_index(&odds), 0.0);
+
+    odds.insert(5);
+    assert!(!evens.is_disjoint(&odds));
+    check_predicates(&evens, &odds);
+}
+
+// ============================================================================
+// Multiple containers and edge cases
+// ============================================================================
+
+#[test]
+fn predicates_with_missing_keys() {
+    let a = bitmap_of(&[1, 65_536, 131_072]);
+    let b = bitmap_of(&[1, 2, 131_072, 196_608]);
+    check_predicates(&a, &b);
+    assert!(!a.is_subset(&b));
+
+    let c = bitmap_of(&[1, 131_072]);
+    assert!(c.is_subset(&a));
+    assert!(c.is_subset(&b));
+    assert!(a.is_superset(&c));
+
+    // Disjoint keys never compare containers
+    let d = bitmap_of(&[262_144, u32::MAX]);
+    assert!(a.is_disjoint(&d));
+    assert_eq!(a.union_len(&d), 5);
+}
+
+#[test]
+fn predicates_with_empty_bitmaps() {
+    let empty = RoaringBitmap::new();
+    let a = bitmap_of(&[3, 70_000]);
+
+    assert!(empty.is_subset(&empty));
+    assert!(empty.is_subset(&a));
+    assert!(!a.is_subset(&empty));
+    assert!(a.is_superset(&empty));
+    assert!(empty.is_disjoint(&empty));
+    assert!(a.is_disjoint(&empty));
+    assert_eq!(a.intersection_len(&empty), 0);
+    assert_eq!(a.union_len(&empty), 2);
+    assert_eq!(a.difference_len(&empty), 2);
+    assert_eq!(empty.difference_len(&a), 0);
+    assert_eq!(empty.jaccard_index(&empty), 1.0);
+    assert_eq!(a.jaccard_index(&empty), 0.0);
+}
+
+#[test]
+fn predicates_on_equal_sets_with_different_containers() {
+    let mut runs = RoaringBitmap::new();
+    runs.extend_consecutive(0..200);
+    let array: RoaringBitmap = (0..200).collect();
+    assert_eq!(array.container_type(0), Some("Array"));
+
+    assert!(runs.is_subset(&array));
+    assert!(array.is_subset(&runs));
+    assert_eq!(runs.jaccard_index(&array), 1.0);
+    assert_eq!(runs.difference_len(&array), 0);
+}
+
+#[test]
+fn cardinalities_match_materialized_operations() {
+    let mut rng = Rng(0xABCD);
+    let mut a = RoaringBitmap::new();
+    let mut b = RoaringBitmap::new();
+    for key in 0..6u32 {
+        let base = key << 16;
+        let kind_a = KINDS[rng.below(3) as usize];
+        let kind_b = KINDS[rng.below(3) as usize];
+        a.extend(random_container(&mut rng, kind_a).iter().map(|v| base + v));
+        b.extend(random_container(&mut rng, kind_b).iter().map(|v| base + v));
+    }
+
+    assert_eq!(a.intersection_len(&b), a.intersection(&b).len());
+    assert_eq!(a.union_len(&b), a.union(&b).len());
+    assert_eq!(a.difference_len(&b), a.difference(&b).len());
+    assert_eq!(b.difference_len(&a), b.difference(&a).len());
+}
@@ -0,0 +1,152 @@
+// Tests for threshold (T-overlap) queries, plain and weighted, checked
+// against a per-value counting reference. Random inputs draw from the lowest
+// 8,192 values of each container so that they overlap heavily
+
+use super::{bitmap_of, expect_bitmap, random_bitmap, Rng};
+use roaring_bitmap::RoaringBitmap;
+use std::collections::BTreeMap;
+
+/// Values whose summed input weights reach `threshold`
+fn reference(inputs: &[(RoaringBitmap, u32)], threshold: u64) -> Vec<u32> {
+    let mut scores: BTreeMap<u32, u64> = BTreeMap::new();
+    for (bitmap, weight) in inputs {
+        for value in bitmap {
+            *scores.entry(value).or_insert(0) += u64::from(*weight);
+        }
+    }
+    scores
+        .into_iter()
+        .filter(|&(_, score)| score >= threshold.max(1))
+        .map(|(value, _)| value)
+        .collect()
+}
+
+// ============================================================================
+// Unweighted thresholds
+// ============================================================================
+
+#[test]
+fn threshold_matches_counting_reference_for_every_k() {
+    let mut rng = Rng(0x0F0F_1234);
+    for round in 0..6 {
+        let bitmaps: Vec<RoaringBitmap> = (0..3 + round)
+            .map(|_| random_bitmap(&mut rng, 3, 8192))
+            .collect();
+        let weighted: Vec<(RoaringBitmap, u32)> =
+            bitmaps.iter().map(|bitmap| (bitmap.clone(), 1)).collect();
+
+        for k in 0..=bitmaps.len() + 1 {
+            let result = RoaringBitmap::threshold(&bitmaps, k);
+            expect_bitmap(&result, &reference(&weighted, k as u64));
+        }
+    }
+}
+
+#[test]
+fn threshold_extremes_are_union_and_intersection() {
+    let mut rng = Rng(0xFACE);
+    let bitmaps: Vec<RoaringBitmap> = (0..6).map(|_| random_bitmap(&mut rng, 3, 8192)).collect();
+
+    assert_eq!(
+        RoaringBitmap::threshold(&bitmaps, 1),
+        RoaringBitmap::union_many(&bitmaps)
+    );
+    assert_eq!(
+        RoaringBitmap::threshold(&bitmaps, 0),
+        RoaringBitmap::union_many(&bitmaps)
+    );
+    assert_eq!(
+        RoaringBitmap::threshold(&bitmaps, bitmaps.len()),
+        RoaringBitmap::intersection_many(&bitmaps)
+    );
+    assert!(RoaringBitmap::threshold(&bitmaps, bitmaps.len() + 1).is_empty());
+}
+
+#[test]
+fn segments_matched_at_least_three_times() {
+    // Twelve segments of users; user u is in segment s when u % (s + 2) == 0
+    let segments: Vec<RoaringBitmap> = (0..12u32)
+        .map(|s| (0..200_000).step_by(s as usize + 2).collect())
+        .collect();
+
+    let result = RoaringBitmap::threshold(&segments, 3);
+    let expected: Vec<u32> = (0..200_000u32)
+        .filter(|u| (0..12).filter(|s| u % (s + 2) == 0).count() >= 3)
+        .collect();
+    expect_bitmap(&result, &expected);
+}
+
+#[test]
+fn run_only_keys_are_merge_counted() {
+    let mut a = RoaringBitmap::new();
+    a.extend_consecutive(0..100);
+    let mut b = RoaringBitmap::new();
+    b.extend_consecutive(50..150);
+    let c = bitmap_of(&[0, 60, 120, 149, 150, 65_535]);
+    let mut d = RoaringBitmap::new();
+    d.extend_consecutive(65_000..65_536);
+
+    let result = RoaringBitmap::threshold([&a, &b, &c, &d], 2);
+    let mut expected: Vec<u32> = (50..100).collect();
+    expected.extend([0, 120, 149]);
+    expected.push(65_535);
+    expected.sort_unstable();
+    expect_bitmap(&result, &expected);
+    assert_eq!(result.container_type(0), Some("Run"));
+}
+
+// ============================================================================
+// Weighted thresholds
+// ============================================================================
+
+#[test]
+fn weighted_threshold_matches_reference() {
+    let mut rng = Rng(0xBEEF_CAFE);
+    for _ in 0..8 {
+        let inputs: Vec<(RoaringBitmap, u32)> = (0..5)
+            .map(|_| (random_bitmap(&mut rng, 3, 8192), rng.below(6)))
+            .collect();
+        let total: u64 = inputs.iter().map(|(_, weight)| u64::from(*weight)).sum();
+
+        for threshold in [0, 1, 2, 3, 5, 8, total / 2, total, total + 1] {
+            let result = RoaringBitmap::threshold_weighted(
+                inputs.iter().map(|(bitmap, weight)| (bitmap, *weight)),
+                threshold,
+            );
+            expect_bitmap(&result, &reference(&inputs, threshold));
+        }
+    }
+}
+
+#[test]
+fn large_weights_use_many_counter_planes() {
+    let dense: RoaringBitmap = (0..10_000).collect();
+    let sparse = bitmap_of(&[1, 5000, 9999, 12_000]);
+    let heavy = u32::MAX;
+
+    let result = RoaringBitmap::threshold_weighted(
+        [(&dense, heavy), (&sparse, heavy), (&dense, 7)],
+        2 * u64::from(heavy),
+    );
+    expect_bitmap(&result, &[1, 5000, 9999]);
+
+    let result = RoaringBitmap::threshold_weighted(
+        [(&dense, heavy), (&sparse, heavy), (&dense, 7)],
+        u64::from(heavy) + 7,
+    );
+    expect_bitmap(&result, &(0..10_000).collect::<Vec<_>>());
+}
+
+#[test]
+fn zero_weights_and_empty_inputs_never_contribute() {
+    let a = bitmap_of(&[1, 2, 3]);
+    let b = bitmap_of(&[3, 4]);
+
+    let result = RoaringBitmap::threshold_weighted([(&a, 0), (&b, 1)], 0);
+    expect_bitmap(&result, &[3, 4]);
+    assert!(RoaringBitmap::threshold_weighted([(&a, 0), (&b, 0)], 0).is_empty());
+
+    let none: [&RoaringBitmap; 0] = [];
+    assert!(RoaringBitmap::threshold(none, 1).is_empty());
+    assert!(RoaringBitmap::threshold([&RoaringBitmap::new()], 1).is_empty());
+}
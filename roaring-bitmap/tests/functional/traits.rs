@@ -0,0 +1,226 @@
+use crate::functional::bitmap_of;
+use roaring_bitmap::RoaringBitmap;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+fn hash_of(bm: &RoaringBitmap) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    bm.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Builds a bitmap one `insert` at a time, which never creates Run containers
+fn inserted<I: IntoIterator<Item = u32>>(values: I) -> RoaringBitmap {
+    let mut bm = RoaringBitmap::new();
+    for value in values {
+        bm.insert(value);
+    }
+    bm
+}
+
+/// Like `inserted`, but inserts even values first so dense containers cross
+/// the Array threshold without looking like runs and end up as Bitmaps
+fn inserted_as_bitmaps<I: IntoIterator<Item = u32>>(values: I) -> RoaringBitmap {
+    let (even, odd): (Vec<u32>, Vec<u32>) = values.into_iter().partition(|v| v % 2 == 0);
+    inserted(even.into_iter().chain(odd))
+}
+
+// ============================================================================
+// Equality
+// ============================================================================
+
+#[test]
+fn eq_empty_bitmaps() {
+    assert_eq!(RoaringBitmap::new(), RoaringBitmap::new());
+    assert_ne!(RoaringBitmap::new(), bitmap_of(&[0]));
+}
+
+#[test]
+fn eq_run_and_array_representations() {
+    let mut runs = RoaringBitmap::new();
+    runs.extend_consecutive(100..200);
+    let array = inserted(100..200);
+
+    assert_eq!(runs.container_type(0), Some("Run"));
+    assert_eq!(array.container_type(0), Some("Array"));
+    assert_eq!(runs, array);
+    assert_eq!(array, runs);
+}
+
+#[test]
+fn eq_run_and_bitmap_representations() {
+    let mut runs = RoaringBitmap::new();
+    runs.extend_consecutive(0..10_000);
+    runs.extend_consecutive(20_000..65_536);
+    let bitmap = inserted_as_bitmaps((0..10_000).chain(20_000..65_536));
+
+    assert_eq!(runs.container_type(0), Some("Run"));
+    assert_eq!(bitmap.container_type(0), Some("Bitmap"));
+    assert_eq!(runs, bitmap);
+}
+
+#[test]
+fn eq_survives_optimize() {
+    let mut bm = inserted((0..1000).chain(65_536..65_600));
+    bm.extend_sparse([200_000, 200_002, 200_004]);
+    let mut optimized = bm.clone();
+    optimized.optimize();
+
+    assert_eq!(bm.container_type(0), Some("Array"));
+    assert_eq!(optimized.container_type(0), Some("Run"));
+    assert_eq!(bm, optimized);
+    assert_eq!(hash_of(&bm), hash_of(&optimized));
+}
+
+#[test]
+fn ne_detects_differences() {
+    let mut runs = RoaringBitmap::new();
+    runs.extend_consecutive(0..1000);
+
+    // Same cardinality, different values
+    let mut shifted = inserted(1..1000);
+    shifted.insert(5000);
+    assert_ne!(runs, shifted);
+
+    // Same values in a different container key
+    let mut moved = RoaringBitmap::new();
+    moved.extend_consecutive(65_536..66_536);
+    assert_ne!(runs, moved);
+
+    // Subset
+    assert_ne!(runs, inserted(0..999));
+}
+
+// ============================================================================
+// Hashing
+// ============================================================================
+
+#[test]
+fn hash_is_representation_independent() {
+    let mut runs = RoaringBitmap::new();
+    runs.extend_consecutive(0..10_000);
+    runs.extend_consecutive(70_000..70_100);
+    runs.insert(u32::MAX);
+
+    let mut mixed = inserted_as_bitmaps((0..10_000).chain(70_000..70_100));
+    mixed.insert(u32::MAX);
+
+    assert_eq!(runs, mixed);
+    assert_eq!(hash_of(&runs), hash_of(&mixed));
+}
+
+#[test]
+fn hash_bitmap_runs_spanning_words() {
+    // Runs crossing 64-bit word boundaries and reaching the container end
+    let mut runs = RoaringBitmap::new();
+    runs.extend_consecutive(60..200);
+    runs.extend_consecutive(1000..1001);
+    runs.extend_consecutive(5000..65_536);
+    let bitmap = inserted_as_bitmaps((60..200).chain(1000..1001).chain(5000..65_536));
+
+    assert_eq!(bitmap.container_type(0), Some("Bitmap"));
+    assert_eq!(hash_of(&runs), hash_of(&bitmap));
+}
+
+#[test]
+fn hash_differs_for_different_sets() {
+    let a = bitmap_of(&[1, 2, 3]);
+    let b = bitmap_of(&[1, 2, 4]);
+    let c = bitmap_of(&[65_537, 65_538, 65_539]);
+    assert_ne!(hash_of(&a), hash_of(&b));
+    assert_ne!(hash_of(&a), hash_of(&c));
+}
+
+#[test]
+fn bitmaps_as_hash_keys() {
+    let mut runs = RoaringBitmap::new();
+    runs.extend_consecutive(0..5000);
+
+    let mut counts: HashMap<RoaringBitmap, u32> = HashMap::new();
+    *counts.entry(runs).or_insert(0) += 1;
+    *counts.entry(inserted_as_bitmaps(0..5000)).or_insert(0) += 1;
+    *counts.entry(bitmap_of(&[7])).or_insert(0) += 1;
+
+    assert_eq!(counts.len(), 2);
+    assert_eq!(counts[&inserted(0..5000)], 2);
+
+    let set: HashSet<RoaringBitmap> = counts.into_keys().collect();
+    assert!(set.contains(&bitmap_of(&[7])));
+}
+
+// ============================================================================
+// Ordering
+// ============================================================================
+
+#[test]
+fn ord_matches_btreeset() {
+    let sets: [&[u32]; 7] = [&[], &[0], &[0, 1], &[0, 2], &[1], &[1, 65_536], &[u32::MAX]];
+    for a in sets {
+        for b in sets {
+            let expected = a
+                .iter()
+                .collect::<BTreeSet<_>>()
+                .cmp(&b.iter().collect::<BTreeSet<_>>());
+            assert_eq!(
+                bitmap_of(a).partial_cmp(&bitmap_of(b)),
+                Some(expected),
+                "{a:?} vs {b:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn ord_is_consistent_with_eq() {
+    let mut runs = RoaringBitmap::new();
+    runs.extend_consecutive(0..5000);
+    let bitmap = inserted(0..5000);
+    assert_eq!(runs.partial_cmp(&bitmap), Some(std::cmp::Ordering::Equal));
+    assert!(runs <= bitmap);
+    assert!(runs >= bitmap);
+    assert!(bitmap_of(&[0, 1]) < bitmap_of(&[0, 2]));
+
+    // Owned bitmaps still resolve `min`/`max` to the inherent methods
+    assert_eq!(bitmap.min(), Some(0));
+    assert_eq!(bitmap.max(), Some(4999));
+
+    let mut sorted = [bitmap_of(&[3]), runs, bitmap_of(&[0, 9]), bitmap_of(&[])];
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let firsts: Vec<Option<u32>> = sorted.iter().map(|bm| bm.min()).collect();
+    assert_eq!(firsts, [None, Some(0), Some(0), Some(3)]);
+    assert_eq!(sorted[1].len(), 5000);
+}
+
+// ============================================================================
+// Debug
+// ============================================================================
+
+#[test]
+fn debug_small_bitmap() {
+    assert_eq!(
+        format!("{:?}", RoaringBitmap::new()),
+        "RoaringBitmap { len: 0, values: [], containers: 0 (Array: 0, Bitmap: 0, Run: 0) }"
+    );
+
+    let mut bm = bitmap_of(&[1, 2, 65_536]);
+    bm.extend_consecutive(200_000..200_010);
+    assert_eq!(
+        format!("{:?}", bm),
+        "RoaringBitmap { len: 13, values: [1, 2, 65536, 200000, 200001, 200002, 200003, \
+         200004, 200005, 200006, 200007, 200008, 200009], containers: 3 (Array: 2, Bitmap: 0, \
+         Run: 1) }"
+    );
+}
+
+#[test]
+fn debug_truncates_large_bitmap() {
+    let bm = inserted_as_bitmaps(0..10_000);
+    let output = format!("{:?}", bm);
+    assert_eq!(
+        output,
+        "RoaringBitmap { len: 10000, values: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, \
+         14, 15, ..], containers: 1 (Array: 0, Bitmap: 1, Run: 0) }"
+    );
+    assert!(format!("{:#?}", bm).contains("len: 10000"));
+}
@@ -0,0 +1,313 @@
+use super::Rng;
+use roaring_bitmap::{RoaringBitmap, RoaringTreemap};
+use std::collections::BTreeSet;
+
+const HIGH: u64 = 1 << 32;
+
+fn treemap_of(values: &[u64]) -> RoaringTreemap {
+    let mut tm = RoaringTreemap::new();
+    for &value in values {
+        assert!(tm.insert(value), "treemap_of expects unique values");
+    }
+    tm
+}
+
+/// Verifies a treemap contains exactly the expected values, from both ends
+fn expect_treemap(treemap: &RoaringTreemap, expected: &[u64]) {
+    let actual: Vec<u64> = treemap.iter().collect();
+    assert_eq!(actual, expected);
+    let reversed: Vec<u64> = treemap.iter().rev().collect();
+    assert_eq!(reversed, expected.iter().rev().copied().collect::<Vec<_>>());
+    assert_eq!(treemap.len() as usize, expected.len());
+    assert_eq!(treemap.iter().len(), expected.len());
+    assert_eq!(treemap.is_empty(), expected.is_empty());
+    assert_eq!(treemap.min(), expected.first().copied());
+    assert_eq!(treemap.max(), expected.last().copied());
+    for &value in expected {
+        assert!(treemap.contains(value));
+    }
+}
+
+/// Values clustered around a few partitions, including their edges
+fn random_treemap(rng: &mut Rng, count: usize) -> (RoaringTreemap, BTreeSet<u64>) {
+    let mut tm = RoaringTreemap::new();
+    let mut reference = BTreeSet::new();
+    for _ in 0..count {
+        let high = rng.next() % 4;
+        let low = match rng.next() % 3 {
+            0 => rng.next() % 100,
+            1 => u32::MAX as u64 - rng.next() % 100,
+            _ => rng.next() % HIGH,
+        };
+        let value = (high << 32) | low;
+        assert_eq!(tm.insert(value), reference.insert(value));
+    }
+    (tm, reference)
+}
+
+fn values_of(reference: &BTreeSet<u64>) -> Vec<u64> {
+    reference.iter().copied().collect()
+}
+
+// ============================================================================
+// Basic operations
+// ============================================================================
+
+#[test]
+fn insert_contains_remove_across_partitions() {
+    let mut tm = RoaringTreemap::new();
+    let values = [0, 1, u32::MAX as u64, HIGH, HIGH + 1, 7 * HIGH, u64::MAX];
+    for &value in &values {
+        assert!(tm.insert(value));
+        assert!(!tm.insert(value));
+    }
+    expect_treemap(&tm, &values);
+    assert!(!tm.contains(2 * HIGH));
+
+    assert!(tm.remove(HIGH));
+    assert!(!tm.remove(HIGH));
+    assert!(!tm.remove(3 * HIGH));
+    expect_treemap(&tm, &[0, 1, u32::MAX as u64, HIGH + 1, 7 * HIGH, u64::MAX]);
+
+    // Emptying a partition drops its bitmap
+    assert!(tm.remove(7 * HIGH));
+    assert_eq!(tm.memory_usage_detailed().bitmaps.len(), 3);
+
+    tm.clear();
+    expect_treemap(&tm, &[]);
+}
+
+#[test]
+fn collect_extend_and_into_iter() {
+    let values = [5 * HIGH, 3, HIGH + 9, 3, 2 * HIGH];
+    let mut tm: RoaringTreemap = values.into_iter().collect();
+    expect_treemap(&tm, &[3, HIGH + 9, 2 * HIGH, 5 * HIGH]);
+
+    tm.extend(&[1u64, u64::MAX]);
+    let owned: Vec<u64> = tm.clone().into_iter().collect();
+    assert_eq!(owned, vec![1, 3, HIGH + 9, 2 * HIGH, 5 * HIGH, u64::MAX]);
+    let back: Vec<u64> = tm.into_iter().rev().take(2).collect();
+    assert_eq!(back, vec![u64::MAX, 5 * HIGH]);
+}
+
+#[test]
+fn iteration_from_both_ends_meets_in_the_middle() {
+    let tm = treemap_of(&[1, 2, HIGH, HIGH + 1, 2 * HIGH]);
+    let mut iter = tm.iter();
+
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.next_back(), Some(2 * HIGH));
+    assert_eq!(iter.next_back(), Some(HIGH + 1));
+    assert_eq!(iter.len(), 2);
+    assert_eq!(iter.next(), Some(2));
+    assert_eq!(iter.next(), Some(HIGH));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+
+    let mut sum = 0;
+    for value in &tm {
+        sum += value >> 32;
+    }
+    assert_eq!(sum, 4);
+}
+
+#[test]
+fn equality_and_debug() {
+    let mut runs = RoaringTreemap::new();
+    runs.extend_consecutive(HIGH..HIGH + 100);
+    let values: RoaringTreemap = (HIGH..HIGH + 100).collect();
+    assert_eq!(runs, values);
+    assert_ne!(runs, treemap_of(&[HIGH]));
+
+    let tm = treemap_of(&[1, HIGH]);
+    assert_eq!(
+        format!("{:?}", tm),
+        "RoaringTreemap { len: 2, values: [1, 4294967296], bitmaps: 2 }"
+    );
+}
+
+// ============================================================================
+// Ranges
+// ============================================================================
+
+#[test]
+fn extend_consecutive_spans_partitions() {
+    let mut tm = RoaringTreemap::new();
+    tm.extend_consecutive(HIGH - 5..3 * HIGH + 5);
+
+    assert_eq!(tm.len(), 2 * HIGH + 10);
+    assert_eq!(tm.min(), Some(HIGH - 5));
+    assert_eq!(tm.max(), Some(3 * HIGH + 4));
+    assert!(tm.contains(2 * HIGH + 12345));
+    assert!(!tm.contains(HIGH - 6));
+    assert!(!tm.contains(3 * HIGH + 5));
+
+    // Full partitions are stored as runs: 65,536 bitmap containers would
+    // take 512 MiB per partition
+    let usage = tm.memory_usage_detailed();
+    assert_eq!(usage.bitmaps.len(), 4);
+    assert!(usage.total < 64 << 20);
+}
+
+#[test]
+fn extend_consecutive_handles_edges_and_empty_ranges() {
+    let mut tm = RoaringTreemap::new();
+    tm.extend_consecutive(u64::MAX - 2..=u64::MAX);
+    tm.extend_consecutive(10..10);
+    #[allow(clippy::reversed_empty_ranges)]
+    tm.extend_consecutive(20..=19);
+    expect_treemap(&tm, &[u64::MAX - 2, u64::MAX - 1, u64::MAX]);
+}
+
+#[test]
+fn remove_range_clips_edges_and_drops_inner_partitions() {
+    let mut tm = RoaringTreemap::new();
+    for high in 0..6u64 {
+        tm.extend_consecutive(high * HIGH..high * HIGH + 10);
+        tm.insert(high * HIGH + u32::MAX as u64);
+    }
+
+    tm.remove_range(HIGH + 5..=4 * HIGH + 2);
+
+    let mut expected = Vec::new();
+    expected.extend(0..10);
+    expected.push(u32::MAX as u64);
+    expected.extend(HIGH..HIGH + 5);
+    expected.extend(4 * HIGH + 3..4 * HIGH + 10);
+    expected.push(4 * HIGH + u32::MAX as u64);
+    expected.extend(5 * HIGH..5 * HIGH + 10);
+    expected.push(5 * HIGH + u32::MAX as u64);
+    expect_treemap(&tm, &expected);
+    assert_eq!(tm.memory_usage_detailed().bitmaps.len(), 4);
+
+    tm.remove_range(..);
+    expect_treemap(&tm, &[]);
+}
+
+#[test]
+fn remove_range_with_excluded_and_unbounded_bounds() {
+    let mut tm = treemap_of(&[0, 5, HIGH, u64::MAX]);
+    tm.remove_range(..0);
+    tm.remove_range(u64::MAX..u64::MAX);
+    expect_treemap(&tm, &[0, 5, HIGH, u64::MAX]);
+
+    tm.remove_range(5..);
+    expect_treemap(&tm, &[0]);
+}
+
+// ============================================================================
+// Set operations
+// ============================================================================
+
+#[test]
+fn set_operations_match_btreeset() {
+    let mut rng = Rng(0x7EE_3A9);
+    for _ in 0..20 {
+        let (a, ref_a) = random_treemap(&mut rng, 3000);
+        let (b, ref_b) = random_treemap(&mut rng, 3000);
+
+        let union: BTreeSet<u64> = ref_a.union(&ref_b).copied().collect();
+        let intersection: BTreeSet<u64> = ref_a.intersection(&ref_b).copied().collect();
+        let difference: BTreeSet<u64> = ref_a.difference(&ref_b).copied().collect();
+        let xor: BTreeSet<u64> = ref_a.symmetric_difference(&ref_b).copied().collect();
+
+        expect_treemap(&a.union(&b), &values_of(&union));
+        expect_treemap(&a.intersection(&b), &values_of(&intersection));
+        expect_treemap(&a.difference(&b), &values_of(&difference));
+        expect_treemap(&a.symmetric_difference(&b), &values_of(&xor));
+
+        let mut in_place = a.clone();
+        in_place.union_with(&b);
+        assert_eq!(in_place, a.union(&b));
+        let mut in_place = a.clone();
+        in_place.intersect_with(&b);
+        assert_eq!(in_place, a.intersection(&b));
+        let mut in_place = a.clone();
+        in_place.difference_with(&b);
+        assert_eq!(in_place, a.difference(&b));
+        let mut in_place = a.clone();
+        in_place.symmetric_difference_with(&b);
+        assert_eq!(in_place, a.symmetric_difference(&b));
+    }
+}
+
+#[test]
+fn set_operations_drop_emptied_partitions() {
+    let a = treemap_of(&[1, HIGH + 1, 2 * HIGH + 1]);
+    let b = treemap_of(&[HIGH + 1, 3 * HIGH]);
+
+    assert_eq!(a.intersection(&b).memory_usage_detailed().bitmaps.len(), 1);
+    assert_eq!(a.difference(&b).memory_usage_detailed().bitmaps.len(), 2);
+
+    let mut xor = a.clone();
+    xor.symmetric_difference_with(&b);
+    expect_treemap(&xor, &[1, 2 * HIGH + 1, 3 * HIGH]);
+    assert_eq!(xor.memory_usage_detailed().bitmaps.len(), 3);
+
+    let mut diff = a.clone();
+    diff -= &a;
+    expect_treemap(&diff, &[]);
+}
+
+#[test]
+fn operators_match_methods() {
+    let a = treemap_of(&[1, 2, HIGH, 5 * HIGH]);
+    let b = treemap_of(&[2, HIGH, 6 * HIGH]);
+
+    assert_eq!(&a | &b, a.union(&b));
+    assert_eq!(&a & &b, a.intersection(&b));
+    assert_eq!(&a ^ &b, a.symmetric_difference(&b));
+    assert_eq!(&a - &b, a.difference(&b));
+
+    assert_eq!(a.clone() | &b, a.union(&b));
+    assert_eq!(a.clone() & &b, a.intersection(&b));
+    assert_eq!(a.clone() ^ &b, a.symmetric_difference(&b));
+    assert_eq!(a.clone() - &b, a.difference(&b));
+
+    assert_eq!(&a | b.clone(), a.union(&b));
+    assert_eq!(&a & b.clone(), a.intersection(&b));
+    assert_eq!(&a ^ b.clone(), a.symmetric_difference(&b));
+    assert_eq!(&a - b.clone(), a.difference(&b));
+
+    let mut c = a.clone();
+    c |= &b;
+    assert_eq!(c, a.union(&b));
+    let mut c = a.clone();
+    c &= &b;
+    assert_eq!(c, a.intersection(&b));
+    let mut c = a.clone();
+    c ^= &b;
+    assert_eq!(c, a.symmetric_difference(&b));
+    let mut c = a.clone();
+    c -= &b;
+    assert_eq!(c, a.difference(&b));
+
+    // Chaining
+    let chained = (&a | &b) & &treemap_of(&[2, 6 * HIGH]);
+    expect_treemap(&chained, &[2, 6 * HIGH]);
+}
+
+// ============================================================================
+// Memory usage
+// ============================================================================
+
+#[test]
+fn memory_usage_detailed_sums_partitions() {
+    let mut tm = RoaringTreemap::new();
+    tm.extend_consecutive(0..100_000);
+    tm.insert(9 * HIGH);
+
+    let usage = tm.memory_usage_detailed();
+    assert_eq!(usage.total, usage.stack + usage.heap);
+    assert_eq!(usage.total, tm.memory_usage());
+
+    let keys: Vec<u32> = usage.bitmaps.iter().map(|(high, _)| *high).collect();
+    assert_eq!(keys, vec![0, 9]);
+
+    let mut first = RoaringBitmap::new();
+    first.extend_consecutive(0..100_000);
+    assert_eq!(usage.bitmaps[0].1.total, first.memory_usage());
+
+    let bitmaps: usize = usage.bitmaps.iter().map(|(_, bitmap)| bitmap.total).sum();
+    assert!(usage.heap >= bitmaps);
+}
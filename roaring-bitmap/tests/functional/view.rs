@@ -0,0 +1,340 @@
+use crate::functional::{bitmap_of, expect_bitmap, Rng};
+use roaring_bitmap::{RoaringBitmap, RoaringBitmapView};
+use std::io::ErrorKind;
+
+fn serialize(bm: &RoaringBitmap) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bm.serialize_into(&mut bytes).unwrap();
+    bytes
+}
+
+/// Creates views over bytes that are expected to be rejected, returning the error kind
+fn view_err(bytes: &[u8]) -> ErrorKind {
+    match RoaringBitmapView::from_bytes(bytes) {
+        Ok(_) => panic!("expected view creation to fail"),
+        Err(err) => err.kind(),
+    }
+}
+
+/// Bitmap with Array, Bitmap and Run containers
+fn mixed_bitmap() -> RoaringBitmap {
+    let mut bm = RoaringBitmap::new();
+    bm.extend_sparse([5, 17, 1000, 65535]); // Array in container 0
+    for i in 0..10_000 {
+        bm.insert(65536 + i * 3); // Bitmap in container 1
+    }
+    bm.extend_consecutive(3 * 65536..4 * 65536); // Full Run in container 3
+    bm.extend_consecutive(10 * 65536 + 100..10 * 65536 + 200); // Run in container 10
+    bm.insert(u32::MAX);
+    bm
+}
+
+/// Asserts that a view answers queries exactly like the bitmap it was made from
+fn assert_view_matches(view: &RoaringBitmapView, bm: &RoaringBitmap) {
+    assert_eq!(view.len(), bm.len());
+    assert_eq!(view.is_empty(), bm.is_empty());
+    assert!(view.iter().eq(bm.iter()));
+    for value in bm.iter().step_by(97) {
+        assert!(view.contains(value));
+    }
+    for probe in [
+        0,
+        4,
+        6,
+        999,
+        1001,
+        65536,
+        65537,
+        65538,
+        2 * 65536,
+        11 * 65536,
+    ] {
+        assert_eq!(view.contains(probe), bm.contains(probe), "probe {}", probe);
+    }
+}
+
+// ============================================================================
+// Queries
+// ============================================================================
+
+#[test]
+fn view_of_empty_bitmap() {
+    let bytes = serialize(&RoaringBitmap::new());
+    let view = RoaringBitmapView::from_bytes(&bytes).unwrap();
+    assert!(view.is_empty());
+    assert_eq!(view.len(), 0);
+    assert_eq!(view.iter().next(), None);
+    assert!(!view.contains(0));
+}
+
+#[test]
+fn view_of_array_container() {
+    let bm = bitmap_of(&[1, 2, 3, 100, 65535]);
+    let bytes = serialize(&bm);
+    let view = RoaringBitmapView::from_bytes(&bytes).unwrap();
+    assert_view_matches(&view, &bm);
+}
+
+#[test]
+fn view_of_bitmap_container() {
+    let mut bm = RoaringBitmap::new();
+    for i in (0..65536).step_by(5) {
+        bm.insert(i);
+    }
+    assert_eq!(bm.container_type(0), Some("Bitmap"));
+
+    let bytes = serialize(&bm);
+    let view = RoaringBitmapView::from_bytes(&bytes).unwrap();
+    assert_view_matches(&view, &bm);
+    assert!(view.contains(65535));
+    assert!(!view.contains(65534));
+}
+
+#[test]
+fn view_of_run_containers() {
+    let mut bm = RoaringBitmap::new();
+    bm.extend_consecutive(10..20);
+    bm.extend_consecutive(30..40);
+    bm.extend_consecutive(65530..70_000);
+
+    let bytes = serialize(&bm);
+    let view = RoaringBitmapView::from_bytes(&bytes).unwrap();
+    assert_view_matches(&view, &bm);
+    assert!(!view.contains(9));
+    assert!(view.contains(10));
+    assert!(view.contains(19));
+    assert!(!view.contains(20));
+    assert!(!view.contains(29));
+    assert!(view.contains(39));
+    assert!(!view.contains(40));
+}
+
+#[test]
+fn view_of_mixed_containers() {
+    let bm = mixed_bitmap();
+    let bytes = serialize(&bm);
+    let view = RoaringBitmapView::from_bytes(&bytes).unwrap();
+    assert_view_matches(&view, &bm);
+}
+
+#[test]
+fn view_on_unaligned_bytes() {
+    // Views decode values byte-wise, so any alignment works
+    let bm = mixed_bitmap();
+    let mut buffer = vec![0xAA];
+    buffer.extend(serialize(&bm));
+
+    let view = RoaringBitmapView::from_bytes(&buffer[1..]).unwrap();
+    assert_view_matches(&view, &bm);
+}
+
+#[test]
+fn view_of_consecutive_serialized_bitmaps() {
+    let a = bitmap_of(&[1, 2, 3]);
+    let b = mixed_bitmap();
+    let c = bitmap_of(&[42]);
+
+    let mut bytes = serialize(&a);
+    bytes.extend(serialize(&b));
+    bytes.extend(serialize(&c));
+
+    let view_a = RoaringBitmapView::from_bytes(&bytes).unwrap();
+    assert_eq!(view_a.serialized_size(), a.serialized_size());
+    let rest = &bytes[view_a.serialized_size()..];
+    let view_b = RoaringBitmapView::from_bytes(rest).unwrap();
+    let rest = &rest[view_b.serialized_size()..];
+    let view_c = RoaringBitmapView::from_bytes(rest).unwrap();
+    assert_eq!(view_c.serialized_size(), rest.len());
+
+    assert_view_matches(&view_a, &a);
+    assert_view_matches(&view_b, &b);
+    assert_view_matches(&view_c, &c);
+}
+
+#[test]
+fn view_to_bitmap() {
+    let bm = mixed_bitmap();
+    let bytes = serialize(&bm);
+    let view = RoaringBitmapView::from_bytes(&bytes).unwrap();
+
+    let owned = view.to_bitmap();
+    assert_eq!(owned.container_stats(), bm.container_stats());
+    assert!(owned.iter().eq(bm.iter()));
+}
+
+// ============================================================================
+// Set Operations
+// ============================================================================
+
+#[test]
+fn view_set_operations_match_owned() {
+    let a = mixed_bitmap();
+    let mut b = RoaringBitmap::new();
+    b.extend_sparse([5, 6, 1000, 65536, 65539, 65540]);
+    for i in 0..8_000 {
+        b.insert(65536 + i * 7); // Bitmap overlapping a's bitmap
+    }
+    b.extend_consecutive(3 * 65536 + 500..3 * 65536 + 700); // Run inside a's full run
+    for i in (0..1000).step_by(3) {
+        b.insert(10 * 65536 + i); // Array overlapping a's run
+    }
+    b.extend_consecutive(20 * 65536..20 * 65536 + 10); // Key only in b
+
+    let (bytes_a, bytes_b) = (serialize(&a), serialize(&b));
+    let view_a = RoaringBitmapView::from_bytes(&bytes_a).unwrap();
+    let view_b = RoaringBitmapView::from_bytes(&bytes_b).unwrap();
+
+    let cases = [
+        (view_a.union(&view_b), a.union(&b)),
+        (view_a.intersection(&view_b), a.intersection(&b)),
+        (view_a.difference(&view_b), a.difference(&b)),
+        (view_b.difference(&view_a), b.difference(&a)),
+        (
+            view_a.symmetric_difference(&view_b),
+            a.symmetric_difference(&b),
+        ),
+    ];
+    for (from_view, from_owned) in cases {
+        assert_eq!(from_view.len(), from_owned.len());
+        assert!(from_view.iter().eq(from_owned.iter()));
+    }
+}
+
+#[test]
+fn view_set_operations_match_owned_for_every_layout_pairing() {
+    // Single-container bitmaps of each layout, drawn from the same window so
+    // that every pairing overlaps
+    let mut rng = Rng(0x71E3_0001);
+    let mut layouts: Vec<RoaringBitmap> = Vec::new();
+    for _ in 0..3 {
+        let array: RoaringBitmap = (0..1 + rng.below(3000))
+            .map(|_| rng.below(20_000))
+            .collect();
+        let mut bitmap = RoaringBitmap::new();
+        while bitmap.len() < 5000 + rng.below(5000) as u64 {
+            bitmap.insert(rng.below(20_000));
+        }
+        let mut run = RoaringBitmap::new();
+        for _ in 0..1 + rng.below(30) {
+            let start = rng.below(19_000);
+            run.extend_consecutive(start..start + 1 + rng.below(1000));
+        }
+        assert_eq!(array.container_type(0), Some("Array"));
+        assert_eq!(bitmap.container_type(0), Some("Bitmap"));
+        assert_eq!(run.container_type(0), Some("Run"));
+        layouts.extend([array, bitmap, run]);
+    }
+    let bytes: Vec<Vec<u8>> = layouts.iter().map(serialize).collect();
+    let views: Vec<RoaringBitmapView> = bytes
+        .iter()
+        .map(|bytes| RoaringBitmapView::from_bytes(bytes).unwrap())
+        .collect();
+
+    for (a, view_a) in layouts.iter().zip(&views) {
+        for (b, view_b) in layouts.iter().zip(&views) {
+            let pairing = (a.container_type(0), b.container_type(0));
+            let cases = [
+                (view_a.union(view_b), a | b),
+                (view_a.intersection(view_b), a & b),
+                (view_a.difference(view_b), a - b),
+                (view_a.symmetric_difference(view_b), a ^ b),
+            ];
+            for (from_view, from_owned) in cases {
+                assert_eq!(from_view, from_owned, "{pairing:?}");
+                // Results keep the Array/Bitmap threshold invariant
+                for (_, kind, len) in from_view.container_stats() {
+                    match kind {
+                        "Array" => assert!(len < 4096, "{pairing:?}"),
+                        "Bitmap" => assert!(len >= 4096, "{pairing:?}"),
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn view_intersection_skips_disjoint_keys() {
+    let a = bitmap_of(&[1, 2, 3]);
+    let b = bitmap_of(&[65536, 65537]);
+    let (bytes_a, bytes_b) = (serialize(&a), serialize(&b));
+    let view_a = RoaringBitmapView::from_bytes(&bytes_a).unwrap();
+    let view_b = RoaringBitmapView::from_bytes(&bytes_b).unwrap();
+
+    assert!(view_a.intersection(&view_b).is_empty());
+    expect_bitmap(&view_a.union(&view_b), &[1, 2, 3, 65536, 65537]);
+}
+
+#[test]
+fn view_bitmap_intersection_converts_small_result_to_array() {
+    let mut a = RoaringBitmap::new();
+    let mut b = RoaringBitmap::new();
+    for i in 0..10_000 {
+        a.insert(i * 2);
+        b.insert(i * 3);
+    }
+    let (bytes_a, bytes_b) = (serialize(&a), serialize(&b));
+    let view_a = RoaringBitmapView::from_bytes(&bytes_a).unwrap();
+    let view_b = RoaringBitmapView::from_bytes(&bytes_b).unwrap();
+
+    let result = view_a.intersection(&view_b);
+    assert_eq!(result.container_type(0), Some("Array"));
+    assert!(result.iter().eq(a.intersection(&b).iter()));
+}
+
+// ============================================================================
+// Invalid Input
+// ============================================================================
+
+#[test]
+fn view_rejects_truncated_input() {
+    let bytes = serialize(&mixed_bitmap());
+    for len in [0, 3, 7, 20, bytes.len() / 2, bytes.len() - 1] {
+        assert_eq!(
+            view_err(&bytes[..len]),
+            ErrorKind::UnexpectedEof,
+            "length {}",
+            len
+        );
+    }
+}
+
+#[test]
+fn view_rejects_unknown_cookie() {
+    assert_eq!(view_err(&[1, 2, 3, 4, 0, 0, 0, 0]), ErrorKind::InvalidData);
+}
+
+#[test]
+fn view_rejects_unsorted_array_values() {
+    let bytes = [
+        0x3A, 0x30, 0, 0, 1, 0, 0, 0, //
+        0, 0, 1, 0, // key 0, cardinality 2
+        16, 0, 0, 0, //
+        5, 0, 5, 0, // duplicate values
+    ];
+    assert_eq!(view_err(&bytes), ErrorKind::InvalidData);
+}
+
+#[test]
+fn view_rejects_adjacent_runs() {
+    let bytes = [
+        0x3B, 0x30, 0, 0, 0x01, //
+        0, 0, 9, 0, // key 0, cardinality 10
+        2, 0, // two runs
+        0, 0, 4, 0, // [0, 4]
+        5, 0, 4, 0, // [5, 9] is adjacent
+    ];
+    assert_eq!(view_err(&bytes), ErrorKind::InvalidData);
+}
+
+#[test]
+fn view_rejects_run_past_container_end() {
+    let bytes = [
+        0x3B, 0x30, 0, 0, 0x01, //
+        0, 0, 9, 0, // key 0, cardinality 10
+        1, 0, // one run
+        0xFA, 0xFF, 9, 0, // [65530, 65539] overflows
+    ];
+    assert_eq!(view_err(&bytes), ErrorKind::InvalidData);
+}
@@ -0,0 +1,982 @@
+// 64-bit Roaring Treemap
+
+//! A `RoaringTreemap` stores 64-bit integers by splitting them into:
+//! - High 32 bits: Partition key (determines which bitmap)
+//! - Low 32 bits: Value stored in that partition's [`RoaringBitmap`]
+//!
+//! Partitions are kept in a `BTreeMap`, so they stay ordered by key and values
+//! iterate in ascending order. Sets whose values share their high 32 bits (the
+//! common case for ids that grew just past `u32::MAX`) are a single bitmap.
+
+use crate::{IntoIter, Iter, MemoryUsage, RoaringBitmap};
+use std::collections::{btree_map, BTreeMap};
+use std::fmt;
+use std::iter::FusedIterator;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Sub, SubAssign};
+
+/// Set of 64-bit integers built from 32-bit Roaring Bitmaps
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct RoaringTreemap {
+    /// Ordered map of (high 32 bits, bitmap of low 32 bits)
+    ///
+    /// # Invariants
+    /// - Each bitmap must be non-empty
+    ///
+    /// This invariant must be maintained by insert/remove operations
+    bitmaps: BTreeMap<u32, RoaringBitmap>,
+}
+
+/// Detailed memory usage information for a RoaringTreemap
+#[derive(Debug, Clone)]
+pub struct TreemapMemoryUsage {
+    /// Total memory usage in bytes (stack + heap)
+    pub total: usize,
+    /// Stack-allocated memory in bytes (the struct itself)
+    pub stack: usize,
+    /// Total heap-allocated memory in bytes
+    pub heap: usize,
+    /// Per-bitmap breakdown, keyed by the high 32 bits
+    pub bitmaps: Vec<(u32, MemoryUsage)>,
+}
+
+impl RoaringTreemap {
+    // Helper methods
+
+    /// Splits a u64 value into high 32 bits (partition key) and low 32 bits
+    #[inline]
+    fn split(value: u64) -> (u32, u32) {
+        ((value >> 32) as u32, value as u32)
+    }
+
+    /// Combines a partition key (high 32 bits) and low 32 bits into u64
+    #[inline]
+    fn combine(high: u32, low: u32) -> u64 {
+        ((high as u64) << 32) | (low as u64)
+    }
+
+    /// Resolves range bounds into an inclusive `(start, end)` pair, or `None`
+    /// if the range is empty
+    fn resolve_range<R: std::ops::RangeBounds<u64>>(range: &R) -> Option<(u64, u64)> {
+        use std::ops::Bound::*;
+
+        let start = match range.start_bound() {
+            Included(&s) => s,
+            Excluded(&s) => s.checked_add(1)?,
+            Unbounded => 0,
+        };
+
+        let end = match range.end_bound() {
+            Included(&e) => e,
+            Excluded(&e) => e.checked_sub(1)?,
+            Unbounded => u64::MAX,
+        };
+
+        (start <= end).then_some((start, end))
+    }
+
+    /// Yields each partition touched by the inclusive range `start..=end`,
+    /// together with the inclusive low range it covers in that partition
+    fn partitions(start: u64, end: u64) -> impl Iterator<Item = (u32, u32, u32)> {
+        let (start_high, start_low) = Self::split(start);
+        let (end_high, end_low) = Self::split(end);
+
+        (start_high..=end_high).map(move |high| {
+            let low_start = if high == start_high { start_low } else { 0 };
+            let low_end = if high == end_high { end_low } else { u32::MAX };
+            (high, low_start, low_end)
+        })
+    }
+
+    // Construction
+
+    /// Creates an empty roaring treemap
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringTreemap;
+    ///
+    /// let tm = RoaringTreemap::new();
+    /// assert!(tm.is_empty());
+    /// assert_eq!(tm.len(), 0);
+    /// ```
+    pub fn new() -> Self {
+        RoaringTreemap {
+            bitmaps: BTreeMap::new(),
+        }
+    }
+
+    // Insertion
+
+    /// Adds a single element to the treemap, returns `true` if the element was newly inserted
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringTreemap;
+    ///
+    /// let mut tm = RoaringTreemap::new();
+    /// assert!(tm.insert(1 << 40));  // Returns true, newly inserted
+    /// assert!(!tm.insert(1 << 40)); // Returns false, already present
+    /// assert_eq!(tm.len(), 1);
+    /// ```
+    pub fn insert(&mut self, value: u64) -> bool {
+        let (high, low) = Self::split(value);
+        self.bitmaps.entry(high).or_default().insert(low)
+    }
+
+    /// Efficiently inserts a range of consecutive values
+    ///
+    /// Each partition the range touches receives a single
+    /// [`RoaringBitmap::extend_consecutive`] call, so the values are stored as
+    /// Run containers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringTreemap;
+    ///
+    /// let mut tm = RoaringTreemap::new();
+    ///
+    /// // Crosses from the first partition into the second
+    /// tm.extend_consecutive(u32::MAX as u64 - 9..u32::MAX as u64 + 11);
+    /// assert_eq!(tm.len(), 20);
+    /// assert!(tm.contains(u32::MAX as u64));
+    /// assert!(tm.contains(1 << 32));
+    /// ```
+    pub fn extend_consecutive<R: std::ops::RangeBounds<u64>>(&mut self, range: R) {
+        let Some((start, end)) = Self::resolve_range(&range) else {
+            return;
+        };
+
+        for (high, low_start, low_end) in Self::partitions(start, end) {
+            self.bitmaps
+                .entry(high)
+                .or_default()
+                .extend_consecutive(low_start..=low_end);
+        }
+    }
+
+    // Query Operations
+
+    /// Checks if an element exists in the treemap
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringTreemap;
+    ///
+    /// let mut tm = RoaringTreemap::new();
+    /// tm.insert(5_000_000_000);
+    /// assert!(tm.contains(5_000_000_000));
+    /// assert!(!tm.contains(5));
+    /// ```
+    pub fn contains(&self, value: u64) -> bool {
+        let (high, low) = Self::split(value);
+        self.bitmaps
+            .get(&high)
+            .is_some_and(|bitmap| bitmap.contains(low))
+    }
+
+    /// Returns the number of elements in the treemap (cardinality)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringTreemap;
+    ///
+    /// let mut tm = RoaringTreemap::new();
+    /// tm.insert(1);
+    /// tm.insert(1 << 32);
+    /// tm.insert(u64::MAX);
+    /// assert_eq!(tm.len(), 3);
+    /// ```
+    pub fn len(&self) -> u64 {
+        self.bitmaps.values().map(RoaringBitmap::len).sum()
+    }
+
+    /// Returns `true` if the treemap contains no elements
+    pub fn is_empty(&self) -> bool {
+        self.bitmaps.is_empty()
+    }
+
+    /// Returns the smallest element
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringTreemap;
+    ///
+    /// let tm: RoaringTreemap = [1 << 40, 7, 1 << 33].into_iter().collect();
+    /// assert_eq!(tm.min(), Some(7));
+    /// assert_eq!(tm.max(), Some(1 << 40));
+    /// ```
+    pub fn min(&self) -> Option<u64> {
+        let (&high, bitmap) = self.bitmaps.first_key_value()?;
+        Some(Self::combine(high, bitmap.min()?))
+    }
+
+    /// Returns the largest element
+    pub fn max(&self) -> Option<u64> {
+        let (&high, bitmap) = self.bitmaps.last_key_value()?;
+        Some(Self::combine(high, bitmap.max()?))
+    }
+
+    // Deletion
+
+    /// Removes a single element from the treemap, returns `true` if the element was present
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringTreemap;
+    ///
+    /// let mut tm = RoaringTreemap::new();
+    /// tm.insert(1 << 50);
+    /// assert!(tm.remove(1 << 50));
+    /// assert!(!tm.remove(1 << 50));
+    /// assert!(tm.is_empty());
+    /// ```
+    pub fn remove(&mut self, value: u64) -> bool {
+        let (high, low) = Self::split(value);
+        let Some(bitmap) = self.bitmaps.get_mut(&high) else {
+            return false;
+        };
+
+        let removed = bitmap.remove(low);
+        if bitmap.is_empty() {
+            self.bitmaps.remove(&high);
+        }
+        removed
+    }
+
+    /// Efficiently removes a range of consecutive values
+    ///
+    /// Partitions that lie entirely inside the range are dropped whole; only
+    /// the first and last partitions are clipped with
+    /// [`RoaringBitmap::remove_range`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringTreemap;
+    ///
+    /// let mut tm: RoaringTreemap = (0..10).map(|i| i << 32).collect();
+    /// tm.remove_range(2 << 32..=7 << 32);
+    ///
+    /// let left: Vec<u64> = tm.iter().map(|v| v >> 32).collect();
+    /// assert_eq!(left, vec![0, 1, 8, 9]);
+    /// ```
+    pub fn remove_range<R: std::ops::RangeBounds<u64>>(&mut self, range: R) {
+        let Some((start, end)) = Self::resolve_range(&range) else {
+            return;
+        };
+        let (start_high, _) = Self::split(start);
+        let (end_high, _) = Self::split(end);
+
+        // Only visit partitions that exist, however wide the range
+        let mut emptied = Vec::new();
+        for (&high, bitmap) in self.bitmaps.range_mut(start_high..=end_high) {
+            let low_start = if high == start_high { start as u32 } else { 0 };
+            let low_end = if high == end_high {
+                end as u32
+            } else {
+                u32::MAX
+            };
+
+            if low_start == 0 && low_end == u32::MAX {
+                bitmap.clear();
+            } else {
+                bitmap.remove_range(low_start..=low_end);
+            }
+            if bitmap.is_empty() {
+                emptied.push(high);
+            }
+        }
+
+        for high in emptied {
+            self.bitmaps.remove(&high);
+        }
+    }
+
+    /// Removes all elements from the treemap
+    pub fn clear(&mut self) {
+        self.bitmaps.clear();
+    }
+
+    // Set Operations
+
+    /// Returns the union (OR) of two treemaps
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringTreemap;
+    ///
+    /// let a: RoaringTreemap = [1, 1 << 40].into_iter().collect();
+    /// let b: RoaringTreemap = [1 << 40, 2 << 40].into_iter().collect();
+    /// let result = a.union(&b);
+    /// assert_eq!(result.len(), 3);
+    /// ```
+    pub fn union(&self, other: &RoaringTreemap) -> RoaringTreemap {
+        let mut result = self.clone();
+        result.union_with(other);
+        result
+    }
+
+    /// Returns the intersection (AND) of two treemaps
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringTreemap;
+    ///
+    /// let a: RoaringTreemap = [1, 1 << 40].into_iter().collect();
+    /// let b: RoaringTreemap = [1 << 40, 2 << 40].into_iter().collect();
+    /// let result = a.intersection(&b);
+    /// assert_eq!(result.iter().collect::<Vec<_>>(), vec![1 << 40]);
+    /// ```
+    pub fn intersection(&self, other: &RoaringTreemap) -> RoaringTreemap {
+        let bitmaps = self
+            .bitmaps
+            .iter()
+            .filter_map(|(high, a)| {
+                let b = other.bitmaps.get(high)?;
+                let bitmap = a.intersection(b);
+                (!bitmap.is_empty()).then_some((*high, bitmap))
+            })
+            .collect();
+
+        RoaringTreemap { bitmaps }
+    }
+
+    /// Returns the difference (AND NOT) of two treemaps
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringTreemap;
+    ///
+    /// let a: RoaringTreemap = [1, 1 << 40].into_iter().collect();
+    /// let b: RoaringTreemap = [1 << 40, 2 << 40].into_iter().collect();
+    /// let result = a.difference(&b);
+    /// assert_eq!(result.iter().collect::<Vec<_>>(), vec![1]);
+    /// ```
+    pub fn difference(&self, other: &RoaringTreemap) -> RoaringTreemap {
+        let bitmaps = self
+            .bitmaps
+            .iter()
+            .filter_map(|(high, a)| {
+                let bitmap = match other.bitmaps.get(high) {
+                    Some(b) => a.difference(b),
+                    None => a.clone(),
+                };
+                (!bitmap.is_empty()).then_some((*high, bitmap))
+            })
+            .collect();
+
+        RoaringTreemap { bitmaps }
+    }
+
+    /// Returns the symmetric difference (XOR) of two treemaps
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringTreemap;
+    ///
+    /// let a: RoaringTreemap = [1, 1 << 40].into_iter().collect();
+    /// let b: RoaringTreemap = [1 << 40, 2 << 40].into_iter().collect();
+    /// let result = a.symmetric_difference(&b);
+    /// assert_eq!(result.iter().collect::<Vec<_>>(), vec![1, 2 << 40]);
+    /// ```
+    pub fn symmetric_difference(&self, other: &RoaringTreemap) -> RoaringTreemap {
+        let mut result = self.clone();
+        result.symmetric_difference_with(other);
+        result
+    }
+
+    // In-place Set Operations
+
+    /// In-place union: adds every element of `other` to `self`
+    ///
+    /// Partitions only present in `other` are cloned; shared partitions use
+    /// [`RoaringBitmap::union_with`].
+    pub fn union_with(&mut self, other: &RoaringTreemap) {
+        for (&high, b) in &other.bitmaps {
+            match self.bitmaps.entry(high) {
+                btree_map::Entry::Occupied(mut entry) => entry.get_mut().union_with(b),
+                btree_map::Entry::Vacant(entry) => {
+                    entry.insert(b.clone());
+                }
+            }
+        }
+    }
+
+    /// In-place intersection: keeps only the elements also in `other`
+    pub fn intersect_with(&mut self, other: &RoaringTreemap) {
+        self.bitmaps
+            .retain(|high, a| match other.bitmaps.get(high) {
+                Some(b) => {
+                    a.intersect_with(b);
+                    !a.is_empty()
+                }
+                None => false,
+            });
+    }
+
+    /// In-place difference: removes every element of `other` from `self`
+    pub fn difference_with(&mut self, other: &RoaringTreemap) {
+        for (high, b) in &other.bitmaps {
+            if let Some(a) = self.bitmaps.get_mut(high) {
+                a.difference_with(b);
+                if a.is_empty() {
+                    self.bitmaps.remove(high);
+                }
+            }
+        }
+    }
+
+    /// In-place symmetric difference: keeps the elements in exactly one of
+    /// `self` and `other`
+    pub fn symmetric_difference_with(&mut self, other: &RoaringTreemap) {
+        for (&high, b) in &other.bitmaps {
+            match self.bitmaps.entry(high) {
+                btree_map::Entry::Occupied(mut entry) => {
+                    entry.get_mut().symmetric_difference_with(b);
+                    if entry.get().is_empty() {
+                        entry.remove();
+                    }
+                }
+                btree_map::Entry::Vacant(entry) => {
+                    entry.insert(b.clone());
+                }
+            }
+        }
+    }
+
+    // Iteration
+
+    /// Returns an iterator over elements in sorted order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringTreemap;
+    ///
+    /// let tm: RoaringTreemap = [1 << 40, 3, 1 << 32].into_iter().collect();
+    /// let values: Vec<u64> = tm.iter().collect();
+    /// assert_eq!(values, vec![3, 1 << 32, 1 << 40]); // Always sorted
+    /// ```
+    pub fn iter(&self) -> TreemapIter<'_> {
+        TreemapIter {
+            inner: Partitioned::new(
+                self.bitmaps
+                    .iter()
+                    .map(|(&high, bitmap)| (high, bitmap.iter())),
+                self.len(),
+            ),
+        }
+    }
+
+    // Memory Usage
+
+    /// Returns total memory usage in bytes
+    ///
+    /// Counts the struct itself, each partition's key and bitmap, and the
+    /// bitmaps' heap allocations. The `BTreeMap`'s internal node overhead is
+    /// not included.
+    pub fn memory_usage(&self) -> usize {
+        self.memory_usage_detailed().total
+    }
+
+    /// Returns detailed memory usage breakdown.
+    ///
+    /// Returns a `TreemapMemoryUsage` struct containing:
+    /// - `total`: Total memory in bytes (stack + heap)
+    /// - `stack`: Stack-allocated memory (the struct itself)
+    /// - `heap`: Total heap-allocated memory
+    /// - `bitmaps`: Each partition's key and [`RoaringBitmap::memory_usage_detailed`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringTreemap;
+    ///
+    /// let mut tm = RoaringTreemap::new();
+    /// tm.extend_consecutive(0..10_000);
+    /// tm.insert(1 << 40);
+    ///
+    /// let usage = tm.memory_usage_detailed();
+    /// assert_eq!(usage.bitmaps.len(), 2);
+    /// assert_eq!(usage.total, usage.stack + usage.heap);
+    /// for (high, bitmap) in &usage.bitmaps {
+    ///     println!("  Partition {}: {} bytes", high, bitmap.total);
+    /// }
+    /// ```
+    pub fn memory_usage_detailed(&self) -> TreemapMemoryUsage {
+        let stack_size = std::mem::size_of::<Self>();
+
+        // Bitmap structs live in the map's heap-allocated nodes, so their
+        // "stack" part counts as heap here
+        let bitmaps: Vec<(u32, MemoryUsage)> = self
+            .bitmaps
+            .iter()
+            .map(|(&high, bitmap)| (high, bitmap.memory_usage_detailed()))
+            .collect();
+        let total_heap = bitmaps
+            .iter()
+            .map(|(_, usage)| std::mem::size_of::<u32>() + usage.total)
+            .sum::<usize>();
+
+        TreemapMemoryUsage {
+            total: stack_size + total_heap,
+            stack: stack_size,
+            heap: total_heap,
+            bitmaps,
+        }
+    }
+}
+
+// Iterators
+
+/// Flattens ordered (high 32 bits, low-value iterator) partitions into u64
+/// values, from either end
+struct Partitioned<P, I> {
+    /// Partitions not yet entered from either end
+    partitions: P,
+    /// High bits and iterator of the partition being iterated from the front
+    front: Option<(u32, I)>,
+    /// High bits and iterator of the partition being iterated from the back
+    back: Option<(u32, I)>,
+    /// Number of values not yet yielded from either end
+    remaining: u64,
+}
+
+impl<P, I> Partitioned<P, I>
+where
+    P: DoubleEndedIterator<Item = (u32, I)>,
+    I: DoubleEndedIterator<Item = u32>,
+{
+    fn new(partitions: P, remaining: u64) -> Self {
+        Partitioned {
+            partitions,
+            front: None,
+            back: None,
+            remaining,
+        }
+    }
+
+    fn next(&mut self) -> Option<u64> {
+        loop {
+            if let Some((high, values)) = &mut self.front {
+                if let Some(low) = values.next() {
+                    self.remaining -= 1;
+                    return Some(RoaringTreemap::combine(*high, low));
+                }
+            }
+
+            match self.partitions.next() {
+                Some(partition) => self.front = Some(partition),
+                None => {
+                    // Drain the partition held by the back end
+                    let (high, values) = self.back.as_mut()?;
+                    let low = values.next()?;
+                    self.remaining -= 1;
+                    return Some(RoaringTreemap::combine(*high, low));
+                }
+            }
+        }
+    }
+
+    fn next_back(&mut self) -> Option<u64> {
+        loop {
+            if let Some((high, values)) = &mut self.back {
+                if let Some(low) = values.next_back() {
+                    self.remaining -= 1;
+                    return Some(RoaringTreemap::combine(*high, low));
+                }
+            }
+
+            match self.partitions.next_back() {
+                Some(partition) => self.back = Some(partition),
+                None => {
+                    // Drain the partition held by the front end
+                    let (high, values) = self.front.as_mut()?;
+                    let low = values.next_back()?;
+                    self.remaining -= 1;
+                    return Some(RoaringTreemap::combine(*high, low));
+                }
+            }
+        }
+    }
+}
+
+/// Partitions of a borrowed treemap, each with an iterator over its bitmap
+type BitmapIters<'a> = std::iter::Map<
+    btree_map::Iter<'a, u32, RoaringBitmap>,
+    fn((&'a u32, &'a RoaringBitmap)) -> (u32, Iter<'a>),
+>;
+
+/// Partitions of an owned treemap, each with an owning iterator over its bitmap
+type BitmapIntoIters = std::iter::Map<
+    btree_map::IntoIter<u32, RoaringBitmap>,
+    fn((u32, RoaringBitmap)) -> (u32, IntoIter),
+>;
+
+/// Iterator over elements in a RoaringTreemap
+///
+/// Yields values in ascending order from the front and descending order from
+/// the back.
+pub struct TreemapIter<'a> {
+    inner: Partitioned<BitmapIters<'a>, Iter<'a>>,
+}
+
+/// Owning iterator over elements in a RoaringTreemap
+///
+/// Created by `RoaringTreemap::into_iter`. Consumes the treemap's bitmaps one
+/// at a time and, like `TreemapIter`, can be driven from both ends.
+pub struct TreemapIntoIter {
+    inner: Partitioned<BitmapIntoIters, IntoIter>,
+}
+
+impl Iterator for TreemapIter<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.inner.remaining as usize;
+        (remaining, Some(remaining))
+    }
+
+    fn last(mut self) -> Option<u64> {
+        self.next_back()
+    }
+}
+
+impl DoubleEndedIterator for TreemapIter<'_> {
+    fn next_back(&mut self) -> Option<u64> {
+        self.inner.next_back()
+    }
+}
+
+impl ExactSizeIterator for TreemapIter<'_> {}
+
+impl FusedIterator for TreemapIter<'_> {}
+
+impl Iterator for TreemapIntoIter {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.inner.remaining as usize;
+        (remaining, Some(remaining))
+    }
+
+    fn last(mut self) -> Option<u64> {
+        self.next_back()
+    }
+}
+
+impl DoubleEndedIterator for TreemapIntoIter {
+    fn next_back(&mut self) -> Option<u64> {
+        self.inner.next_back()
+    }
+}
+
+impl ExactSizeIterator for TreemapIntoIter {}
+
+impl FusedIterator for TreemapIntoIter {}
+
+// Collection trait implementations
+
+impl IntoIterator for RoaringTreemap {
+    type Item = u64;
+    type IntoIter = TreemapIntoIter;
+
+    /// Consumes the treemap, yielding its elements in sorted order
+    fn into_iter(self) -> TreemapIntoIter {
+        let remaining = self.len();
+        TreemapIntoIter {
+            inner: Partitioned::new(
+                self.bitmaps
+                    .into_iter()
+                    .map(|(high, bitmap)| (high, bitmap.into_iter())),
+                remaining,
+            ),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a RoaringTreemap {
+    type Item = u64;
+    type IntoIter = TreemapIter<'a>;
+
+    /// Iterates over the elements in sorted order, same as `iter()`
+    fn into_iter(self) -> TreemapIter<'a> {
+        self.iter()
+    }
+}
+
+impl FromIterator<u64> for RoaringTreemap {
+    /// Builds a treemap from an iterator of values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringTreemap;
+    ///
+    /// let tm: RoaringTreemap = (0..10u64).map(|i| i << 31).collect();
+    /// assert_eq!(tm.len(), 10);
+    /// ```
+    fn from_iter<I: IntoIterator<Item = u64>>(values: I) -> Self {
+        let mut treemap = RoaringTreemap::new();
+        treemap.extend(values);
+        treemap
+    }
+}
+
+impl Extend<u64> for RoaringTreemap {
+    /// Inserts every value from the iterator
+    ///
+    /// # Performance
+    ///
+    /// Consecutive values with the same high 32 bits are handed to that
+    /// partition's bitmap in one `extend` call, so sorted input keeps
+    /// [`RoaringBitmap`]'s single-pass container building.
+    fn extend<I: IntoIterator<Item = u64>>(&mut self, values: I) {
+        let mut chunk_high = 0u32;
+        let mut chunk: Vec<u32> = Vec::new();
+
+        for value in values {
+            let (high, low) = Self::split(value);
+            if high != chunk_high && !chunk.is_empty() {
+                self.bitmaps
+                    .entry(chunk_high)
+                    .or_default()
+                    .extend(chunk.drain(..));
+            }
+            chunk_high = high;
+            chunk.push(low);
+        }
+
+        if !chunk.is_empty() {
+            self.bitmaps.entry(chunk_high).or_default().extend(chunk);
+        }
+    }
+}
+
+impl<'a> Extend<&'a u64> for RoaringTreemap {
+    /// Inserts every referenced value from the iterator
+    fn extend<I: IntoIterator<Item = &'a u64>>(&mut self, values: I) {
+        self.extend(values.into_iter().copied());
+    }
+}
+
+// Formatting
+
+impl fmt::Debug for RoaringTreemap {
+    /// Prints the cardinality, the first few values and the number of bitmaps
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringTreemap;
+    ///
+    /// let tm: RoaringTreemap = [1, 1 << 32].into_iter().collect();
+    /// assert_eq!(
+    ///     format!("{:?}", tm),
+    ///     "RoaringTreemap { len: 2, values: [1, 4294967296], bitmaps: 2 }"
+    /// );
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RoaringTreemap")
+            .field("len", &self.len())
+            .field("values", &DebugValues(self))
+            .field("bitmaps", &self.bitmaps.len())
+            .finish()
+    }
+}
+
+/// Debug helper listing the first `DEBUG_MAX_VALUES` values of a treemap
+struct DebugValues<'a>(&'a RoaringTreemap);
+
+impl fmt::Debug for DebugValues<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut list = f.debug_list();
+        list.entries(self.0.iter().take(crate::DEBUG_MAX_VALUES));
+        if self.0.len() > crate::DEBUG_MAX_VALUES as u64 {
+            list.finish_non_exhaustive()
+        } else {
+            list.finish()
+        }
+    }
+}
+
+// ============================================================================
+// TRAIT IMPLEMENTATIONS FOR OPERATOR OVERLOADING
+// ============================================================================
+
+// BitOr: Union operator (|)
+impl BitOr<&RoaringTreemap> for &RoaringTreemap {
+    type Output = RoaringTreemap;
+
+    /// Implements the `|` operator for union (allocating).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::RoaringTreemap;
+    ///
+    /// let a: RoaringTreemap = [1, 1 << 40].into_iter().collect();
+    /// let b: RoaringTreemap = [2 << 40].into_iter().collect();
+    /// assert_eq!((&a | &b).len(), 3);
+    /// ```
+    fn bitor(self, rhs: &RoaringTreemap) -> RoaringTreemap {
+        self.union(rhs)
+    }
+}
+
+// BitOrAssign: In-place union operator (|=)
+impl BitOrAssign<&RoaringTreemap> for RoaringTreemap {
+    /// Implements the `|=` operator for in-place union.
+    fn bitor_assign(&mut self, rhs: &RoaringTreemap) {
+        self.union_with(rhs);
+    }
+}
+
+// BitAnd: Intersection operator (&)
+impl BitAnd<&RoaringTreemap> for &RoaringTreemap {
+    type Output = RoaringTreemap;
+
+    /// Implements the `&` operator for intersection (allocating).
+    fn bitand(self, rhs: &RoaringTreemap) -> RoaringTreemap {
+        self.intersection(rhs)
+    }
+}
+
+// BitAndAssign: In-place intersection operator (&=)
+impl BitAndAssign<&RoaringTreemap> for RoaringTreemap {
+    /// Implements the `&=` operator for in-place intersection.
+    fn bitand_assign(&mut self, rhs: &RoaringTreemap) {
+        self.intersect_with(rhs);
+    }
+}
+
+// BitXor: Symmetric difference operator (^)
+impl BitXor<&RoaringTreemap> for &RoaringTreemap {
+    type Output = RoaringTreemap;
+
+    /// Implements the `^` operator for symmetric difference (allocating).
+    fn bitxor(self, rhs: &RoaringTreemap) -> RoaringTreemap {
+        self.symmetric_difference(rhs)
+    }
+}
+
+// BitXorAssign: In-place symmetric difference operator (^=)
+impl BitXorAssign<&RoaringTreemap> for RoaringTreemap {
+    /// Implements the `^=` operator for in-place symmetric difference.
+    fn bitxor_assign(&mut self, rhs: &RoaringTreemap) {
+        self.symmetric_difference_with(rhs);
+    }
+}
+
+// Sub: Difference operator (-)
+impl Sub<&RoaringTreemap> for &RoaringTreemap {
+    type Output = RoaringTreemap;
+
+    /// Implements the `-` operator for difference (allocating).
+    fn sub(self, rhs: &RoaringTreemap) -> RoaringTreemap {
+        self.difference(rhs)
+    }
+}
+
+// SubAssign: In-place difference operator (-=)
+impl SubAssign<&RoaringTreemap> for RoaringTreemap {
+    /// Implements the `-=` operator for in-place difference.
+    fn sub_assign(&mut self, rhs: &RoaringTreemap) {
+        self.difference_with(rhs);
+    }
+}
+
+// Additional trait implementations for chaining operations
+// These allow operations like: (&a | &b) & &c, reusing the owned operand
+
+impl BitOr<&RoaringTreemap> for RoaringTreemap {
+    type Output = RoaringTreemap;
+
+    fn bitor(mut self, rhs: &RoaringTreemap) -> RoaringTreemap {
+        self.union_with(rhs);
+        self
+    }
+}
+
+impl BitAnd<&RoaringTreemap> for RoaringTreemap {
+    type Output = RoaringTreemap;
+
+    fn bitand(mut self, rhs: &RoaringTreemap) -> RoaringTreemap {
+        self.intersect_with(rhs);
+        self
+    }
+}
+
+impl BitXor<&RoaringTreemap> for RoaringTreemap {
+    type Output = RoaringTreemap;
+
+    fn bitxor(mut self, rhs: &RoaringTreemap) -> RoaringTreemap {
+        self.symmetric_difference_with(rhs);
+        self
+    }
+}
+
+impl Sub<&RoaringTreemap> for RoaringTreemap {
+    type Output = RoaringTreemap;
+
+    fn sub(mut self, rhs: &RoaringTreemap) -> RoaringTreemap {
+        self.difference_with(rhs);
+        self
+    }
+}
+
+// Additional trait implementations for mixed reference/owned operations
+
+impl BitAnd<RoaringTreemap> for &RoaringTreemap {
+    type Output = RoaringTreemap;
+
+    fn bitand(self, mut rhs: RoaringTreemap) -> RoaringTreemap {
+        rhs.intersect_with(self);
+        rhs
+    }
+}
+
+impl BitOr<RoaringTreemap> for &RoaringTreemap {
+    type Output = RoaringTreemap;
+
+    fn bitor(self, mut rhs: RoaringTreemap) -> RoaringTreemap {
+        rhs.union_with(self);
+        rhs
+    }
+}
+
+impl BitXor<RoaringTreemap> for &RoaringTreemap {
+    type Output = RoaringTreemap;
+
+    fn bitxor(self, mut rhs: RoaringTreemap) -> RoaringTreemap {
+        rhs.symmetric_difference_with(self);
+        rhs
+    }
+}
+
+impl Sub<RoaringTreemap> for &RoaringTreemap {
+    type Output = RoaringTreemap;
+
+    fn sub(self, rhs: RoaringTreemap) -> RoaringTreemap {
+        self.difference(&rhs)
+    }
+}
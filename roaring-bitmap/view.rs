@@ -0,0 +1,862 @@
+// Zero-copy view over a serialized Roaring Bitmap
+
+//! A `RoaringBitmapView` borrows bytes in the portable Roaring serialization
+//! format (see [`RoaringBitmap::serialize_into`]) and answers queries directly
+//! against the on-disk Array, Bitmap and Run layouts.
+//!
+//! The bytes are validated once when the view is created. Afterwards only a
+//! small per-container descriptor (key, layout, byte slice) is kept in memory,
+//! which makes views suitable for bitmaps in memory-mapped files.
+
+use crate::kernels::WordOp;
+use crate::{
+    invalid_data, ArrayContainer, BitmapContainer, Container, RoaringBitmap, RunContainer,
+    ARRAY_TO_BITMAP_THRESHOLD, NO_OFFSET_THRESHOLD, SERIAL_COOKIE, SERIAL_COOKIE_NO_RUNCONTAINER,
+    SERIAL_MAX_ARRAY_CARDINALITY,
+};
+use std::io;
+
+/// Borrowed, read-only view of a bitmap stored in the portable Roaring format
+#[derive(Clone)]
+pub struct RoaringBitmapView<'a> {
+    /// Sorted vector of (key, container view) pairs, mirroring `RoaringBitmap::containers`
+    ///
+    /// # Invariants
+    /// - Keys are sorted in ascending order and unique
+    /// - Each container view has been validated and is non-empty
+    containers: Vec<(u16, ContainerView<'a>)>,
+
+    /// Number of bytes of the input occupied by this bitmap
+    size: usize,
+}
+
+/// A single container borrowed from serialized bytes
+///
+/// Values are little-endian and may be unaligned, so they are decoded on access.
+#[derive(Clone, Copy)]
+enum ContainerView<'a> {
+    /// Sorted u16 values (2 bytes each)
+    Array { data: &'a [u8] },
+
+    /// 1024 u64 words (8192 bytes)
+    Bitmap { data: &'a [u8], cardinality: u64 },
+
+    /// (start, length_minus_1) u16 pairs (4 bytes each), without the run count prefix
+    Run { data: &'a [u8], cardinality: u64 },
+}
+
+/// Iterator over elements in a RoaringBitmapView
+pub struct ViewIter<'a> {
+    /// Remaining containers to iterate
+    containers: std::slice::Iter<'a, (u16, ContainerView<'a>)>,
+    /// Key and cursor of the container currently being iterated
+    current: Option<(u16, ContainerViewIter<'a>)>,
+}
+
+/// Cursor over the low 16-bit values of a single container view
+enum ContainerViewIter<'a> {
+    Array {
+        data: &'a [u8],
+        index: usize,
+    },
+    Bitmap {
+        data: &'a [u8],
+        word_index: usize,
+        current_word: u64,
+    },
+    Run {
+        data: &'a [u8],
+        run_index: usize,
+        offset: u32,
+    },
+}
+
+impl<'a> RoaringBitmapView<'a> {
+    // Construction
+
+    /// Creates a view over bytes in the portable Roaring serialization format.
+    ///
+    /// The whole bitmap is validated up front (same checks as
+    /// [`RoaringBitmap::deserialize_from`]), so later queries never fail. Trailing
+    /// bytes after the bitmap are ignored; [`serialized_size`](Self::serialized_size)
+    /// reports where the bitmap ends.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind `UnexpectedEof` for truncated input, or `InvalidData`
+    /// when the bytes do not describe a valid bitmap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::{RoaringBitmap, RoaringBitmapView};
+    ///
+    /// let mut bm = RoaringBitmap::new();
+    /// bm.extend_consecutive(0..100_000);
+    /// bm.insert(1_000_000);
+    ///
+    /// let mut bytes = Vec::new();
+    /// bm.serialize_into(&mut bytes).unwrap();
+    ///
+    /// let view = RoaringBitmapView::from_bytes(&bytes).unwrap();
+    /// assert_eq!(view.len(), 100_001);
+    /// assert!(view.contains(1_000_000));
+    /// assert!(!view.contains(100_000));
+    /// ```
+    pub fn from_bytes(bytes: &'a [u8]) -> io::Result<Self> {
+        let mut cursor = ByteCursor { bytes, position: 0 };
+        let cookie = cursor.read_u32()?;
+
+        // Cookie determines container count and which containers are Runs
+        let (count, run_flags) = if cookie as u16 == SERIAL_COOKIE {
+            let count = (cookie >> 16) as usize + 1;
+            let run_flags = cursor.take(count.div_ceil(8))?;
+            (count, Some(run_flags))
+        } else if cookie == SERIAL_COOKIE_NO_RUNCONTAINER {
+            let count = cursor.read_u32()? as usize;
+            if count > 1 << 16 {
+                return Err(invalid_data("container count exceeds 65536"));
+            }
+            (count, None)
+        } else {
+            return Err(invalid_data("unknown cookie"));
+        };
+
+        // Descriptive header
+        let header = cursor.take(count * 4)?;
+
+        // Offset header: data is laid out sequentially, so offsets are not needed
+        let has_offsets = run_flags.is_none() || count >= NO_OFFSET_THRESHOLD;
+        if has_offsets {
+            cursor.take(count * 4)?;
+        }
+
+        // Container data
+        let mut containers = Vec::with_capacity(count);
+        for index in 0..count {
+            let key = read_u16_at(header, index * 2);
+            let cardinality = read_u16_at(header, index * 2 + 1) as u64 + 1;
+            if let Some(&(prev_key, _)) = containers.last() {
+                if key <= prev_key {
+                    return Err(invalid_data("container keys are not strictly increasing"));
+                }
+            }
+
+            let is_run = run_flags.is_some_and(|flags| flags[index / 8] & (1 << (index % 8)) != 0);
+
+            let container = if is_run {
+                let num_runs = cursor.read_u16()? as usize;
+                ContainerView::run(cursor.take(num_runs * 4)?, cardinality)?
+            } else if cardinality <= SERIAL_MAX_ARRAY_CARDINALITY {
+                ContainerView::array(cursor.take(cardinality as usize * 2)?)?
+            } else {
+                ContainerView::bitmap(cursor.take(1024 * 8)?, cardinality)?
+            };
+
+            containers.push((key, container));
+        }
+
+        Ok(RoaringBitmapView {
+            containers,
+            size: cursor.position,
+        })
+    }
+
+    /// Returns the number of bytes of the input occupied by this bitmap.
+    ///
+    /// Useful when several serialized bitmaps are stored back to back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::{RoaringBitmap, RoaringBitmapView};
+    ///
+    /// let mut a = RoaringBitmap::new();
+    /// let mut b = RoaringBitmap::new();
+    /// a.extend_sparse([1, 2, 3]);
+    /// b.extend_sparse([40, 50]);
+    ///
+    /// let mut bytes = Vec::new();
+    /// a.serialize_into(&mut bytes).unwrap();
+    /// b.serialize_into(&mut bytes).unwrap();
+    ///
+    /// let first = RoaringBitmapView::from_bytes(&bytes).unwrap();
+    /// let second = RoaringBitmapView::from_bytes(&bytes[first.serialized_size()..]).unwrap();
+    /// assert_eq!(first.len(), 3);
+    /// assert_eq!(second.len(), 2);
+    /// ```
+    pub fn serialized_size(&self) -> usize {
+        self.size
+    }
+
+    // Query Operations
+
+    /// Checks if an element exists in the view
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::{RoaringBitmap, RoaringBitmapView};
+    ///
+    /// let mut bm = RoaringBitmap::new();
+    /// bm.insert(10);
+    /// let mut bytes = Vec::new();
+    /// bm.serialize_into(&mut bytes).unwrap();
+    ///
+    /// let view = RoaringBitmapView::from_bytes(&bytes).unwrap();
+    /// assert!(view.contains(10));
+    /// assert!(!view.contains(20));
+    /// ```
+    pub fn contains(&self, value: u32) -> bool {
+        let (key, low) = RoaringBitmap::split(value);
+
+        match self.containers.binary_search_by_key(&key, |(k, _)| *k) {
+            Ok(index) => self.containers[index].1.contains(low),
+            Err(_) => false,
+        }
+    }
+
+    /// Returns the number of elements in the view (cardinality)
+    pub fn len(&self) -> u64 {
+        self.containers
+            .iter()
+            .map(|(_, container)| container.len())
+            .sum()
+    }
+
+    /// Returns `true` if the view contains no elements
+    pub fn is_empty(&self) -> bool {
+        self.containers.is_empty()
+    }
+
+    // Iteration
+
+    /// Returns an iterator over elements in sorted order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::{RoaringBitmap, RoaringBitmapView};
+    ///
+    /// let mut bm = RoaringBitmap::new();
+    /// bm.extend_sparse([3, 1, 70_000]);
+    /// let mut bytes = Vec::new();
+    /// bm.serialize_into(&mut bytes).unwrap();
+    ///
+    /// let view = RoaringBitmapView::from_bytes(&bytes).unwrap();
+    /// assert_eq!(view.iter().collect::<Vec<_>>(), vec![1, 3, 70_000]);
+    /// ```
+    pub fn iter(&self) -> ViewIter<'_> {
+        ViewIter {
+            containers: self.containers.iter(),
+            current: None,
+        }
+    }
+
+    // Conversion
+
+    /// Decodes the view into an owned `RoaringBitmap`
+    pub fn to_bitmap(&self) -> RoaringBitmap {
+        RoaringBitmap {
+            containers: self
+                .containers
+                .iter()
+                .map(|(key, container)| (*key, container.decode()))
+                .collect(),
+        }
+    }
+
+    // Set Operations
+    //
+    // Results are owned bitmaps. Containers under keys present in both views are
+    // combined directly on the serialized layouts: Bitmap words are read and
+    // combined in place, and Array and Run layouts are merged from their sorted
+    // values and runs. Only containers copied whole into the result (keys held
+    // by one side) are decoded, and skipped containers are never touched.
+
+    /// Returns the union (OR) of two views as an owned bitmap
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use roaring_bitmap::{RoaringBitmap, RoaringBitmapView};
+    ///
+    /// let mut a = RoaringBitmap::new();
+    /// let mut b = RoaringBitmap::new();
+    /// a.extend_sparse([1, 2]);
+    /// b.extend_sparse([2, 3]);
+    /// let (mut bytes_a, mut bytes_b) = (Vec::new(), Vec::new());
+    /// a.serialize_into(&mut bytes_a).unwrap();
+    /// b.serialize_into(&mut bytes_b).unwrap();
+    ///
+    /// let view_a = RoaringBitmapView::from_bytes(&bytes_a).unwrap();
+    /// let view_b = RoaringBitmapView::from_bytes(&bytes_b).unwrap();
+    /// let result = view_a.union(&view_b);
+    /// assert_eq!(result.iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    /// ```
+    pub fn union(&self, other: &RoaringBitmapView<'_>) -> RoaringBitmap {
+        self.merge(other, true, true, |a, b| {
+            ContainerView::combine(WordOp::Or, a, b)
+        })
+    }
+
+    /// Returns the intersection (AND) of two views as an owned bitmap
+    ///
+    /// Containers are intersected directly on the serialized layouts: Bitmap
+    /// words are ANDed in place, and otherwise the smaller container is probed
+    /// against the larger one.
+    pub fn intersection(&self, other: &RoaringBitmapView<'_>) -> RoaringBitmap {
+        self.merge(other, false, false, ContainerView::intersection)
+    }
+
+    /// Returns the difference (AND NOT) of two views as an owned bitmap
+    pub fn difference(&self, other: &RoaringBitmapView<'_>) -> RoaringBitmap {
+        self.merge(other, true, false, |a, b| {
+            ContainerView::combine(WordOp::AndNot, a, b)
+        })
+    }
+
+    /// Returns the symmetric difference (XOR) of two views as an owned bitmap
+    pub fn symmetric_difference(&self, other: &RoaringBitmapView<'_>) -> RoaringBitmap {
+        self.merge(other, true, true, |a, b| {
+            ContainerView::combine(WordOp::Xor, a, b)
+        })
+    }
+
+    /// Merges containers of two views by key.
+    ///
+    /// Containers present on only one side are decoded when `keep_self` /
+    /// `keep_other` is set, and `both` combines containers present on both sides.
+    fn merge<F>(
+        &self,
+        other: &RoaringBitmapView<'_>,
+        keep_self: bool,
+        keep_other: bool,
+        both: F,
+    ) -> RoaringBitmap
+    where
+        F: Fn(&ContainerView<'_>, &ContainerView<'_>) -> Option<Container>,
+    {
+        let mut result_containers = Vec::new();
+        let mut i = 0;
+        let mut j = 0;
+
+        while i < self.containers.len() && j < other.containers.len() {
+            let (key_a, container_a) = &self.containers[i];
+            let (key_b, container_b) = &other.containers[j];
+
+            match key_a.cmp(key_b) {
+                std::cmp::Ordering::Less => {
+                    if keep_self {
+                        result_containers.push((*key_a, container_a.decode()));
+                    }
+                    i += 1;
+                }
+                std::cmp::Ordering::Equal => {
+                    if let Some(container) = both(container_a, container_b) {
+                        result_containers.push((*key_a, container));
+                    }
+                    i += 1;
+                    j += 1;
+                }
+                std::cmp::Ordering::Greater => {
+                    if keep_other {
+                        result_containers.push((*key_b, container_b.decode()));
+                    }
+                    j += 1;
+                }
+            }
+        }
+
+        if keep_self {
+            for (key, container) in &self.containers[i..] {
+                result_containers.push((*key, container.decode()));
+            }
+        }
+
+        if keep_other {
+            for (key, container) in &other.containers[j..] {
+                result_containers.push((*key, container.decode()));
+            }
+        }
+
+        RoaringBitmap {
+            containers: result_containers,
+        }
+    }
+}
+
+impl<'a> ContainerView<'a> {
+    /// Validates an Array layout: values must be strictly increasing
+    fn array(data: &'a [u8]) -> io::Result<Self> {
+        let count = data.len() / 2;
+        for index in 1..count {
+            if read_u16_at(data, index - 1) >= read_u16_at(data, index) {
+                return Err(invalid_data(
+                    "array container values are not strictly increasing",
+                ));
+            }
+        }
+        Ok(ContainerView::Array { data })
+    }
+
+    /// Validates a Bitmap layout: the number of set bits must match `cardinality`
+    fn bitmap(data: &'a [u8], cardinality: u64) -> io::Result<Self> {
+        let actual: u64 = (0..1024)
+            .map(|index| read_u64_at(data, index).count_ones() as u64)
+            .sum();
+        if actual != cardinality {
+            return Err(invalid_data("bitmap container cardinality mismatch"));
+        }
+        Ok(ContainerView::Bitmap { data, cardinality })
+    }
+
+    /// Validates a Run layout: runs must be sorted, non-overlapping,
+    /// non-adjacent and add up to `cardinality`
+    fn run(data: &'a [u8], cardinality: u64) -> io::Result<Self> {
+        let mut total = 0u64;
+        let mut next_allowed_start = 0u32;
+
+        for index in 0..data.len() / 4 {
+            let (start, length) = read_run_at(data, index);
+            // length is (actual_length - 1), so end = start + length
+            let end = start as u32 + length as u32;
+
+            if end > u16::MAX as u32 {
+                return Err(invalid_data("run extends past the end of the container"));
+            }
+            if (start as u32) < next_allowed_start {
+                return Err(invalid_data("runs are unsorted, overlapping or adjacent"));
+            }
+
+            next_allowed_start = end + 2;
+            total += length as u64 + 1;
+        }
+
+        if data.is_empty() || total != cardinality {
+            return Err(invalid_data("run container cardinality mismatch"));
+        }
+
+        Ok(ContainerView::Run { data, cardinality })
+    }
+
+    /// Returns the number of elements in the container
+    fn len(&self) -> u64 {
+        match self {
+            ContainerView::Array { data } => (data.len() / 2) as u64,
+            ContainerView::Bitmap { cardinality, .. } => *cardinality,
+            ContainerView::Run { cardinality, .. } => *cardinality,
+        }
+    }
+
+    /// Checks if a value exists, using binary search for Array and Run layouts
+    fn contains(&self, value: u16) -> bool {
+        match self {
+            ContainerView::Array { data } => {
+                let (mut low, mut high) = (0, data.len() / 2);
+                while low < high {
+                    let mid = low + (high - low) / 2;
+                    match read_u16_at(data, mid).cmp(&value) {
+                        std::cmp::Ordering::Less => low = mid + 1,
+                        std::cmp::Ordering::Equal => return true,
+                        std::cmp::Ordering::Greater => high = mid,
+                    }
+                }
+                false
+            }
+            ContainerView::Bitmap { data, .. } => {
+                let word = read_u64_at(data, value as usize / 64);
+                word & (1u64 << (value % 64)) != 0
+            }
+            ContainerView::Run { data, .. } => {
+                // Find the number of runs starting at or before value
+                let (mut low, mut high) = (0, data.len() / 4);
+                while low < high {
+                    let mid = low + (high - low) / 2;
+                    if read_run_at(data, mid).0 <= value {
+                        low = mid + 1;
+                    } else {
+                        high = mid;
+                    }
+                }
+                if low == 0 {
+                    return false;
+                }
+                let (start, length) = read_run_at(data, low - 1);
+                value as u32 <= start as u32 + length as u32
+            }
+        }
+    }
+
+    /// Returns a cursor over the values in the container
+    fn iter(&self) -> ContainerViewIter<'a> {
+        match *self {
+            ContainerView::Array { data } => ContainerViewIter::Array { data, index: 0 },
+            ContainerView::Bitmap { data, .. } => ContainerViewIter::Bitmap {
+                data,
+                word_index: 0,
+                current_word: read_u64_at(data, 0),
+            },
+            ContainerView::Run { data, .. } => ContainerViewIter::Run {
+                data,
+                run_index: 0,
+                offset: 0,
+            },
+        }
+    }
+
+    /// Decodes the container into an owned `Container`
+    fn decode(&self) -> Container {
+        match self {
+            ContainerView::Array { data } => {
+                let values = (0..data.len() / 2)
+                    .map(|index| read_u16_at(data, index))
+                    .collect();
+                container_from_values(values)
+            }
+            ContainerView::Bitmap { data, cardinality } => {
+                let mut bitmap = BitmapContainer::new();
+                for (index, word) in bitmap.bits.iter_mut().enumerate() {
+                    *word = read_u64_at(data, index);
+                }
+                bitmap.cardinality = *cardinality;
+                Container::Bitmap(bitmap)
+            }
+            ContainerView::Run { data, .. } => {
+                let runs = (0..data.len() / 4)
+                    .map(|index| read_run_at(data, index))
+                    .collect();
+                Container::Run(RunContainer { runs })
+            }
+        }
+    }
+
+    /// Intersects two container views without decoding either of them
+    /// (None if empty)
+    fn intersection(a: &ContainerView<'_>, b: &ContainerView<'_>) -> Option<Container> {
+        match (a, b) {
+            (ContainerView::Bitmap { data: x, .. }, ContainerView::Bitmap { data: y, .. }) => {
+                let mut result = BitmapContainer::new();
+                for (index, word) in result.bits.iter_mut().enumerate() {
+                    *word = read_u64_at(x, index) & read_u64_at(y, index);
+                }
+                result.cardinality = result.bits.iter().map(|w| w.count_ones() as u64).sum();
+
+                if result.cardinality == 0 {
+                    None
+                } else if result.cardinality < ARRAY_TO_BITMAP_THRESHOLD as u64 {
+                    Some(Container::Array(result.to_array()))
+                } else {
+                    Some(Container::Bitmap(result))
+                }
+            }
+            (ContainerView::Run { .. }, ContainerView::Run { .. }) => {
+                // Long runs make probing value by value wasteful; merge the runs
+                merge_runs(WordOp::And, a.runs(), b.runs())
+            }
+            _ => {
+                // Probe each value of the smaller container against the larger one
+                let (small, large) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+                let values: Vec<u16> = small.iter().filter(|&v| large.contains(v)).collect();
+                if values.is_empty() {
+                    None
+                } else {
+                    Some(container_from_values(values))
+                }
+            }
+        }
+    }
+
+    /// Combines two container views with `op` (Or, AndNot or Xor) without
+    /// decoding either of them (None if empty)
+    ///
+    /// If either side is a Bitmap, the left side is laid into a bitmap and the
+    /// right side is applied word by word; Array and Run layouts are otherwise
+    /// merged as sorted runs.
+    fn combine(op: WordOp, a: &ContainerView<'_>, b: &ContainerView<'_>) -> Option<Container> {
+        match (a, b) {
+            // Only the array's values can survive, so probe them
+            (ContainerView::Array { .. }, ContainerView::Bitmap { .. })
+                if matches!(op, WordOp::AndNot) =>
+            {
+                let values: Vec<u16> = a.iter().filter(|&v| !b.contains(v)).collect();
+                if values.is_empty() {
+                    None
+                } else {
+                    Some(container_from_values(values))
+                }
+            }
+            (ContainerView::Bitmap { .. }, _) | (_, ContainerView::Bitmap { .. }) => {
+                let mut result = BitmapContainer::new();
+                a.apply_words(WordOp::Or, &mut result);
+                b.apply_words(op, &mut result);
+                result.recount();
+                Container::from_bitmap(result)
+            }
+            _ => merge_runs(op, a.runs(), b.runs()),
+        }
+    }
+
+    /// Applies `op(word, bits)` to each word of `bitmap` this container has
+    /// values in, where `bits` are the container's values within the word.
+    /// Words the container has no values in are left untouched, so `op` must
+    /// not be And
+    fn apply_words(&self, op: WordOp, bitmap: &mut BitmapContainer) {
+        match *self {
+            ContainerView::Array { .. } => {
+                let mut values = self.iter().peekable();
+                while let Some(value) = values.next() {
+                    let index = value as usize / 64;
+                    let mut bits = 1u64 << (value % 64);
+                    while let Some(next) = values.next_if(|&next| next as usize / 64 == index) {
+                        bits |= 1u64 << (next % 64);
+                    }
+                    bitmap.bits[index] = op.apply(bitmap.bits[index], bits);
+                }
+            }
+            ContainerView::Bitmap { data, .. } => {
+                for (index, word) in bitmap.bits.iter_mut().enumerate() {
+                    *word = op.apply(*word, read_u64_at(data, index));
+                }
+            }
+            ContainerView::Run { data, .. } => {
+                for run in 0..data.len() / 4 {
+                    let (start, length) = read_run_at(data, run);
+                    for (index, mask) in BitmapContainer::range_masks(start, start + length) {
+                        bitmap.bits[index] = op.apply(bitmap.bits[index], mask);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Yields the values of an Array or Run layout as inclusive `(start, end)`
+    /// runs in ascending order, coalescing consecutive Array values
+    fn runs(&self) -> impl Iterator<Item = (u16, u16)> + 'a {
+        let view = *self;
+        let mut index = 0;
+        std::iter::from_fn(move || match view {
+            ContainerView::Array { data } => {
+                let count = data.len() / 2;
+                if index >= count {
+                    return None;
+                }
+                let start = read_u16_at(data, index);
+                let mut end = start;
+                index += 1;
+                while index < count && read_u16_at(data, index) as u32 == end as u32 + 1 {
+                    end += 1;
+                    index += 1;
+                }
+                Some((start, end))
+            }
+            ContainerView::Run { data, .. } => {
+                if index >= data.len() / 4 {
+                    return None;
+                }
+                let (start, length) = read_run_at(data, index);
+                index += 1;
+                Some((start, start + length))
+            }
+            ContainerView::Bitmap { .. } => unreachable!("bitmap views are combined by word"),
+        })
+    }
+}
+
+/// Walks two ascending run sequences together and keeps the stretches where
+/// `op` of the two memberships is set (None if nothing is kept)
+///
+/// Each step ends at the next run boundary on either side, so the cost is
+/// linear in the number of runs rather than values.
+fn merge_runs(
+    op: WordOp,
+    a: impl Iterator<Item = (u16, u16)>,
+    b: impl Iterator<Item = (u16, u16)>,
+) -> Option<Container> {
+    let (mut a, mut b) = (a.peekable(), b.peekable());
+    let mut runs = Vec::new();
+    let mut position = 0u32;
+
+    loop {
+        // Runs that end before `position` have been fully accounted for
+        while a.next_if(|&(_, end)| (end as u32) < position).is_some() {}
+        while b.next_if(|&(_, end)| (end as u32) < position).is_some() {}
+        let (next_a, next_b) = (a.peek().copied(), b.peek().copied());
+        if next_a.is_none() && next_b.is_none() {
+            break;
+        }
+
+        // Membership of `position` on each side, and the last value up to
+        // which neither membership changes
+        let mut end = u16::MAX as u32;
+        let mut member = |run: Option<(u16, u16)>| match run {
+            Some((start, run_end)) if start as u32 <= position => {
+                end = end.min(run_end as u32);
+                true
+            }
+            Some((start, _)) => {
+                end = end.min(start as u32 - 1);
+                false
+            }
+            None => false,
+        };
+        let (in_a, in_b) = (member(next_a), member(next_b));
+
+        if op.apply(in_a as u64, in_b as u64) != 0 {
+            RunContainer::push_merged(&mut runs, position as u16, end as u16);
+        }
+        if end == u16::MAX as u32 {
+            break;
+        }
+        position = end + 1;
+    }
+
+    RunContainer::non_empty(runs).map(RunContainer::into_container)
+}
+
+/// Builds an Array container from sorted values, or a Bitmap if there are too many
+fn container_from_values(values: Vec<u16>) -> Container {
+    let array = ArrayContainer { values };
+    if array.len() as usize >= ARRAY_TO_BITMAP_THRESHOLD {
+        Container::Bitmap(BitmapContainer::from_array(&array))
+    } else {
+        Container::Array(array)
+    }
+}
+
+// Byte access helpers
+
+/// Reads the little-endian u16 at position `index` (in u16 units)
+#[inline]
+fn read_u16_at(data: &[u8], index: usize) -> u16 {
+    u16::from_le_bytes([data[index * 2], data[index * 2 + 1]])
+}
+
+/// Reads the little-endian u64 at position `index` (in u64 units)
+#[inline]
+fn read_u64_at(data: &[u8], index: usize) -> u64 {
+    let bytes = &data[index * 8..index * 8 + 8];
+    u64::from_le_bytes(bytes.try_into().unwrap())
+}
+
+/// Reads the (start, length_minus_1) run at position `index` (in run units)
+#[inline]
+fn read_run_at(data: &[u8], index: usize) -> (u16, u16) {
+    (
+        read_u16_at(data, index * 2),
+        read_u16_at(data, index * 2 + 1),
+    )
+}
+
+/// Bounds-checked sequential reader over a byte slice
+struct ByteCursor<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteCursor<'a> {
+    /// Returns the next `len` bytes, or `UnexpectedEof` if the input is too short
+    fn take(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        let end = self
+            .position
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "serialized bitmap is truncated",
+                )
+            })?;
+        let slice = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn read_u16(&mut self) -> io::Result<u16> {
+        Ok(read_u16_at(self.take(2)?, 0))
+    }
+
+    fn read_u32(&mut self) -> io::Result<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+}
+
+// Iterator implementation
+
+impl Iterator for ContainerViewIter<'_> {
+    type Item = u16;
+
+    fn next(&mut self) -> Option<u16> {
+        match self {
+            ContainerViewIter::Array { data, index } => {
+                if *index < data.len() / 2 {
+                    let value = read_u16_at(data, *index);
+                    *index += 1;
+                    Some(value)
+                } else {
+                    None
+                }
+            }
+            ContainerViewIter::Bitmap {
+                data,
+                word_index,
+                current_word,
+            } => {
+                // Skip exhausted words
+                while *current_word == 0 {
+                    *word_index += 1;
+                    if *word_index >= 1024 {
+                        return None;
+                    }
+                    *current_word = read_u64_at(data, *word_index);
+                }
+
+                // Pop the lowest set bit
+                let bit = current_word.trailing_zeros() as usize;
+                *current_word &= *current_word - 1;
+                Some((*word_index * 64 + bit) as u16)
+            }
+            ContainerViewIter::Run {
+                data,
+                run_index,
+                offset,
+            } => {
+                if *run_index >= data.len() / 4 {
+                    return None;
+                }
+
+                let (start, length) = read_run_at(data, *run_index);
+                let value = start as u32 + *offset;
+
+                // length is (actual_length - 1), so the last offset is `length`
+                if *offset == length as u32 {
+                    *run_index += 1;
+                    *offset = 0;
+                } else {
+                    *offset += 1;
+                }
+
+                Some(value as u16)
+            }
+        }
+    }
+}
+
+impl Iterator for ViewIter<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        loop {
+            if let Some((key, cursor)) = &mut self.current {
+                if let Some(low) = cursor.next() {
+                    return Some(RoaringBitmap::combine(*key, low));
+                }
+            }
+
+            // Current container exhausted, move to the next one
+            let (key, container) = self.containers.next()?;
+            self.current = Some((*key, container.iter()));
+        }
+    }
+}
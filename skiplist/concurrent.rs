@@ -0,0 +1,986 @@
+// Lock-free concurrent skip list
+
+//! A lock-free skip list map that many threads can use at once.
+//!
+//! [`ConcurrentSkipList`] is shared by reference (for example in an `Arc`) and
+//! supports concurrent [`insert`](ConcurrentSkipList::insert),
+//! [`remove`](ConcurrentSkipList::remove), [`get`](ConcurrentSkipList::get) and
+//! ordered iteration, all through `&self`. This makes it suitable as a memtable
+//! or any other ordered index written and read by several threads.
+//!
+//! Unlike [`SkipList`](crate::SkipList) it is not intrusive: the list allocates
+//! a node for each key and value, because a removed node has to stay readable
+//! for as long as other threads may still be looking at it.
+//!
+//! ## Algorithm
+//!
+//! Towers are linked with compare-and-swap, as in the lock-free skip lists of
+//! Fraser and of Herlihy, Lev, Luchangco and Shavit:
+//!
+//! - The low bit of a tower pointer *marks* the node that owns it as removed
+//!   at that level. A marked pointer never changes again.
+//! - `remove` marks a node's tower from the top down. Marking level 0 is the
+//!   point where the node leaves the map, so only one of several concurrent
+//!   removers succeeds.
+//! - `insert` links level 0 first, which is the point where the node enters
+//!   the map, then links the upper levels one at a time. It stops building
+//!   the tower if the node is removed in the meantime.
+//! - Searches *snip* marked nodes out of every level they pass, so a removed
+//!   node is unlinked by whichever thread meets it first. A search never
+//!   follows a link out of a removed node, since the node after it may already
+//!   have been unlinked and freed; it starts over instead. Iterators step along
+//!   level 0 while the links are unmarked, and search again for the next key
+//!   when they meet a removed node.
+//!
+//! ## Memory reclamation
+//!
+//! Removed nodes are freed through epoch-based reclamation. Every operation
+//! pins the list's collector, and a node is retired once it is unlinked from
+//! every level; it is freed after all operations that might still see it have
+//! finished. An [`Entry`] keeps its operation's pin, so a node stays readable
+//! through an entry even after it has been removed. Keeping entries or
+//! iterators alive for a long time therefore delays reclamation.
+//!
+//! # Example
+//!
+//! ```rust
+//! use skiplist::ConcurrentSkipList;
+//! use std::sync::Arc;
+//! use std::thread;
+//!
+//! let memtable = Arc::new(ConcurrentSkipList::new());
+//!
+//! let writers: Vec<_> = (0..4)
+//!     .map(|thread| {
+//!         let memtable = Arc::clone(&memtable);
+//!         thread::spawn(move || {
+//!             for i in 0..100 {
+//!                 let key = i * 4 + thread;
+//!                 assert!(memtable.insert(key, format!("value {}", key)).is_ok());
+//!             }
+//!         })
+//!     })
+//!     .collect();
+//! for writer in writers {
+//!     writer.join().unwrap();
+//! }
+//!
+//! assert_eq!(memtable.len(), 400);
+//! assert_eq!(memtable.get(&42).unwrap().value(), "value 42");
+//! let keys: Vec<u32> = memtable.range(10..14).map(|entry| *entry.key()).collect();
+//! assert_eq!(keys, vec![10, 11, 12, 13]);
+//! ```
+
+mod epoch;
+
+use crate::{before_start, past_end, MAX_LEVEL};
+use epoch::{Collector, Guard};
+use smallvec::SmallVec;
+use std::borrow::Borrow;
+use std::fmt;
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds};
+use std::ptr::{self, NonNull};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicPtr, AtomicU64, AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Low bit of a tower pointer, set once the pointer's owner is removed at that level.
+const MARK: usize = 1;
+
+/// Number of parties that hold a new node: the list, until the node is
+/// removed and unlinked, and its inserter, until the tower is built. The last
+/// one to let go retires the node.
+const NODE_OWNERS: usize = 2;
+
+/// Retired nodes are collected after every this many retirements.
+const COLLECT_INTERVAL: usize = 64;
+
+#[inline]
+fn is_marked<T>(ptr: *mut T) -> bool {
+    ptr.addr() & MARK != 0
+}
+
+#[inline]
+fn marked<T>(ptr: *mut T) -> *mut T {
+    ptr.map_addr(|addr| addr | MARK)
+}
+
+#[inline]
+fn unmarked<T>(ptr: *mut T) -> *mut T {
+    ptr.map_addr(|addr| addr & !MARK)
+}
+
+/// Successor links of a node (or of the head) at each level.
+type Tower<K, V> = [AtomicPtr<Node<K, V>>];
+
+/// A key-value pair with its tower, allocated by the list.
+struct Node<K, V> {
+    key: K,
+    value: V,
+
+    /// Parties still holding the node (see `NODE_OWNERS`).
+    owners: AtomicUsize,
+
+    /// Epoch in which the node was retired.
+    retired_epoch: AtomicUsize,
+
+    /// Next node in the list's garbage stack once retired.
+    retired_next: AtomicPtr<Node<K, V>>,
+
+    /// Successor at each level, marked when this node is removed at that level.
+    tower: SmallVec<[AtomicPtr<Node<K, V>>; 4]>,
+}
+
+impl<K, V> Node<K, V> {
+    fn new(key: K, value: V, height: usize) -> Self {
+        Self {
+            key,
+            value,
+            owners: AtomicUsize::new(NODE_OWNERS),
+            retired_epoch: AtomicUsize::new(0),
+            retired_next: AtomicPtr::new(ptr::null_mut()),
+            tower: (0..height)
+                .map(|_| AtomicPtr::new(ptr::null_mut()))
+                .collect(),
+        }
+    }
+}
+
+/// Neighbours of a key at each level, as found by `find`.
+struct Position<'g, K, V> {
+    /// Last tower before the key at each level.
+    preds: SmallVec<[&'g Tower<K, V>; MAX_LEVEL + 1]>,
+
+    /// First node at or after the key at each level.
+    succs: SmallVec<[*mut Node<K, V>; MAX_LEVEL + 1]>,
+}
+
+/// A lock-free ordered map supporting concurrent access from many threads.
+///
+/// Keys are unique. All operations take `&self`; share the list between
+/// threads with an `Arc` or scoped threads. Lookups return [`Entry`] handles,
+/// which keep the entry readable even if another thread removes it.
+///
+/// Iteration is in key order and *weakly consistent*: it never yields an
+/// entry twice or out of order, and yields every entry that stays in the list
+/// for the whole iteration, but entries inserted or removed meanwhile may or
+/// may not be seen.
+pub struct ConcurrentSkipList<K, V> {
+    /// Sentinel tower with a link per level.
+    head: Box<Tower<K, V>>,
+
+    /// Highest level any node may use (where searches start).
+    level: AtomicUsize,
+
+    /// Maximum level of any node.
+    max_level: usize,
+
+    /// Number of entries.
+    len: AtomicUsize,
+
+    /// Splitmix64 counter drawing tower heights.
+    seed: AtomicU64,
+
+    /// Epoch collector every operation pins.
+    collector: Collector,
+
+    /// Retired nodes waiting for their epoch to expire (a Treiber stack).
+    garbage: AtomicPtr<Node<K, V>>,
+
+    /// Number of nodes retired so far, to collect every `COLLECT_INTERVAL`.
+    retired: AtomicUsize,
+
+    /// The list owns its nodes; the raw pointer also opts out of the automatic
+    /// `Send` and `Sync` impls, which are provided below with the right bounds.
+    _marker: PhantomData<*mut Node<K, V>>,
+}
+
+// SAFETY: Keys and values are moved into the list by one thread, read through
+// shared references by any thread, and dropped by whichever thread frees the
+// node, so they must be both `Send` and `Sync`. All shared state is atomic.
+unsafe impl<K: Send + Sync, V: Send + Sync> Send for ConcurrentSkipList<K, V> {}
+unsafe impl<K: Send + Sync, V: Send + Sync> Sync for ConcurrentSkipList<K, V> {}
+
+impl<K, V> ConcurrentSkipList<K, V>
+where
+    K: Ord,
+{
+    /// Creates a new empty concurrent skiplist.
+    ///
+    /// Uses `MAX_LEVEL` as the maximum level and `DEFAULT_PROBABILITY` for
+    /// promotion, seeded from the system time.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use skiplist::ConcurrentSkipList;
+    ///
+    /// let map: ConcurrentSkipList<u64, String> = ConcurrentSkipList::new();
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        Self::with_max_level(MAX_LEVEL)
+    }
+
+    /// Creates a new empty concurrent skiplist with a custom maximum level.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_level` - Maximum number of levels (0-based, so actual levels = max_level + 1)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use skiplist::ConcurrentSkipList;
+    ///
+    /// let map: ConcurrentSkipList<u64, String> = ConcurrentSkipList::with_max_level(8);
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn with_max_level(max_level: usize) -> Self {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos() as u64;
+
+        Self {
+            head: (0..=max_level)
+                .map(|_| AtomicPtr::new(ptr::null_mut()))
+                .collect(),
+            level: AtomicUsize::new(0),
+            max_level,
+            len: AtomicUsize::new(0),
+            seed: AtomicU64::new(seed),
+            collector: Collector::new(),
+            garbage: AtomicPtr::new(ptr::null_mut()),
+            retired: AtomicUsize::new(0),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of entries.
+    ///
+    /// While other threads insert or remove entries, the result is only a
+    /// snapshot and may already be out of date.
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+
+    /// Returns `true` if the skiplist contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Inserts a key-value pair.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the key was inserted
+    /// * `Err((key, value))` if the key is already present
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use skiplist::ConcurrentSkipList;
+    ///
+    /// let map = ConcurrentSkipList::new();
+    /// assert!(map.insert(7, "seven").is_ok());
+    /// assert_eq!(map.insert(7, "again"), Err((7, "again")));
+    /// ```
+    pub fn insert(&self, key: K, value: V) -> Result<(), (K, V)> {
+        let guard = self.collector.pin();
+        let level = self.random_level();
+
+        // Raise the search level before linking, so every search that may
+        // need to unlink the node starts high enough
+        let top = self.level.fetch_max(level, Ordering::AcqRel).max(level);
+
+        let node = Box::into_raw(Box::new(Node::new(key, value, level + 1)));
+
+        // SAFETY: The node is owned by this call until level 0 is linked, and
+        // kept alive afterwards by its inserter's share of `owners`; every
+        // other node is reached while pinned.
+        unsafe {
+            let new = &*node;
+
+            let mut position = loop {
+                let (position, found) = self.find(Bound::Included(&new.key), top, &guard);
+                if found {
+                    let Node { key, value, .. } = *Box::from_raw(node);
+                    return Err((key, value));
+                }
+
+                // Linking level 0 puts the entry in the map
+                let succ = position.succs[0];
+                new.tower[0].store(succ, Ordering::Relaxed);
+                if position.preds[0][0]
+                    .compare_exchange(succ, node, Ordering::AcqRel, Ordering::Acquire)
+                    .is_ok()
+                {
+                    break position;
+                }
+            };
+            self.len.fetch_add(1, Ordering::Relaxed);
+
+            // Link the upper levels, unless a remover gets to the node first
+            'tower: for level in 1..=level {
+                loop {
+                    let succ = position.succs[level];
+
+                    // A successor with the same key was removed after the
+                    // first search passed it on this level. Linking in front
+                    // of it would hide it from the searches that unlink it,
+                    // so search again instead, which snips it
+                    let removed_twin = succ.as_ref().is_some_and(|succ| succ.key == new.key);
+                    if !removed_twin {
+                        // Point the node at its successor; a marked link
+                        // means the node is being removed
+                        let current = new.tower[level].load(Ordering::Acquire);
+                        if is_marked(current) {
+                            break 'tower;
+                        }
+                        if current != succ
+                            && new.tower[level]
+                                .compare_exchange(
+                                    current,
+                                    succ,
+                                    Ordering::AcqRel,
+                                    Ordering::Acquire,
+                                )
+                                .is_err()
+                        {
+                            break 'tower;
+                        }
+
+                        if position.preds[level][level]
+                            .compare_exchange(succ, node, Ordering::AcqRel, Ordering::Acquire)
+                            .is_ok()
+                        {
+                            break;
+                        }
+                    }
+
+                    // The neighbours changed: search again, giving up if the
+                    // node is no longer in the map
+                    let (fresh, _) = self.find(Bound::Included(&new.key), top, &guard);
+                    if fresh.succs[0] != node {
+                        break 'tower;
+                    }
+                    position = fresh;
+                }
+            }
+
+            // A remover that marked the node while levels were still being
+            // linked may have missed some of them. Reading the mark with a
+            // read-modify-write orders it against the remover's own marking:
+            // either this call sees the mark, or the remover's search sees
+            // every level linked above
+            if is_marked(new.tower[0].fetch_or(0, Ordering::AcqRel)) {
+                self.find(Bound::Included(&new.key), top, &guard);
+            }
+            self.release(node);
+        }
+
+        Ok(())
+    }
+
+    /// Removes the entry with the given key.
+    ///
+    /// The key may be any borrowed form of the key type, as with
+    /// [`SkipList::get`](crate::SkipList::get). When several threads remove
+    /// the same key at once, exactly one of them gets the entry.
+    ///
+    /// # Returns
+    ///
+    /// The removed entry, which stays readable while the returned handle
+    /// lives, or `None` if the key is not present
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use skiplist::ConcurrentSkipList;
+    ///
+    /// let map = ConcurrentSkipList::new();
+    /// map.insert("k".to_string(), 1).unwrap();
+    ///
+    /// let removed = map.remove("k").unwrap();
+    /// assert_eq!(*removed.value(), 1);
+    /// assert!(removed.is_removed());
+    /// assert!(map.remove("k").is_none());
+    /// ```
+    pub fn remove<Q>(&self, key: &Q) -> Option<Entry<'_, K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let guard = self.collector.pin();
+        let top = self.level.load(Ordering::Acquire);
+
+        // SAFETY: Nodes are reached while pinned, and the removed node is
+        // only retired after it is unlinked, so it outlives the guard
+        unsafe {
+            let (position, found) = self.find(Bound::Included(key), top, &guard);
+            if !found {
+                return None;
+            }
+            let node = position.succs[0];
+            let target = &*node;
+
+            // Mark the upper levels from the top down, which also stops an
+            // unfinished tower from growing
+            for link in target.tower[1..].iter().rev() {
+                let mut succ = link.load(Ordering::Acquire);
+                while !is_marked(succ) {
+                    match link.compare_exchange_weak(
+                        succ,
+                        marked(succ),
+                        Ordering::AcqRel,
+                        Ordering::Acquire,
+                    ) {
+                        Ok(_) => break,
+                        Err(current) => succ = current,
+                    }
+                }
+            }
+
+            // Marking level 0 removes the entry; only one remover succeeds
+            let mut succ = target.tower[0].load(Ordering::Acquire);
+            loop {
+                if is_marked(succ) {
+                    return None;
+                }
+                match target.tower[0].compare_exchange_weak(
+                    succ,
+                    marked(succ),
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                ) {
+                    Ok(_) => break,
+                    Err(current) => succ = current,
+                }
+            }
+            self.len.fetch_sub(1, Ordering::Relaxed);
+
+            // Searching for the key snips the node out of every level
+            self.find(
+                Bound::Included(key),
+                top.max(target.tower.len() - 1),
+                &guard,
+            );
+            self.release(node);
+
+            Some(Entry::new(node, Rc::new(guard)))
+        }
+    }
+
+    /// Looks up an entry by key.
+    ///
+    /// The key may be any borrowed form of the key type, as with
+    /// [`SkipList::get`](crate::SkipList::get).
+    ///
+    /// # Returns
+    ///
+    /// A handle to the entry, or `None` if the key is not present
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use skiplist::ConcurrentSkipList;
+    ///
+    /// let map = ConcurrentSkipList::new();
+    /// map.insert(42, "answer").unwrap();
+    ///
+    /// assert_eq!(map.get(&42).map(|entry| *entry.value()), Some("answer"));
+    /// assert!(map.get(&7).is_none());
+    /// ```
+    pub fn get<Q>(&self, key: &Q) -> Option<Entry<'_, K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let guard = self.collector.pin();
+        let node = self.lower_bound(Bound::Included(key), &guard)?;
+        if node.key.borrow() != key {
+            return None;
+        }
+        Some(Entry::new(node as *const _ as *mut _, Rc::new(guard)))
+    }
+
+    /// Returns `true` if the skiplist contains the key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Returns the entry with the smallest key.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use skiplist::ConcurrentSkipList;
+    ///
+    /// let map = ConcurrentSkipList::new();
+    /// for key in [30, 10, 20] {
+    ///     map.insert(key, ()).unwrap();
+    /// }
+    /// assert_eq!(map.first().map(|entry| *entry.key()), Some(10));
+    /// ```
+    pub fn first(&self) -> Option<Entry<'_, K, V>> {
+        self.iter().next()
+    }
+
+    /// Returns an iterator over the entries in key order.
+    ///
+    /// The iterator is weakly consistent (see [`ConcurrentSkipList`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use skiplist::ConcurrentSkipList;
+    ///
+    /// let map = ConcurrentSkipList::new();
+    /// for key in [3, 1, 2] {
+    ///     map.insert(key, key * 10).unwrap();
+    /// }
+    ///
+    /// let values: Vec<i32> = map.iter().map(|entry| *entry.value()).collect();
+    /// assert_eq!(values, vec![10, 20, 30]);
+    /// ```
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let guard = self.collector.pin();
+        let next = self.lower_bound::<K>(Bound::Unbounded, &guard);
+        Iter {
+            list: self,
+            next: next.map(NonNull::from),
+            guard: Rc::new(guard),
+        }
+    }
+
+    /// Returns an iterator over the entries whose keys fall within `range`,
+    /// in key order.
+    ///
+    /// Like [`SkipList::range`](crate::SkipList::range), the bounds may use a
+    /// borrowed form of the key. The iterator is weakly consistent (see
+    /// [`ConcurrentSkipList`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use skiplist::ConcurrentSkipList;
+    ///
+    /// let map = ConcurrentSkipList::new();
+    /// for key in 0..10 {
+    ///     map.insert(key, ()).unwrap();
+    /// }
+    ///
+    /// let keys: Vec<i32> = map.range(3..6).map(|entry| *entry.key()).collect();
+    /// assert_eq!(keys, vec![3, 4, 5]);
+    /// ```
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, K, V, R, Q>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let guard = self.collector.pin();
+        let next = self.lower_bound(range.start_bound(), &guard);
+        Range {
+            list: self,
+            next: next.map(NonNull::from),
+            range,
+            guard: Rc::new(guard),
+            _query: PhantomData,
+        }
+    }
+
+    /// Draws the level of a new node: each level is reached with probability 1/2.
+    fn random_level(&self) -> usize {
+        // Splitmix64 over a shared counter: one atomic add per draw, no retries
+        let mut z = self
+            .seed
+            .fetch_add(0x9E37_79B9_7F4A_7C15, Ordering::Relaxed);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+
+        (z.trailing_ones() as usize).min(self.max_level)
+    }
+
+    /// Finds the neighbours of the range starting at `start` on every level up
+    /// to `top`, snipping removed nodes out of each level on the way.
+    ///
+    /// Returns the neighbours, and whether the node after them on level 0
+    /// has the start key itself.
+    ///
+    /// # Safety
+    ///
+    /// `guard` must pin this list's collector, and `top` must not exceed `max_level`.
+    unsafe fn find<'g, Q>(
+        &'g self,
+        start: Bound<&Q>,
+        top: usize,
+        _guard: &Guard<'_>,
+    ) -> (Position<'g, K, V>, bool)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        'retry: loop {
+            let mut position = Position {
+                preds: SmallVec::from_elem(&*self.head, top + 1),
+                succs: SmallVec::from_elem(ptr::null_mut(), top + 1),
+            };
+            let mut pred: &Tower<K, V> = &self.head;
+
+            for level in (0..=top).rev() {
+                let mut curr = pred[level].load(Ordering::Acquire);
+                if is_marked(curr) {
+                    // The predecessor is being removed: start over
+                    continue 'retry;
+                }
+
+                while let Some(node) = curr.as_ref() {
+                    let succ = node.tower[level].load(Ordering::Acquire);
+                    if is_marked(succ) {
+                        // The node is removed at this level: unlink it
+                        let succ = unmarked(succ);
+                        if pred[level]
+                            .compare_exchange(curr, succ, Ordering::AcqRel, Ordering::Acquire)
+                            .is_err()
+                        {
+                            continue 'retry;
+                        }
+                        curr = succ;
+                        continue;
+                    }
+
+                    if !before_start(&node.key, start) {
+                        break;
+                    }
+                    pred = &node.tower;
+                    curr = succ;
+                }
+
+                position.preds[level] = pred;
+                position.succs[level] = curr;
+            }
+
+            let found = match start {
+                Bound::Included(key) => position.succs[0]
+                    .as_ref()
+                    .is_some_and(|node| node.key.borrow() == key),
+                _ => false,
+            };
+            return (position, found);
+        }
+    }
+
+    /// Returns the first node that isn't removed and doesn't come before `start`.
+    fn lower_bound<'g, Q>(&'g self, start: Bound<&Q>, guard: &Guard<'_>) -> Option<&'g Node<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let top = self.level.load(Ordering::Acquire);
+        loop {
+            // SAFETY: The caller is pinned and top is within the head
+            let (position, _) = unsafe { self.find(start, top, guard) };
+            // SAFETY: find only returns nodes reached while pinned
+            let node = unsafe { position.succs[0].as_ref() }?;
+            if !is_marked(node.tower[0].load(Ordering::Acquire)) {
+                return Some(node);
+            }
+            // Removed since the search passed it: the next search snips it
+        }
+    }
+
+    /// Returns the first node after `node` that isn't removed.
+    ///
+    /// Steps along level 0 while that is safe, and otherwise searches for the
+    /// first key after `node`'s.
+    fn next_live<'g>(&'g self, node: &Node<K, V>, guard: &Guard<'_>) -> Option<&'g Node<K, V>> {
+        let succ = node.tower[0].load(Ordering::Acquire);
+        if !is_marked(succ) {
+            // SAFETY: An unmarked link leads to a node that was still linked
+            // while pinned
+            let next = unsafe { succ.as_ref() }?;
+            if !is_marked(next.tower[0].load(Ordering::Acquire)) {
+                return Some(next);
+            }
+        }
+        self.lower_bound(Bound::Excluded(&node.key), guard)
+    }
+
+    /// Gives up one owner's hold on `node`, retiring it after the last one.
+    ///
+    /// # Safety
+    ///
+    /// Each of the `NODE_OWNERS` parties must call this once. The list's call
+    /// must come after the node is marked and unlinked from every level.
+    unsafe fn release(&self, node: *mut Node<K, V>) {
+        if (*node).owners.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.retire(node);
+        }
+    }
+
+    /// Pushes an unlinked node onto the garbage stack.
+    ///
+    /// # Safety
+    ///
+    /// `node` must be unreachable from the list and retired only once.
+    unsafe fn retire(&self, node: *mut Node<K, V>) {
+        (*node)
+            .retired_epoch
+            .store(self.collector.epoch(), Ordering::Relaxed);
+
+        let mut head = self.garbage.load(Ordering::Relaxed);
+        loop {
+            (*node).retired_next.store(head, Ordering::Relaxed);
+            match self.garbage.compare_exchange_weak(
+                head,
+                node,
+                Ordering::Release,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => break,
+                Err(current) => head = current,
+            }
+        }
+
+        let retired = self.retired.fetch_add(1, Ordering::Relaxed) + 1;
+        if retired.is_multiple_of(COLLECT_INTERVAL) {
+            self.collect();
+        }
+    }
+
+    /// Frees retired nodes whose epoch has expired and puts the rest back.
+    fn collect(&self) {
+        let epoch = self.collector.try_advance();
+
+        let mut pending = self.garbage.swap(ptr::null_mut(), Ordering::Acquire);
+        let mut kept: *mut Node<K, V> = ptr::null_mut();
+        let mut kept_tail: *mut Node<K, V> = ptr::null_mut();
+
+        // SAFETY: The swap gave this call sole ownership of the taken nodes,
+        // which are unreachable and were allocated by insert from a Box
+        unsafe {
+            while let Some(node) = pending.as_ref() {
+                let current = pending;
+                pending = node.retired_next.load(Ordering::Relaxed);
+
+                if Collector::expired(node.retired_epoch.load(Ordering::Relaxed), epoch) {
+                    drop(Box::from_raw(current));
+                } else {
+                    node.retired_next.store(kept, Ordering::Relaxed);
+                    if kept.is_null() {
+                        kept_tail = current;
+                    }
+                    kept = current;
+                }
+            }
+
+            // Return the nodes that must wait longer
+            if let Some(tail) = kept_tail.as_ref() {
+                let mut head = self.garbage.load(Ordering::Relaxed);
+                loop {
+                    tail.retired_next.store(head, Ordering::Relaxed);
+                    match self.garbage.compare_exchange_weak(
+                        head,
+                        kept,
+                        Ordering::Release,
+                        Ordering::Relaxed,
+                    ) {
+                        Ok(_) => break,
+                        Err(current) => head = current,
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<K, V> Drop for ConcurrentSkipList<K, V> {
+    /// Frees every node still linked, then every retired node.
+    fn drop(&mut self) {
+        // SAFETY: Dropping requires exclusive access, so no operation is in
+        // progress: linked and retired nodes are disjoint and all came from
+        // insert's Box
+        unsafe {
+            let mut current = unmarked(*self.head[0].get_mut());
+            while !current.is_null() {
+                let node = Box::from_raw(current);
+                current = unmarked(node.tower[0].load(Ordering::Relaxed));
+            }
+
+            let mut current = *self.garbage.get_mut();
+            while !current.is_null() {
+                let node = Box::from_raw(current);
+                current = node.retired_next.load(Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+impl<K, V> Default for ConcurrentSkipList<K, V>
+where
+    K: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a ConcurrentSkipList<K, V>
+where
+    K: Ord,
+{
+    type Item = Entry<'a, K, V>;
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+/// A handle to an entry of a [`ConcurrentSkipList`].
+///
+/// Returned by lookups, removals and iterators. The handle keeps the entry
+/// readable even if another thread removes it, by holding the epoch pin of
+/// the operation that produced it; use [`is_removed`](Self::is_removed) to
+/// check whether that has happened.
+///
+/// Entries are not `Send`: they are read by the thread that looked them up.
+pub struct Entry<'a, K, V> {
+    node: NonNull<Node<K, V>>,
+    _guard: Rc<Guard<'a>>,
+}
+
+impl<'a, K, V> Entry<'a, K, V> {
+    fn new(node: *mut Node<K, V>, guard: Rc<Guard<'a>>) -> Self {
+        Self {
+            node: NonNull::new(node).unwrap(),
+            _guard: guard,
+        }
+    }
+
+    fn node(&self) -> &Node<K, V> {
+        // SAFETY: The pinned guard keeps the node from being freed
+        unsafe { self.node.as_ref() }
+    }
+
+    /// Returns the entry's key.
+    pub fn key(&self) -> &K {
+        &self.node().key
+    }
+
+    /// Returns the entry's value.
+    pub fn value(&self) -> &V {
+        &self.node().value
+    }
+
+    /// Returns `true` if the entry has been removed from the skiplist.
+    pub fn is_removed(&self) -> bool {
+        is_marked(self.node().tower[0].load(Ordering::Acquire))
+    }
+}
+
+impl<K, V> Clone for Entry<'_, K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            node: self.node,
+            _guard: Rc::clone(&self._guard),
+        }
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for Entry<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Entry")
+            .field("key", self.key())
+            .field("value", self.value())
+            .finish()
+    }
+}
+
+/// An iterator over the entries of a [`ConcurrentSkipList`], in key order.
+///
+/// Created by [`ConcurrentSkipList::iter`]. Every yielded [`Entry`] shares the
+/// iterator's epoch pin.
+pub struct Iter<'a, K, V> {
+    list: &'a ConcurrentSkipList<K, V>,
+
+    /// Next entry to yield.
+    next: Option<NonNull<Node<K, V>>>,
+    guard: Rc<Guard<'a>>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V>
+where
+    K: Ord,
+{
+    type Item = Entry<'a, K, V>;
+
+    fn next(&mut self) -> Option<Entry<'a, K, V>> {
+        let ptr = self.next?;
+        // SAFETY: The iterator's guard keeps reached nodes from being freed
+        let node = unsafe { ptr.as_ref() };
+        self.next = self.list.next_live(node, &self.guard).map(NonNull::from);
+        Some(Entry::new(ptr.as_ptr(), Rc::clone(&self.guard)))
+    }
+}
+
+impl<K: Ord, V> FusedIterator for Iter<'_, K, V> {}
+
+/// An iterator over the entries of a [`ConcurrentSkipList`] within a key
+/// range, in key order.
+///
+/// Created by [`ConcurrentSkipList::range`].
+pub struct Range<'a, K, V, R, Q = K>
+where
+    K: Borrow<Q>,
+    R: RangeBounds<Q>,
+    Q: Ord + ?Sized,
+{
+    list: &'a ConcurrentSkipList<K, V>,
+
+    /// Next entry to yield, if it is still within the range.
+    next: Option<NonNull<Node<K, V>>>,
+    range: R,
+    guard: Rc<Guard<'a>>,
+    /// The borrowed key form the bounds are expressed in.
+    _query: PhantomData<fn(&Q)>,
+}
+
+impl<'a, K, V, R, Q> Iterator for Range<'a, K, V, R, Q>
+where
+    K: Ord + Borrow<Q>,
+    R: RangeBounds<Q>,
+    Q: Ord + ?Sized,
+{
+    type Item = Entry<'a, K, V>;
+
+    fn next(&mut self) -> Option<Entry<'a, K, V>> {
+        let ptr = self.next?;
+        // SAFETY: The iterator's guard keeps reached nodes from being freed
+        let node = unsafe { ptr.as_ref() };
+        if past_end(&node.key, self.range.end_bound()) {
+            self.next = None;
+            return None;
+        }
+
+        self.next = self.list.next_live(node, &self.guard).map(NonNull::from);
+        Some(Entry::new(ptr.as_ptr(), Rc::clone(&self.guard)))
+    }
+}
+
+impl<K, V, R, Q> FusedIterator for Range<'_, K, V, R, Q>
+where
+    K: Ord + Borrow<Q>,
+    R: RangeBounds<Q>,
+    Q: Ord + ?Sized,
+{
+}
@@ -0,0 +1,168 @@
+// Epoch-based memory reclamation for the concurrent skiplist
+
+//! Threads *pin* a [`Collector`] before reading shared nodes and unpin it by
+//! dropping the returned [`Guard`]. A node that has been unlinked is stamped
+//! with the current epoch when it is retired, and may be freed once the global
+//! epoch has advanced twice since: the epoch only advances when every pinned
+//! guard has seen the current one, so by then no guard that could still reach
+//! the node is left.
+//!
+//! Each guard claims a slot in an append-only list instead of a per-thread
+//! record, so no thread-local state is needed and a collector can belong to a
+//! single data structure. Slots are reused once their guard is dropped, so the
+//! list only grows to the number of guards alive at the same time.
+//!
+//! The protocol follows `crossbeam-epoch`: pinning publishes the epoch and then
+//! issues a `SeqCst` fence before any shared pointer is read, and advancing
+//! issues a `SeqCst` fence before scanning the slots.
+
+use std::ptr;
+use std::sync::atomic::{fence, AtomicPtr, AtomicUsize, Ordering};
+
+/// Slot state of a slot no guard is using. Claimed slots hold `epoch << 1 | 1`.
+const FREE: usize = 0;
+
+/// One guard's published epoch.
+struct Slot {
+    /// `FREE`, or the pinned epoch shifted left with the low bit set.
+    state: AtomicUsize,
+
+    /// Next slot in the collector's list (set before the slot is published).
+    next: *mut Slot,
+}
+
+/// Global epoch and the slots of all pinned guards.
+pub(crate) struct Collector {
+    /// The global epoch, advanced by `try_advance`.
+    epoch: AtomicUsize,
+
+    /// Head of the append-only slot list.
+    slots: AtomicPtr<Slot>,
+}
+
+impl Collector {
+    /// Creates a collector at epoch 0 with no slots.
+    pub(crate) fn new() -> Self {
+        Self {
+            epoch: AtomicUsize::new(0),
+            slots: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    /// Pins the current epoch until the returned guard is dropped.
+    ///
+    /// Nodes reachable from shared pointers read while the guard is alive are
+    /// not freed before it is dropped.
+    pub(crate) fn pin(&self) -> Guard<'_> {
+        let pinned = (self.epoch.load(Ordering::Relaxed) << 1) | 1;
+
+        // Claim the first free slot, or publish a new one
+        let mut current = self.slots.load(Ordering::Acquire);
+        let slot = loop {
+            // SAFETY: Slots are only freed when the collector is dropped
+            match unsafe { current.as_ref() } {
+                Some(slot) => {
+                    if slot
+                        .state
+                        .compare_exchange(FREE, pinned, Ordering::SeqCst, Ordering::Relaxed)
+                        .is_ok()
+                    {
+                        break slot;
+                    }
+                    current = slot.next;
+                }
+                None => break self.push_slot(pinned),
+            }
+        };
+
+        // Order the published epoch before every shared read made under the guard
+        fence(Ordering::SeqCst);
+
+        Guard { slot }
+    }
+
+    /// Publishes a new slot already holding `state`.
+    fn push_slot(&self, state: usize) -> &Slot {
+        let slot = Box::into_raw(Box::new(Slot {
+            state: AtomicUsize::new(state),
+            next: ptr::null_mut(),
+        }));
+
+        let mut head = self.slots.load(Ordering::Relaxed);
+        loop {
+            // SAFETY: The slot isn't published yet, so nothing else accesses it
+            unsafe { (*slot).next = head };
+            match self
+                .slots
+                .compare_exchange_weak(head, slot, Ordering::AcqRel, Ordering::Relaxed)
+            {
+                // SAFETY: Slots are only freed when the collector is dropped
+                Ok(_) => return unsafe { &*slot },
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    /// Returns the epoch to stamp on a node that was just unlinked.
+    pub(crate) fn epoch(&self) -> usize {
+        // Order the unlinking before the epoch is read
+        fence(Ordering::SeqCst);
+        self.epoch.load(Ordering::Relaxed)
+    }
+
+    /// Advances the global epoch if every pinned guard has seen the current
+    /// one, and returns the global epoch.
+    pub(crate) fn try_advance(&self) -> usize {
+        let epoch = self.epoch.load(Ordering::Relaxed);
+        fence(Ordering::SeqCst);
+
+        let mut current = self.slots.load(Ordering::Acquire);
+        // SAFETY: Slots are only freed when the collector is dropped
+        while let Some(slot) = unsafe { current.as_ref() } {
+            let state = slot.state.load(Ordering::Relaxed);
+            if state != FREE && state >> 1 != epoch {
+                // A guard is still pinned in the previous epoch
+                return epoch;
+            }
+            current = slot.next;
+        }
+        fence(Ordering::Acquire);
+
+        let next = epoch.wrapping_add(1);
+        match self
+            .epoch
+            .compare_exchange(epoch, next, Ordering::Release, Ordering::Relaxed)
+        {
+            Ok(_) => next,
+            Err(current) => current,
+        }
+    }
+
+    /// Returns `true` if a node retired in `retired` can be freed at `epoch`.
+    pub(crate) fn expired(retired: usize, epoch: usize) -> bool {
+        epoch.wrapping_sub(retired) >= 2
+    }
+}
+
+impl Drop for Collector {
+    fn drop(&mut self) {
+        let mut current = *self.slots.get_mut();
+        while !current.is_null() {
+            // SAFETY: Guards borrow the collector, so none is left; every slot
+            // was created by push_slot from a Box
+            let slot = unsafe { Box::from_raw(current) };
+            current = slot.next;
+        }
+    }
+}
+
+/// A pinned epoch. Dropping the guard unpins it.
+pub(crate) struct Guard<'a> {
+    slot: &'a Slot,
+}
+
+impl Drop for Guard<'_> {
+    fn drop(&mut self) {
+        self.slot.state.store(FREE, Ordering::Release);
+    }
+}
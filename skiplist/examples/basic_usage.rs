@@ -1,6 +1,6 @@
-/// Example demonstrating basic skiplist operations
-///
-/// Run with: cargo run --example basic_usage
+//! Example demonstrating basic skiplist operations
+//!
+//! Run with: cargo run --example basic_usage
 
 use skiplist::{SkipList, SkipListEntry, SkipListNode};
 
@@ -1,6 +1,6 @@
-/// Example demonstrating real-world use cases with ordered data
-///
-/// Run with: cargo run --example ordered_data
+//! Example demonstrating real-world use cases with ordered data
+//!
+//! Run with: cargo run --example ordered_data
 
 use skiplist::{SkipList, SkipListEntry, SkipListNode};
 
@@ -1,8 +1,9 @@
-/// Example demonstrating range queries and iteration patterns
-///
-/// Run with: cargo run --example range_queries
+//! Example demonstrating range queries and iteration patterns
+//!
+//! Run with: cargo run --example range_queries
 
 use skiplist::{SkipList, SkipListEntry, SkipListNode};
+use std::ops::Bound::{Excluded, Unbounded};
 
 #[derive(Debug)]
 struct Product {
@@ -92,13 +93,9 @@ fn example_range_queries() {
     let min_price = 2000;
     let max_price = 5000;
 
-    let mut current = catalog.get(&min_price).or_else(|| catalog.successor(&min_price));
     let mut count = 0;
 
-    while let Some(product) = current {
-        if *product.key() > max_price {
-            break;
-        }
+    for product in catalog.range(min_price..=max_price) {
         println!(
             "  {} - {} ({})",
             product.price_display(),
@@ -106,7 +103,6 @@ fn example_range_queries() {
             product.category
         );
         count += 1;
-        current = catalog.successor(product.key());
     }
     println!("Found {} products\n", count);
 
@@ -303,55 +299,32 @@ fn example_batch_operations() {
     // Batch operation: Remove all items under $15
     println!("Removing items under $15...");
     let threshold = 1500;
-    let mut to_remove = Vec::new();
 
-    let mut current = catalog.first();
-    while let Some(product) = current {
-        if *product.key() >= threshold {
-            break;
-        }
-        println!("  Marking for removal: {} ({})", product.name, product.price_display());
-        to_remove.push(*product.key());
-        current = catalog.successor(product.key());
-    }
-
-    // Remove collected items
-    for key in to_remove {
-        catalog.remove(&key);
+    // The whole span is unlinked in one pass
+    let removed = catalog.remove_range(..threshold);
+    for product in &removed {
+        println!("  Removed: {} ({})", product.name, product.price_display());
     }
-    println!("Removed {} items", catalog.len());
+    println!("Removed {} items", removed.len());
 
     // Batch operation: Update prices (discount on expensive items)
     println!("\nApplying 10% discount on items over $50...");
     let discount_threshold = 5000;
-    let mut updates = Vec::new();
 
-    let mut current = catalog.successor(&discount_threshold);
-    while let Some(product) = current {
+    // The price is the key, so detach the affected span, reprice it and
+    // insert it again
+    for mut product in catalog.remove_range((Excluded(discount_threshold), Unbounded)) {
         let new_price = (product.price as f64 * 0.9) as u64;
         println!(
-            "  {} - {} -> {}",
+            "  {} - {} -> ${}.{:02}",
             product.name,
             product.price_display(),
-            format!("${}.{:02}", new_price / 100, new_price % 100)
+            new_price / 100,
+            new_price % 100
         );
 
-        // Collect updates (need to remove and re-insert with new key)
-        updates.push((
-            *product.key(),
-            product.name.clone(),
-            product.category.clone(),
-            product.in_stock,
-            new_price,
-        ));
-
-        current = catalog.successor(product.key());
-    }
-
-    // Apply updates
-    for (old_price, name, category, in_stock, new_price) in updates {
-        catalog.remove(&old_price);
-        catalog.insert(Product::new(new_price, &name, &category, in_stock)).unwrap();
+        product.price = new_price;
+        catalog.insert(product).unwrap();
     }
 
     println!("\nFinal count: {}", catalog.len());
@@ -61,12 +61,25 @@
 //!     assert_eq!(user_ref.name, "Alice");
 //! }
 //! ```
+//!
+//! ## Concurrent Access
+//!
+//! [`SkipList`] needs `&mut self` to modify it. For an ordered map shared by many
+//! threads, such as a memtable, use [`ConcurrentSkipList`] from the [`concurrent`]
+//! module: a lock-free, non-intrusive skip list with epoch-based reclamation.
 
 #![warn(missing_docs)]
 #![warn(clippy::all)]
 
+pub mod concurrent;
+
+pub use concurrent::ConcurrentSkipList;
+
 use smallvec::SmallVec;
+use std::borrow::Borrow;
+use std::iter::FusedIterator;
 use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds};
 use std::ptr::NonNull;
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -83,8 +96,9 @@ pub const DEFAULT_PROBABILITY: f64 = 0.5;
 
 /// Skiplist metadata that must be embedded in value structures.
 ///
-/// This structure contains the forward pointers at each level. Users must include
-/// this structure in their value types to use them in a skiplist.
+/// This structure contains the forward pointers at each level, along with the
+/// width of each link (used for positional access). Users must include this
+/// structure in their value types to use them in a skiplist.
 ///
 /// # Example
 ///
@@ -111,6 +125,12 @@ pub struct SkipListNode {
     /// Stores up to 4 pointers inline (covers 93.75% of nodes with p=0.5),
     /// automatically spills to heap for taller nodes.
     forward: SmallVec<[Option<NonNull<u8>>; 4]>,
+
+    /// Width of each forward link.
+    /// span[i] is the number of level-0 steps that forward[i] skips (a link to
+    /// the end of the list spans the remaining entries), so summing the spans
+    /// along a search path gives an entry's position.
+    span: SmallVec<[usize; 4]>,
 }
 
 impl SkipListNode {
@@ -121,6 +141,7 @@ impl SkipListNode {
     pub fn new() -> Self {
         Self {
             forward: SmallVec::new(),
+            span: SmallVec::new(),
         }
     }
 
@@ -132,7 +153,9 @@ impl SkipListNode {
     pub fn with_level(level: usize) -> Self {
         let mut forward = SmallVec::new();
         forward.resize(level + 1, None);
-        Self { forward }
+        let mut span = SmallVec::new();
+        span.resize(level + 1, 0);
+        Self { forward, span }
     }
 }
 
@@ -187,6 +210,111 @@ pub trait SkipListEntry {
     fn skiplist_node_mut(&mut self) -> &mut SkipListNode;
 }
 
+/// Chooses the level of each node inserted into a skiplist.
+///
+/// A node at level `n` is linked into levels `0..=n`. The skiplist uses
+/// [`XorShiftLevels`] unless another generator is supplied through
+/// [`SkipListBuilder::level_generator`].
+///
+/// # Example
+///
+/// ```rust
+/// use skiplist::LevelGenerator;
+///
+/// /// Promotes every fourth node by one level, for predictable layouts in tests.
+/// struct EveryFourth(usize);
+///
+/// impl LevelGenerator for EveryFourth {
+///     fn random_level(&mut self, max_level: usize) -> usize {
+///         self.0 += 1;
+///         if self.0 % 4 == 0 { max_level.min(1) } else { 0 }
+///     }
+/// }
+/// ```
+pub trait LevelGenerator {
+    /// Returns the level for a new node.
+    ///
+    /// Levels above `max_level` are clamped to `max_level` by the skiplist.
+    fn random_level(&mut self, max_level: usize) -> usize;
+}
+
+/// The default level generator: a xorshift64 PRNG promoting each node one
+/// more level with a fixed probability.
+///
+/// Levels follow a geometric distribution: a node reaches level `k` with
+/// probability `p^k`.
+#[derive(Debug, Clone)]
+pub struct XorShiftLevels {
+    /// Xorshift64 state (never zero).
+    state: u64,
+
+    /// Probability of promoting a node to the next level.
+    probability: f64,
+}
+
+impl XorShiftLevels {
+    /// Creates a generator with a fixed seed, so the same sequence of inserts
+    /// always produces the same layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Initial PRNG state (0 is replaced by 1, since xorshift would stay at 0)
+    /// * `probability` - Chance of promoting a node to the next level
+    ///
+    /// # Panics
+    ///
+    /// Panics if `probability` is not within `0.0..=1.0`.
+    pub fn new(seed: u64, probability: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&probability),
+            "level promotion probability must be within 0.0..=1.0, got {}",
+            probability
+        );
+        Self {
+            state: if seed == 0 { 1 } else { seed }, // Avoid zero state
+            probability,
+        }
+    }
+
+    /// Creates a generator seeded from the current time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `probability` is not within `0.0..=1.0`.
+    pub fn from_time(probability: f64) -> Self {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos() as u64;
+        Self::new(seed, probability)
+    }
+
+    /// Advances the xorshift64 state and returns a uniform value in `0.0..1.0`.
+    fn next_f64(&mut self) -> f64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+
+        // The top 53 bits fill an f64 mantissa exactly
+        (x >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+impl LevelGenerator for XorShiftLevels {
+    fn random_level(&mut self, max_level: usize) -> usize {
+        let mut level = 0;
+
+        // Flip a biased coin until it comes up tails or the max level is hit
+        while level < max_level && self.next_f64() < self.probability {
+            level += 1;
+        }
+
+        level
+    }
+}
+
 /// An intrusive skiplist implementation.
 ///
 /// This skiplist maintains elements in sorted order by key, with O(log n) average
@@ -234,8 +362,11 @@ where
     /// Maximum allowed level.
     max_level: usize,
 
-    /// RNG state for level generation (xorshift64)
-    rng_state: u64,
+    /// Chooses the level of each inserted node.
+    levels: Box<dyn LevelGenerator>,
+
+    /// Whether several entries may share a key (multimap mode).
+    allow_duplicates: bool,
 
     /// Phantom data to hold type parameters.
     _marker: PhantomData<(K, E)>,
@@ -292,18 +423,42 @@ where
     /// let skiplist: SkipList<i32, Item> = SkipList::with_max_level(8);
     /// ```
     pub fn with_max_level(max_level: usize) -> Self {
-        // Initialize RNG with current time
-        let seed = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_nanos() as u64;
+        Self::builder().max_level(max_level).build()
+    }
 
-        Self {
-            head: SkipListNode::with_level(max_level),
-            len: 0,
-            level: 0,
-            max_level,
-            rng_state: if seed == 0 { 1 } else { seed }, // Avoid zero state
+    /// Returns a builder for configuring a new skiplist.
+    ///
+    /// The builder sets the maximum level, the promotion probability and the
+    /// seed of the built-in level generator, or replaces the generator entirely.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use skiplist::SkipList;
+    /// # use skiplist::{SkipListEntry, SkipListNode};
+    /// # struct Item { id: i32, skiplist_meta: SkipListNode }
+    /// # impl SkipListEntry for Item {
+    /// #     type Key = i32;
+    /// #     fn key(&self) -> &i32 { &self.id }
+    /// #     fn skiplist_node(&self) -> &SkipListNode { &self.skiplist_meta }
+    /// #     fn skiplist_node_mut(&mut self) -> &mut SkipListNode { &mut self.skiplist_meta }
+    /// # }
+    ///
+    /// // Same seed, same layout: reproducible tests and benchmarks
+    /// let skiplist: SkipList<i32, Item> = SkipList::builder()
+    ///     .seed(42)
+    ///     .max_level(12)
+    ///     .probability(0.25)
+    ///     .build();
+    /// assert!(skiplist.is_empty());
+    /// ```
+    pub fn builder() -> SkipListBuilder<K, E> {
+        SkipListBuilder {
+            max_level: MAX_LEVEL,
+            probability: DEFAULT_PROBABILITY,
+            seed: None,
+            levels: None,
+            allow_duplicates: false,
             _marker: PhantomData,
         }
     }
@@ -311,7 +466,9 @@ where
     /// Inserts an entry into the skiplist.
     ///
     /// The entry is inserted in sorted order based on its key. If an entry with the same
-    /// key already exists, the insertion fails and the entry is returned in the `Err` variant.
+    /// key already exists, the insertion fails and the entry is returned in the `Err` variant,
+    /// unless the skiplist allows duplicates (see [`SkipListBuilder::allow_duplicates`]); then
+    /// the entry is placed after every existing entry with its key.
     ///
     /// # Arguments
     ///
@@ -320,7 +477,7 @@ where
     /// # Returns
     ///
     /// * `Ok(())` if insertion succeeded
-    /// * `Err(entry)` if an entry with the same key already exists
+    /// * `Err(entry)` if an entry with the same key already exists and duplicates aren't allowed
     ///
     /// # Example
     ///
@@ -338,14 +495,142 @@ where
     /// let item = Box::new(Item { id: 42, skiplist_meta: SkipListNode::new() });
     /// assert!(skiplist.insert(item).is_ok());
     /// ```
-    pub fn insert(&mut self, mut entry: Box<E>) -> Result<(), Box<E>> {
-        let key = entry.key();
+    pub fn insert(&mut self, entry: Box<E>) -> Result<(), Box<E>> {
+        let mut slot = self.find_slot(entry.key(), self.allow_duplicates);
 
-        // Track update pointers at each level (where to insert)
-        let mut update: Vec<*const SkipListNode> = vec![std::ptr::null(); self.max_level + 1];
+        // Equal keys are only passed when duplicates are allowed, so a match
+        // right after the slot means the key already exists
+        if !self.allow_duplicates && self.entry_after(&slot, entry.key()).is_some() {
+            return Err(entry);
+        }
 
-        // Find insertion position
-        let mut current = &self.head as *const SkipListNode;
+        // SAFETY: The slot was just found and the skiplist hasn't changed since
+        unsafe { self.link(&mut slot, entry) };
+        Ok(())
+    }
+
+    /// Inserts an entry, replacing any existing entry with the same key.
+    ///
+    /// Unlike `remove` followed by `insert`, this searches only once. An existing
+    /// entry is swapped out in place: the new entry takes over its tower, so the
+    /// shape of the skiplist doesn't change. When duplicates are allowed, the
+    /// earliest inserted entry with the key is the one replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `entry` - The entry to insert (must be boxed)
+    ///
+    /// # Returns
+    ///
+    /// The replaced entry, or `None` if no entry with the key existed and the
+    /// new entry was inserted
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use skiplist::{SkipList, SkipListEntry, SkipListNode};
+    /// # struct Item { id: i32, value: String, skiplist_meta: SkipListNode }
+    /// # impl SkipListEntry for Item {
+    /// #     type Key = i32;
+    /// #     fn key(&self) -> &i32 { &self.id }
+    /// #     fn skiplist_node(&self) -> &SkipListNode { &self.skiplist_meta }
+    /// #     fn skiplist_node_mut(&mut self) -> &mut SkipListNode { &mut self.skiplist_meta }
+    /// # }
+    /// # fn item(id: i32, value: &str) -> Box<Item> {
+    /// #     Box::new(Item { id, value: value.to_string(), skiplist_meta: SkipListNode::new() })
+    /// # }
+    /// let mut skiplist: SkipList<i32, Item> = SkipList::new();
+    ///
+    /// assert!(skiplist.replace(item(42, "old")).is_none());
+    /// let old = skiplist.replace(item(42, "new")).unwrap();
+    /// assert_eq!(old.value, "old");
+    /// assert_eq!(skiplist.get(&42).unwrap().value, "new");
+    /// assert_eq!(skiplist.len(), 1);
+    /// ```
+    pub fn replace(&mut self, entry: Box<E>) -> Option<Box<E>> {
+        let mut slot = self.find_slot(entry.key(), false);
+
+        // SAFETY: The slot was just found and the skiplist hasn't changed since
+        unsafe {
+            match self.entry_after(&slot, entry.key()) {
+                Some(old_ptr) => Some(Self::swap(&slot, old_ptr, entry)),
+                None => {
+                    self.link(&mut slot, entry);
+                    None
+                }
+            }
+        }
+    }
+
+    /// Gets the entry for `key` for in-place manipulation.
+    ///
+    /// The search runs once; inserting through a [`VacantEntry`] reuses the
+    /// position it found instead of searching again. When duplicates are allowed,
+    /// an [`OccupiedEntry`] refers to the earliest inserted entry with the key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to look up
+    ///
+    /// # Returns
+    ///
+    /// [`Entry::Occupied`] if an entry with the key exists, [`Entry::Vacant`] otherwise
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use skiplist::{SkipList, SkipListEntry, SkipListNode};
+    /// # struct Item { id: i32, hits: u32, skiplist_meta: SkipListNode }
+    /// # impl SkipListEntry for Item {
+    /// #     type Key = i32;
+    /// #     fn key(&self) -> &i32 { &self.id }
+    /// #     fn skiplist_node(&self) -> &SkipListNode { &self.skiplist_meta }
+    /// #     fn skiplist_node_mut(&mut self) -> &mut SkipListNode { &mut self.skiplist_meta }
+    /// # }
+    /// let mut skiplist: SkipList<i32, Item> = SkipList::new();
+    ///
+    /// for id in [3, 1, 3, 3] {
+    ///     skiplist
+    ///         .entry(id)
+    ///         .or_insert_with(|| Box::new(Item { id, hits: 0, skiplist_meta: SkipListNode::new() }))
+    ///         .hits += 1;
+    /// }
+    /// assert_eq!(skiplist.get(&3).unwrap().hits, 3);
+    /// assert_eq!(skiplist.get(&1).unwrap().hits, 1);
+    /// ```
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, E> {
+        let slot = self.find_slot(&key, false);
+        match self.entry_after(&slot, &key) {
+            Some(entry) => Entry::Occupied(OccupiedEntry {
+                list: self,
+                slot,
+                entry,
+            }),
+            None => Entry::Vacant(VacantEntry {
+                list: self,
+                slot,
+                key,
+            }),
+        }
+    }
+
+    /// Finds where an entry with `key` belongs, recording the last node before
+    /// that position at every level.
+    ///
+    /// Entries with an equal key are passed over when `past_equal` is set, so
+    /// the slot lies after them; otherwise it lies before them.
+    fn find_slot(&mut self, key: &K, past_equal: bool) -> Slot {
+        let head = &mut self.head as *mut SkipListNode;
+
+        // Levels above the current height are filled in by link() if the
+        // skiplist grows, and always start from the head
+        let mut update: Vec<*mut SkipListNode> = vec![head; self.max_level + 1];
+
+        // Position of each update node (the head is at 0, entries at 1..=len)
+        let mut rank: Vec<usize> = vec![0; self.max_level + 1];
+        let mut traversed = 0;
+
+        let mut current = head;
 
         for level in (0..=self.level).rev() {
             unsafe {
@@ -353,39 +638,55 @@ where
                     let current_node = &*current;
                     match current_node.forward[level] {
                         Some(next_ptr) => {
-                            let next_entry: &E = next_ptr.cast::<E>().as_ref();
+                            let next_entry: &mut E = next_ptr.cast::<E>().as_mut();
 
                             match next_entry.key().cmp(key) {
-                                std::cmp::Ordering::Less => {
-                                    current = next_entry.skiplist_node() as *const SkipListNode;
-                                }
-                                std::cmp::Ordering::Equal => {
-                                    // Key already exists, return error
-                                    return Err(entry);
-                                }
-                                std::cmp::Ordering::Greater => {
+                                std::cmp::Ordering::Less => {}
+                                std::cmp::Ordering::Equal if past_equal => {}
+                                std::cmp::Ordering::Equal | std::cmp::Ordering::Greater => {
                                     break;
                                 }
                             }
+                            traversed += current_node.span[level];
+                            current = next_entry.skiplist_node_mut() as *mut SkipListNode;
                         }
                         None => break,
                     }
                 }
             }
             update[level] = current;
+            rank[level] = traversed;
+        }
+
+        Slot { update, rank }
+    }
+
+    /// Returns the entry directly after `slot` if it has the given key.
+    fn entry_after(&self, slot: &Slot, key: &K) -> Option<NonNull<u8>> {
+        // SAFETY: Slots are only used while the skiplist is unchanged, so the
+        // update node and the entry after it are live
+        unsafe {
+            (&(*slot.update[0]).forward)[0].filter(|&ptr| ptr.cast::<E>().as_ref().key() == key)
         }
+    }
+
+    /// Links `entry` into the skiplist at `slot` and returns its pointer.
+    ///
+    /// # Safety
+    ///
+    /// `slot` must come from [`find_slot`](Self::find_slot) on this skiplist with
+    /// no changes in between, and `entry` must belong at that position.
+    unsafe fn link(&mut self, slot: &mut Slot, mut entry: Box<E>) -> NonNull<u8> {
+        let Slot { update, rank } = slot;
 
         // Generate random level for new node
-        let new_level = self.random_level();
+        let new_level = self.levels.random_level(self.max_level).min(self.max_level);
 
         // Update skiplist level if necessary
         if new_level > self.level {
-            for item in update
-                .iter_mut()
-                .skip(self.level + 1)
-                .take(new_level - self.level)
-            {
-                *item = &self.head as *const SkipListNode;
+            // The head's new links run to the end, past every entry
+            for span in &mut self.head.span[self.level + 1..=new_level] {
+                *span = self.len;
             }
             self.level = new_level;
         }
@@ -393,6 +694,7 @@ where
         // Initialize the entry's skiplist node with proper level
         let node = entry.skiplist_node_mut();
         node.forward.resize(new_level + 1, None);
+        node.span.resize(new_level + 1, 0);
 
         // Convert Box to raw pointer and store as type-erased pointer
         let entry_ptr = Box::into_raw(entry);
@@ -400,47 +702,71 @@ where
 
         // Update forward pointers at each level
         for (level, &update_node_ptr) in update.iter().enumerate().take(new_level + 1) {
-            unsafe {
-                let update_node = &*update_node_ptr;
+            let update_node = &mut *update_node_ptr;
 
-                // New node points to what update[level] pointed to
-                (*entry_ptr).skiplist_node_mut().forward[level] = update_node.forward[level];
+            // New node points to what update[level] pointed to, taking over
+            // the part of the link beyond the insertion point
+            let new_node = (*entry_ptr).skiplist_node_mut();
+            new_node.forward[level] = update_node.forward[level];
+            new_node.span[level] = update_node.span[level] - (rank[0] - rank[level]);
 
-                // update[level] now points to new node
-                let update_node_mut = &mut *(update_node_ptr as *mut SkipListNode);
-                update_node_mut.forward[level] = Some(erased_ptr);
-            }
+            // update[level] now points to new node
+            update_node.forward[level] = Some(erased_ptr);
+            update_node.span[level] = rank[0] - rank[level] + 1;
+        }
+
+        // Links above the new node now skip one more entry
+        for (level, &update_node_ptr) in update
+            .iter()
+            .enumerate()
+            .take(self.level + 1)
+            .skip(new_level + 1)
+        {
+            (&mut (*update_node_ptr).span)[level] += 1;
         }
 
         self.len += 1;
-        Ok(())
+        erased_ptr
     }
 
-    /// Generate a random level for a new node using xorshift64
-    fn random_level(&mut self) -> usize {
-        // Xorshift64 - fast PRNG
-        let mut x = self.rng_state;
-        x ^= x << 13;
-        x ^= x >> 7;
-        x ^= x << 17;
-        self.rng_state = x;
+    /// Puts `entry` in place of `old_ptr`, handing it the old entry's tower,
+    /// and returns the old entry.
+    ///
+    /// # Safety
+    ///
+    /// `old_ptr` must be the entry directly after `slot`, which must come from
+    /// [`find_slot`](Self::find_slot) with no changes in between, and `entry`
+    /// must have the same key.
+    unsafe fn swap(slot: &Slot, old_ptr: NonNull<u8>, mut entry: Box<E>) -> Box<E> {
+        let mut old = Box::from_raw(old_ptr.cast::<E>().as_ptr());
 
-        let mut level = 0;
-        let mut rng = x;
+        // The new entry takes over the old tower along with its links and widths
+        let new_node = entry.skiplist_node_mut();
+        let old_node = old.skiplist_node_mut();
+        std::mem::swap(&mut new_node.forward, &mut old_node.forward);
+        std::mem::swap(&mut new_node.span, &mut old_node.span);
+        let height = new_node.forward.len();
 
-        // Flip coin until we get tails or hit max level
-        while level < self.max_level && (rng & 1) == 0 {
-            level += 1;
-            rng >>= 1;
+        let new_ptr = NonNull::new(Box::into_raw(entry) as *mut u8).unwrap();
+
+        // Every node that linked to the old entry directly precedes it
+        for (level, &update_node_ptr) in slot.update.iter().enumerate().take(height) {
+            (&mut (*update_node_ptr).forward)[level] = Some(new_ptr);
         }
 
-        level
+        // The old entry leaves with whatever tower the new one brought
+        let old_node = old.skiplist_node_mut();
+        old_node.forward.clear();
+        old_node.span.clear();
+        old
     }
 
     /// Removes an entry from the skiplist by key and returns it.
     ///
     /// If an entry with the specified key exists, it is removed from the skiplist
-    /// and returned. Otherwise, `None` is returned.
+    /// and returned. Otherwise, `None` is returned. When duplicates are allowed,
+    /// the earliest inserted entry with the key is removed (see [`remove_one`](Self::remove_one)).
+    /// As with [`get`](Self::get), `key` may be a borrowed form of the key type.
     ///
     /// # Arguments
     ///
@@ -467,7 +793,11 @@ where
     ///     println!("Removed: {}", item.value);
     /// }
     /// ```
-    pub fn remove(&mut self, key: &K) -> Option<Box<E>> {
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<Box<E>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         // Track update pointers at each level (nodes pointing to the target)
         let mut update: Vec<*mut SkipListNode> = vec![std::ptr::null_mut(); self.max_level + 1];
 
@@ -482,7 +812,7 @@ where
                         Some(next_ptr) => {
                             let next_entry: &E = next_ptr.cast::<E>().as_ref();
 
-                            match next_entry.key().cmp(key) {
+                            match next_entry.key().borrow().cmp(key) {
                                 std::cmp::Ordering::Less => {
                                     current = next_entry.skiplist_node() as *const SkipListNode;
                                 }
@@ -504,37 +834,47 @@ where
             if let Some(target_ptr) = current_node.forward[0] {
                 let target_entry: &E = target_ptr.cast::<E>().as_ref();
 
-                if target_entry.key() == key {
+                if target_entry.key().borrow() == key {
                     // Found the node to remove
-                    let target_node = target_entry.skiplist_node();
-
-                    // Update forward pointers at each level
-                    for (level, &update_node_ptr) in update.iter().enumerate().take(self.level + 1)
-                    {
-                        let update_node = &mut *update_node_ptr;
-                        if let Some(ptr) = update_node.forward[level] {
-                            if ptr == target_ptr {
-                                // Skip over the node being removed
-                                update_node.forward[level] = target_node.forward[level];
-                            }
-                        }
-                    }
+                    return Some(self.unlink(&update, target_ptr));
+                }
+            }
+        }
 
-                    // Update list level if we removed from top levels
-                    while self.level > 0 && self.head.forward[self.level].is_none() {
-                        self.level -= 1;
-                    }
+        None
+    }
 
-                    self.len -= 1;
+    /// Unlinks `target` given the last node before it at every level.
+    ///
+    /// # Safety
+    ///
+    /// `update[level]` must be the node directly before `target` at each level
+    /// up to `self.level`, and `target` must be an entry of this skiplist.
+    unsafe fn unlink(&mut self, update: &[*mut SkipListNode], target_ptr: NonNull<u8>) -> Box<E> {
+        let target_node = node_of::<E>(target_ptr);
 
-                    // Convert raw pointer back to Box and return
-                    let removed_ptr = target_ptr.cast::<E>().as_ptr();
-                    return Some(Box::from_raw(removed_ptr));
-                }
+        // Update forward pointers at each level
+        for (level, &update_node_ptr) in update.iter().enumerate().take(self.level + 1) {
+            let update_node = &mut *update_node_ptr;
+            if update_node.forward[level] == Some(target_ptr) {
+                // Skip over the node being removed, absorbing its link
+                update_node.forward[level] = target_node.forward[level];
+                update_node.span[level] += target_node.span[level];
             }
+            // Every link at or above the target now skips one entry fewer
+            update_node.span[level] -= 1;
         }
 
-        None
+        // Update list level if we removed from top levels
+        while self.level > 0 && self.head.forward[self.level].is_none() {
+            self.level -= 1;
+        }
+
+        self.len -= 1;
+
+        // Convert raw pointer back to Box and return
+        let removed_ptr = target_ptr.cast::<E>().as_ptr();
+        Box::from_raw(removed_ptr)
     }
 
     /// Removes an entry from the skiplist by key without returning it.
@@ -566,13 +906,21 @@ where
     ///     println!("Entry removed");
     /// }
     /// ```
-    pub fn remove_by_key(&mut self, key: &K) -> bool {
+    pub fn remove_by_key<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         // Just use remove() and drop the returned value
         self.remove(key).is_some()
     }
 
     /// Looks up an entry by key and returns a reference to it.
     ///
+    /// The key may be any borrowed form of the skiplist's key type, such as
+    /// `&str` for `String` keys, as long as both order the same way. This holds
+    /// for every key lookup (`remove`, `successor`, `range`, `rank`, ...).
+    ///
     /// # Arguments
     ///
     /// * `key` - The key to search for
@@ -598,7 +946,11 @@ where
     ///     println!("Found: {}", item.value);
     /// }
     /// ```
-    pub fn get(&self, key: &K) -> Option<&E> {
+    pub fn get<Q>(&self, key: &Q) -> Option<&E>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         // Start from the head (sentinel node)
         let mut current = &self.head;
 
@@ -610,25 +962,32 @@ where
                 // SAFETY: Pointer is valid (stored by insert from Box<E>)
                 let next_entry: &E = unsafe { next_ptr.cast::<E>().as_ref() };
 
-                match next_entry.key().cmp(key) {
+                match next_entry.key().borrow().cmp(key) {
                     std::cmp::Ordering::Less => {
                         // next < key: move forward at this level
                         current = next_entry.skiplist_node();
                     }
-                    std::cmp::Ordering::Equal => {
+                    std::cmp::Ordering::Equal if !self.allow_duplicates => {
                         // Found exact match!
                         return Some(next_entry);
                     }
-                    std::cmp::Ordering::Greater => {
-                        // next > key: drop down to next level
+                    std::cmp::Ordering::Equal | std::cmp::Ordering::Greater => {
+                        // next > key: drop down to next level. With duplicates,
+                        // an earlier entry with the same key may still follow.
                         break;
                     }
                 }
             }
         }
 
-        // Not found after checking all levels
-        None
+        // With duplicates the first match is the next entry at level 0;
+        // otherwise the key wasn't found on any level
+        current.forward[0]
+            .map(|ptr| {
+                // SAFETY: Pointer is valid (stored by insert from Box<E>)
+                unsafe { ptr.cast::<E>().as_ref() }
+            })
+            .filter(|entry| entry.key().borrow() == key)
     }
 
     /// Looks up an entry by key and returns a mutable reference to it.
@@ -658,7 +1017,11 @@ where
     ///     item.value = "Updated".to_string();
     /// }
     /// ```
-    pub fn get_mut(&mut self, key: &K) -> Option<&mut E> {
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut E>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         // Start from the head (sentinel node)
         let mut current = &self.head;
 
@@ -670,27 +1033,101 @@ where
                 // SAFETY: Pointer is valid (stored by insert from Box<E>)
                 let next_entry: &E = unsafe { next_ptr.cast::<E>().as_ref() };
 
-                match next_entry.key().cmp(key) {
+                match next_entry.key().borrow().cmp(key) {
                     std::cmp::Ordering::Less => {
                         // next < key: move forward at this level
                         current = next_entry.skiplist_node();
                     }
-                    std::cmp::Ordering::Equal => {
+                    std::cmp::Ordering::Equal if !self.allow_duplicates => {
                         // Found exact match! Return mutable reference
                         // SAFETY: We have &mut self, so exclusive access is guaranteed
                         let mut mut_ptr = next_ptr.cast::<E>();
                         return Some(unsafe { mut_ptr.as_mut() });
                     }
-                    std::cmp::Ordering::Greater => {
-                        // next > key: drop down to next level
+                    std::cmp::Ordering::Equal | std::cmp::Ordering::Greater => {
+                        // next > key: drop down to next level. With duplicates,
+                        // an earlier entry with the same key may still follow.
                         break;
                     }
                 }
             }
         }
 
-        // Not found after checking all levels
-        None
+        // With duplicates the first match is the next entry at level 0;
+        // otherwise the key wasn't found on any level
+        let mut mut_ptr = current.forward[0]?.cast::<E>();
+        // SAFETY: We have &mut self, so exclusive access is guaranteed
+        let entry = unsafe { mut_ptr.as_mut() };
+        (entry.key().borrow() == key).then_some(entry)
+    }
+
+    /// Searches with a comparator instead of a key.
+    ///
+    /// `compare` is called with entry keys and returns how each one orders
+    /// against the target, like [`slice::binary_search_by`]: `Less` for keys
+    /// before it, `Equal` for matches and `Greater` for keys after it. The
+    /// comparator must agree with the key ordering, so all `Less` keys come
+    /// first, which makes it suitable for projections such as one field of a
+    /// composite key or a case-insensitive name.
+    ///
+    /// # Arguments
+    ///
+    /// * `compare` - Orders a key against the target
+    ///
+    /// # Returns
+    ///
+    /// The first entry whose key compares `Equal`, or `None` if there is none
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use skiplist::{SkipList, SkipListEntry, SkipListNode};
+    /// # struct Person { name: (String, u32), skiplist_meta: SkipListNode }
+    /// # impl SkipListEntry for Person {
+    /// #     type Key = (String, u32);
+    /// #     fn key(&self) -> &(String, u32) { &self.name }
+    /// #     fn skiplist_node(&self) -> &SkipListNode { &self.skiplist_meta }
+    /// #     fn skiplist_node_mut(&mut self) -> &mut SkipListNode { &mut self.skiplist_meta }
+    /// # }
+    /// // Keyed by (surname, id)
+    /// let mut people: SkipList<(String, u32), Person> = SkipList::new();
+    /// for (surname, id) in [("smith", 7), ("jones", 3), ("smith", 2)] {
+    ///     let name = (surname.to_string(), id);
+    ///     let person = Box::new(Person { name, skiplist_meta: SkipListNode::new() });
+    ///     assert!(people.insert(person).is_ok());
+    /// }
+    ///
+    /// // Find the first smith without knowing any id
+    /// let smith = people.search_by(|(surname, _)| surname.as_str().cmp("smith"));
+    /// assert_eq!(smith.map(|person| person.name.1), Some(2));
+    /// assert!(people.search_by(|(surname, _)| surname.as_str().cmp("brown")).is_none());
+    /// ```
+    pub fn search_by<F>(&self, mut compare: F) -> Option<&E>
+    where
+        F: FnMut(&K) -> std::cmp::Ordering,
+    {
+        // Start from the head (sentinel node)
+        let mut current = &self.head;
+
+        // Move forward at each level while next.key orders before the target,
+        // so the search ends just before the first match
+        for level in (0..=self.level).rev() {
+            while let Some(next_ptr) = current.forward[level] {
+                // SAFETY: Pointer is valid (stored by insert from Box<E>)
+                let next_entry: &E = unsafe { next_ptr.cast::<E>().as_ref() };
+                if compare(next_entry.key()) != std::cmp::Ordering::Less {
+                    break;
+                }
+                current = next_entry.skiplist_node();
+            }
+        }
+
+        current.forward[0]
+            .map(|ptr| {
+                // SAFETY: Pointer is valid (stored by insert from Box<E>)
+                unsafe { ptr.cast::<E>().as_ref() }
+            })
+            .filter(|entry| compare(entry.key()) == std::cmp::Ordering::Equal)
     }
 
     /// Returns a reference to the first (smallest) entry in the skiplist.
@@ -760,7 +1197,11 @@ where
     ///     println!("Next key after 42: {}", next.key());
     /// }
     /// ```
-    pub fn successor(&self, key: &K) -> Option<&E> {
+    pub fn successor<Q>(&self, key: &Q) -> Option<&E>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         // Start from the head (sentinel node)
         let mut current = &self.head;
 
@@ -772,7 +1213,7 @@ where
                 // SAFETY: Pointer is valid (stored by insert from Box<E>)
                 let next_entry: &E = unsafe { next_ptr.cast::<E>().as_ref() };
 
-                match next_entry.key().cmp(key) {
+                match next_entry.key().borrow().cmp(key) {
                     std::cmp::Ordering::Less | std::cmp::Ordering::Equal => {
                         // next <= key: move forward to find larger element
                         current = next_entry.skiplist_node();
@@ -793,13 +1234,19 @@ where
         })
     }
 
-    /// Returns `true` if the skiplist contains no elements.
+    /// Returns a reference to the last entry (with the largest key).
+    ///
+    /// Runs along the express lanes, dropping a level only when the current
+    /// one ends, so it takes O(log n) average time rather than a level-0 walk.
+    ///
+    /// # Returns
+    ///
+    /// A reference to the last entry, or `None` if the skiplist is empty
     ///
     /// # Example
     ///
     /// ```rust
-    /// # use skiplist::SkipList;
-    /// # use skiplist::{SkipListEntry, SkipListNode};
+    /// # use skiplist::{SkipList, SkipListEntry, SkipListNode};
     /// # struct Item { id: i32, skiplist_meta: SkipListNode }
     /// # impl SkipListEntry for Item {
     /// #     type Key = i32;
@@ -807,19 +1254,445 @@ where
     /// #     fn skiplist_node(&self) -> &SkipListNode { &self.skiplist_meta }
     /// #     fn skiplist_node_mut(&mut self) -> &mut SkipListNode { &mut self.skiplist_meta }
     /// # }
-    /// let skiplist: SkipList<i32, Item> = SkipList::new();
-    /// assert!(skiplist.is_empty());
+    /// let mut skiplist: SkipList<i32, Item> = SkipList::new();
+    /// for id in [20, 30, 10] {
+    ///     let item = Box::new(Item { id, skiplist_meta: SkipListNode::new() });
+    ///     assert!(skiplist.insert(item).is_ok());
+    /// }
+    ///
+    /// assert_eq!(skiplist.last().map(|item| item.id), Some(30));
     /// ```
-    pub fn is_empty(&self) -> bool {
-        self.len == 0
+    pub fn last(&self) -> Option<&E> {
+        self.last_where(|_| true)
     }
 
-    /// Returns the number of elements in the skiplist.
+    /// Finds the previous entry before the given key (predecessor).
+    ///
+    /// Returns the largest entry whose key is strictly less than the given key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to find the predecessor of
+    ///
+    /// # Returns
+    ///
+    /// A reference to the predecessor entry, or `None` if no such entry exists
     ///
     /// # Example
     ///
     /// ```rust
-    /// # use skiplist::SkipList;
+    /// # use skiplist::{SkipList, SkipListEntry, SkipListNode};
+    /// # struct Item { id: i32, skiplist_meta: SkipListNode }
+    /// # impl SkipListEntry for Item {
+    /// #     type Key = i32;
+    /// #     fn key(&self) -> &i32 { &self.id }
+    /// #     fn skiplist_node(&self) -> &SkipListNode { &self.skiplist_meta }
+    /// #     fn skiplist_node_mut(&mut self) -> &mut SkipListNode { &mut self.skiplist_meta }
+    /// # }
+    /// let mut skiplist: SkipList<i32, Item> = SkipList::new();
+    /// for id in [10, 20, 30] {
+    ///     let item = Box::new(Item { id, skiplist_meta: SkipListNode::new() });
+    ///     assert!(skiplist.insert(item).is_ok());
+    /// }
+    ///
+    /// assert_eq!(skiplist.predecessor(&20).map(|item| item.id), Some(10));
+    /// assert_eq!(skiplist.predecessor(&25).map(|item| item.id), Some(20));
+    /// assert!(skiplist.predecessor(&10).is_none());
+    /// ```
+    pub fn predecessor<Q>(&self, key: &Q) -> Option<&E>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.last_where(|next| next.borrow() < key)
+    }
+
+    /// Finds the entry with the greatest key less than or equal to the given key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The upper bound to search for
+    ///
+    /// # Returns
+    ///
+    /// The entry with `key` itself if present, otherwise its predecessor, or
+    /// `None` if every key is greater
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use skiplist::{SkipList, SkipListEntry, SkipListNode};
+    /// # struct Item { id: i32, skiplist_meta: SkipListNode }
+    /// # impl SkipListEntry for Item {
+    /// #     type Key = i32;
+    /// #     fn key(&self) -> &i32 { &self.id }
+    /// #     fn skiplist_node(&self) -> &SkipListNode { &self.skiplist_meta }
+    /// #     fn skiplist_node_mut(&mut self) -> &mut SkipListNode { &mut self.skiplist_meta }
+    /// # }
+    /// let mut skiplist: SkipList<i32, Item> = SkipList::new();
+    /// for id in [10, 20, 30] {
+    ///     let item = Box::new(Item { id, skiplist_meta: SkipListNode::new() });
+    ///     assert!(skiplist.insert(item).is_ok());
+    /// }
+    ///
+    /// assert_eq!(skiplist.floor(&20).map(|item| item.id), Some(20));
+    /// assert_eq!(skiplist.floor(&25).map(|item| item.id), Some(20));
+    /// assert!(skiplist.floor(&5).is_none());
+    /// ```
+    pub fn floor<Q>(&self, key: &Q) -> Option<&E>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.last_where(|next| next.borrow() <= key)
+    }
+
+    /// Finds the entry with the least key greater than or equal to the given key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The lower bound to search for
+    ///
+    /// # Returns
+    ///
+    /// The entry with `key` itself if present, otherwise its successor, or
+    /// `None` if every key is smaller
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use skiplist::{SkipList, SkipListEntry, SkipListNode};
+    /// # struct Item { id: i32, skiplist_meta: SkipListNode }
+    /// # impl SkipListEntry for Item {
+    /// #     type Key = i32;
+    /// #     fn key(&self) -> &i32 { &self.id }
+    /// #     fn skiplist_node(&self) -> &SkipListNode { &self.skiplist_meta }
+    /// #     fn skiplist_node_mut(&mut self) -> &mut SkipListNode { &mut self.skiplist_meta }
+    /// # }
+    /// let mut skiplist: SkipList<i32, Item> = SkipList::new();
+    /// for id in [10, 20, 30] {
+    ///     let item = Box::new(Item { id, skiplist_meta: SkipListNode::new() });
+    ///     assert!(skiplist.insert(item).is_ok());
+    /// }
+    ///
+    /// assert_eq!(skiplist.ceiling(&20).map(|item| item.id), Some(20));
+    /// assert_eq!(skiplist.ceiling(&25).map(|item| item.id), Some(30));
+    /// assert!(skiplist.ceiling(&35).is_none());
+    /// ```
+    pub fn ceiling<Q>(&self, key: &Q) -> Option<&E>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.lower_bound(Bound::Included(key)).map(|ptr| {
+            // SAFETY: Pointer is valid (stored by insert from Box<E>)
+            unsafe { ptr.cast::<E>().as_ref() }
+        })
+    }
+
+    /// Returns the last entry whose key satisfies `accept`.
+    ///
+    /// `accept` must hold for a prefix of the keys in order. Each level is
+    /// followed while the next key is accepted, so the entry reached at level
+    /// 0 is the last accepted one.
+    fn last_where(&self, accept: impl Fn(&K) -> bool) -> Option<&E> {
+        // Start from the head (sentinel node), which has no entry of its own
+        let mut current = &self.head;
+        let mut found = None;
+
+        for level in (0..=self.level).rev() {
+            while let Some(next_ptr) = current.forward[level] {
+                // SAFETY: Pointer is valid (stored by insert from Box<E>)
+                let next_entry: &E = unsafe { next_ptr.cast::<E>().as_ref() };
+                if !accept(next_entry.key()) {
+                    break;
+                }
+                current = next_entry.skiplist_node();
+                found = Some(next_entry);
+            }
+        }
+
+        found
+    }
+
+    /// Returns the entry at `index` in key order (0-based).
+    ///
+    /// Every link records how many entries it skips, so the search follows
+    /// the express lanes while the skipped total stays within `index`.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The position of the entry, where 0 is the first entry
+    ///
+    /// # Returns
+    ///
+    /// A reference to the entry, or `None` if `index >= len()`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use skiplist::{SkipList, SkipListEntry, SkipListNode};
+    /// # struct Item { id: i32, skiplist_meta: SkipListNode }
+    /// # impl SkipListEntry for Item {
+    /// #     type Key = i32;
+    /// #     fn key(&self) -> &i32 { &self.id }
+    /// #     fn skiplist_node(&self) -> &SkipListNode { &self.skiplist_meta }
+    /// #     fn skiplist_node_mut(&mut self) -> &mut SkipListNode { &mut self.skiplist_meta }
+    /// # }
+    /// let mut skiplist: SkipList<i32, Item> = SkipList::new();
+    /// for id in [30, 10, 20] {
+    ///     let item = Box::new(Item { id, skiplist_meta: SkipListNode::new() });
+    ///     assert!(skiplist.insert(item).is_ok());
+    /// }
+    ///
+    /// assert_eq!(skiplist.get_by_index(0).map(|item| item.id), Some(10));
+    /// assert_eq!(skiplist.get_by_index(2).map(|item| item.id), Some(30));
+    /// assert!(skiplist.get_by_index(3).is_none());
+    /// ```
+    pub fn get_by_index(&self, index: usize) -> Option<&E> {
+        if index >= self.len {
+            return None;
+        }
+
+        // The entry at `index` is the last one at or before position index + 1
+        let (ptr, _) = self.seek(index + 1)[0];
+        ptr.map(|ptr| {
+            // SAFETY: Pointer is valid (stored by insert from Box<E>)
+            unsafe { ptr.cast::<E>().as_ref() }
+        })
+    }
+
+    /// Returns the position (0-based) of the entry with the given key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to look up
+    ///
+    /// # Returns
+    ///
+    /// The number of entries with smaller keys, or `None` if the key is not present
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use skiplist::{SkipList, SkipListEntry, SkipListNode};
+    /// # struct Item { id: i32, skiplist_meta: SkipListNode }
+    /// # impl SkipListEntry for Item {
+    /// #     type Key = i32;
+    /// #     fn key(&self) -> &i32 { &self.id }
+    /// #     fn skiplist_node(&self) -> &SkipListNode { &self.skiplist_meta }
+    /// #     fn skiplist_node_mut(&mut self) -> &mut SkipListNode { &mut self.skiplist_meta }
+    /// # }
+    /// let mut skiplist: SkipList<i32, Item> = SkipList::new();
+    /// for id in [30, 10, 20] {
+    ///     let item = Box::new(Item { id, skiplist_meta: SkipListNode::new() });
+    ///     assert!(skiplist.insert(item).is_ok());
+    /// }
+    ///
+    /// assert_eq!(skiplist.rank(&20), Some(1));
+    /// assert_eq!(skiplist.rank(&25), None);
+    /// ```
+    pub fn rank<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (position, next) = self.position_of(Bound::Included(key));
+
+        // `position` entries come before the next one at level 0
+        // SAFETY: Pointer is valid (stored by insert from Box<E>)
+        let next_entry: &E = unsafe { next?.cast::<E>().as_ref() };
+        (next_entry.key().borrow() == key).then_some(position)
+    }
+
+    /// Removes the entry at `index` in key order (0-based) and returns it.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The position of the entry to remove
+    ///
+    /// # Returns
+    ///
+    /// The removed entry, or `None` if `index >= len()`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use skiplist::{SkipList, SkipListEntry, SkipListNode};
+    /// # struct Item { id: i32, skiplist_meta: SkipListNode }
+    /// # impl SkipListEntry for Item {
+    /// #     type Key = i32;
+    /// #     fn key(&self) -> &i32 { &self.id }
+    /// #     fn skiplist_node(&self) -> &SkipListNode { &self.skiplist_meta }
+    /// #     fn skiplist_node_mut(&mut self) -> &mut SkipListNode { &mut self.skiplist_meta }
+    /// # }
+    /// let mut skiplist: SkipList<i32, Item> = SkipList::new();
+    /// for id in [30, 10, 20] {
+    ///     let item = Box::new(Item { id, skiplist_meta: SkipListNode::new() });
+    ///     assert!(skiplist.insert(item).is_ok());
+    /// }
+    ///
+    /// assert_eq!(skiplist.remove_by_index(1).map(|item| item.id), Some(20));
+    /// assert_eq!(skiplist.get_by_index(1).map(|item| item.id), Some(30));
+    /// ```
+    pub fn remove_by_index(&mut self, index: usize) -> Option<Box<E>> {
+        if index >= self.len {
+            return None;
+        }
+
+        // Last node before the target at each level
+        let path = self.seek(index);
+        let head = &mut self.head as *mut SkipListNode;
+        let update: Vec<*mut SkipListNode> = path
+            .iter()
+            .map(|&(ptr, _)| match ptr {
+                // SAFETY: Pointer is valid (stored by insert from Box<E>)
+                Some(ptr) => unsafe {
+                    (*ptr.cast::<E>().as_ptr()).skiplist_node_mut() as *mut SkipListNode
+                },
+                None => head,
+            })
+            .collect();
+
+        unsafe {
+            let target_ptr = (&(*update[0]).forward)[0]?;
+            Some(self.unlink(&update, target_ptr))
+        }
+    }
+
+    /// Returns an iterator over the entries at positions within `range`, in key order.
+    ///
+    /// Both ends are located through the link widths in O(log n), so a page
+    /// of `m` entries costs O(log n + m). Positions past the end are clamped
+    /// to `len()`. The iterator is double-ended and exact-size like [`iter`](Self::iter).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use skiplist::{SkipList, SkipListEntry, SkipListNode};
+    /// # struct Item { id: i32, skiplist_meta: SkipListNode }
+    /// # impl SkipListEntry for Item {
+    /// #     type Key = i32;
+    /// #     fn key(&self) -> &i32 { &self.id }
+    /// #     fn skiplist_node(&self) -> &SkipListNode { &self.skiplist_meta }
+    /// #     fn skiplist_node_mut(&mut self) -> &mut SkipListNode { &mut self.skiplist_meta }
+    /// # }
+    /// let mut skiplist: SkipList<i32, Item> = SkipList::new();
+    /// for id in 0..100 {
+    ///     let item = Box::new(Item { id: id * 10, skiplist_meta: SkipListNode::new() });
+    ///     assert!(skiplist.insert(item).is_ok());
+    /// }
+    ///
+    /// // The third page of ten
+    /// let page: Vec<i32> = skiplist.range_by_index(20..30).map(|item| item.id).collect();
+    /// assert_eq!(page, (200..300).step_by(10).collect::<Vec<_>>());
+    ///
+    /// let tail: Vec<i32> = skiplist.range_by_index(97..).rev().map(|item| item.id).collect();
+    /// assert_eq!(tail, vec![990, 980, 970]);
+    /// ```
+    pub fn range_by_index<R>(&self, range: R) -> Iter<'_, K, E>
+    where
+        R: RangeBounds<usize>,
+    {
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.saturating_add(1),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.len,
+        }
+        .min(self.len);
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        }
+        .min(end);
+
+        // Positions count from 1, so the slice covers positions start+1..=end.
+        // The cursor's front is the first entry after position `start` at each
+        // level, and its back the last entry at or before position `end`
+        // (`None` when that isn't inside the slice).
+        let front = self
+            .seek(start)
+            .iter()
+            .enumerate()
+            .map(|(level, &(ptr, _))| match ptr {
+                // SAFETY: Pointer is valid (stored by insert from Box<E>)
+                Some(ptr) => unsafe { node_of::<E>(ptr) }.forward[level],
+                None => self.head.forward[level],
+            })
+            .collect();
+        let back = self
+            .seek(end)
+            .iter()
+            .map(|&(ptr, position)| if position > start { ptr } else { None })
+            .collect();
+
+        Iter {
+            cursor: RawCursor {
+                front,
+                back: Some(back),
+                remaining: end - start,
+            },
+            _marker: PhantomData,
+        }
+    }
+
+    /// Finds the last node at or before `position` at every level.
+    ///
+    /// Positions count entries from 1, with the head at 0. Each element is the
+    /// node (`None` for the head) and its position.
+    fn seek(&self, position: usize) -> SmallVec<[(Option<NonNull<u8>>, usize); MAX_LEVEL + 1]> {
+        let mut path = SmallVec::from_elem((None, 0), self.level + 1);
+
+        // Start from the head (sentinel node)
+        let mut current = &self.head;
+        let mut current_ptr = None;
+        let mut traversed = 0;
+
+        // Move forward at each level while the link doesn't overshoot
+        for level in (0..=self.level).rev() {
+            while let Some(next_ptr) = current.forward[level] {
+                if traversed + current.span[level] > position {
+                    break;
+                }
+                traversed += current.span[level];
+                // SAFETY: Pointer is valid (stored by insert from Box<E>)
+                current = unsafe { node_of::<E>(next_ptr) };
+                current_ptr = Some(next_ptr);
+            }
+            path[level] = (current_ptr, traversed);
+        }
+
+        path
+    }
+
+    /// Returns `true` if the skiplist contains no elements.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use skiplist::SkipList;
+    /// # use skiplist::{SkipListEntry, SkipListNode};
+    /// # struct Item { id: i32, skiplist_meta: SkipListNode }
+    /// # impl SkipListEntry for Item {
+    /// #     type Key = i32;
+    /// #     fn key(&self) -> &i32 { &self.id }
+    /// #     fn skiplist_node(&self) -> &SkipListNode { &self.skiplist_meta }
+    /// #     fn skiplist_node_mut(&mut self) -> &mut SkipListNode { &mut self.skiplist_meta }
+    /// # }
+    /// let skiplist: SkipList<i32, Item> = SkipList::new();
+    /// assert!(skiplist.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of elements in the skiplist.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use skiplist::SkipList;
     /// # use skiplist::{SkipListEntry, SkipListNode};
     /// # struct Item { id: i32, skiplist_meta: SkipListNode }
     /// # impl SkipListEntry for Item {
@@ -834,14 +1707,1567 @@ where
     pub fn len(&self) -> usize {
         self.len
     }
-}
 
-impl<K, E> Default for SkipList<K, E>
-where
-    K: Ord,
-    E: SkipListEntry<Key = K>,
-{
-    fn default() -> Self {
-        Self::new()
+    /// Removes and drops every entry in the skiplist.
+    ///
+    /// The skiplist keeps its maximum level and RNG state and can be reused.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use skiplist::{SkipList, SkipListEntry, SkipListNode};
+    /// # struct Item { id: i32, skiplist_meta: SkipListNode }
+    /// # impl SkipListEntry for Item {
+    /// #     type Key = i32;
+    /// #     fn key(&self) -> &i32 { &self.id }
+    /// #     fn skiplist_node(&self) -> &SkipListNode { &self.skiplist_meta }
+    /// #     fn skiplist_node_mut(&mut self) -> &mut SkipListNode { &mut self.skiplist_meta }
+    /// # }
+    /// let mut skiplist: SkipList<i32, Item> = SkipList::new();
+    /// let item = Box::new(Item { id: 1, skiplist_meta: SkipListNode::new() });
+    /// assert!(skiplist.insert(item).is_ok());
+    ///
+    /// skiplist.clear();
+    /// assert!(skiplist.is_empty());
+    /// assert!(skiplist.get(&1).is_none());
+    /// ```
+    pub fn clear(&mut self) {
+        drop(self.drain());
+    }
+
+    /// Removes every entry from the skiplist, returning them in key order.
+    ///
+    /// The skiplist is emptied as soon as `drain()` is called. Entries that are
+    /// not consumed are dropped when the iterator is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use skiplist::{SkipList, SkipListEntry, SkipListNode};
+    /// # struct Item { id: i32, skiplist_meta: SkipListNode }
+    /// # impl SkipListEntry for Item {
+    /// #     type Key = i32;
+    /// #     fn key(&self) -> &i32 { &self.id }
+    /// #     fn skiplist_node(&self) -> &SkipListNode { &self.skiplist_meta }
+    /// #     fn skiplist_node_mut(&mut self) -> &mut SkipListNode { &mut self.skiplist_meta }
+    /// # }
+    /// let mut skiplist: SkipList<i32, Item> = SkipList::new();
+    /// for id in [3, 1, 2] {
+    ///     let item = Box::new(Item { id, skiplist_meta: SkipListNode::new() });
+    ///     assert!(skiplist.insert(item).is_ok());
+    /// }
+    ///
+    /// let ids: Vec<i32> = skiplist.drain().map(|item| item.id).collect();
+    /// assert_eq!(ids, vec![1, 2, 3]);
+    /// assert!(skiplist.is_empty());
+    /// ```
+    pub fn drain(&mut self) -> Drain<'_, K, E> {
+        Drain {
+            cursor: self.detach(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over the entries in key order.
+    ///
+    /// Forward iteration follows level-0 pointers in O(1) per step. Reverse
+    /// iteration (`next_back`, `rev()`) finds each predecessor through the
+    /// express lanes in O(log n) average time.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use skiplist::{SkipList, SkipListEntry, SkipListNode};
+    /// # struct Item { id: i32, skiplist_meta: SkipListNode }
+    /// # impl SkipListEntry for Item {
+    /// #     type Key = i32;
+    /// #     fn key(&self) -> &i32 { &self.id }
+    /// #     fn skiplist_node(&self) -> &SkipListNode { &self.skiplist_meta }
+    /// #     fn skiplist_node_mut(&mut self) -> &mut SkipListNode { &mut self.skiplist_meta }
+    /// # }
+    /// let mut skiplist: SkipList<i32, Item> = SkipList::new();
+    /// for id in [3, 1, 2] {
+    ///     let item = Box::new(Item { id, skiplist_meta: SkipListNode::new() });
+    ///     assert!(skiplist.insert(item).is_ok());
+    /// }
+    ///
+    /// let ids: Vec<i32> = skiplist.iter().map(|item| item.id).collect();
+    /// assert_eq!(ids, vec![1, 2, 3]);
+    ///
+    /// let reversed: Vec<i32> = skiplist.iter().rev().map(|item| item.id).collect();
+    /// assert_eq!(reversed, vec![3, 2, 1]);
+    /// ```
+    pub fn iter(&self) -> Iter<'_, K, E> {
+        Iter {
+            cursor: RawCursor::new(&self.head, self.level, self.len),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns an iterator that allows modifying each entry, in key order.
+    ///
+    /// Entries are yielded as [`RefMut`] guards. Changing an entry's key would
+    /// silently break the skiplist's ordering, so each guard remembers the key
+    /// it was created with and panics on drop if the key has changed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use skiplist::{SkipList, SkipListEntry, SkipListNode};
+    /// # struct Item { id: i32, hits: u32, skiplist_meta: SkipListNode }
+    /// # impl SkipListEntry for Item {
+    /// #     type Key = i32;
+    /// #     fn key(&self) -> &i32 { &self.id }
+    /// #     fn skiplist_node(&self) -> &SkipListNode { &self.skiplist_meta }
+    /// #     fn skiplist_node_mut(&mut self) -> &mut SkipListNode { &mut self.skiplist_meta }
+    /// # }
+    /// let mut skiplist: SkipList<i32, Item> = SkipList::new();
+    /// for id in [1, 2, 3] {
+    ///     let item = Box::new(Item { id, hits: 0, skiplist_meta: SkipListNode::new() });
+    ///     assert!(skiplist.insert(item).is_ok());
+    /// }
+    ///
+    /// for mut item in skiplist.iter_mut() {
+    ///     item.hits += 10;
+    /// }
+    /// assert_eq!(skiplist.get(&2).unwrap().hits, 10);
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, E>
+    where
+        K: Clone,
+    {
+        IterMut {
+            cursor: RawCursor::new(&self.head, self.level, self.len),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over the entries whose keys fall within `range`, in key order.
+    ///
+    /// The levels are descended once to find the first entry in the range;
+    /// after that the iterator follows level-0 pointers until it passes the
+    /// end bound, so iterating `m` entries costs O(log n + m). A range whose
+    /// start lies after its end yields nothing. The bounds may use a borrowed
+    /// form of the key, e.g. `(Bound::Included("a"), Bound::Excluded("n"))`
+    /// over `String` keys.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use skiplist::{SkipList, SkipListEntry, SkipListNode};
+    /// # struct Item { id: i32, skiplist_meta: SkipListNode }
+    /// # impl SkipListEntry for Item {
+    /// #     type Key = i32;
+    /// #     fn key(&self) -> &i32 { &self.id }
+    /// #     fn skiplist_node(&self) -> &SkipListNode { &self.skiplist_meta }
+    /// #     fn skiplist_node_mut(&mut self) -> &mut SkipListNode { &mut self.skiplist_meta }
+    /// # }
+    /// let mut skiplist: SkipList<i32, Item> = SkipList::new();
+    /// for id in [10, 20, 30, 40, 50] {
+    ///     let item = Box::new(Item { id, skiplist_meta: SkipListNode::new() });
+    ///     assert!(skiplist.insert(item).is_ok());
+    /// }
+    ///
+    /// let ids: Vec<i32> = skiplist.range(15..=40).map(|item| item.id).collect();
+    /// assert_eq!(ids, vec![20, 30, 40]);
+    ///
+    /// let ids: Vec<i32> = skiplist.range(..30).map(|item| item.id).collect();
+    /// assert_eq!(ids, vec![10, 20]);
+    /// ```
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, K, E, R, Q>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        Range {
+            next: self.lower_bound(range.start_bound()),
+            range,
+            _marker: PhantomData,
+            _query: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over mutable guards for the entries whose keys fall
+    /// within `range`, in key order.
+    ///
+    /// Like [`range`](Self::range), but entries are yielded as [`RefMut`]
+    /// guards, which panic on drop if the entry's key was changed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use skiplist::{SkipList, SkipListEntry, SkipListNode};
+    /// # struct Item { id: i32, hits: u32, skiplist_meta: SkipListNode }
+    /// # impl SkipListEntry for Item {
+    /// #     type Key = i32;
+    /// #     fn key(&self) -> &i32 { &self.id }
+    /// #     fn skiplist_node(&self) -> &SkipListNode { &self.skiplist_meta }
+    /// #     fn skiplist_node_mut(&mut self) -> &mut SkipListNode { &mut self.skiplist_meta }
+    /// # }
+    /// let mut skiplist: SkipList<i32, Item> = SkipList::new();
+    /// for id in 1..=5 {
+    ///     let item = Box::new(Item { id, hits: 0, skiplist_meta: SkipListNode::new() });
+    ///     assert!(skiplist.insert(item).is_ok());
+    /// }
+    ///
+    /// for mut item in skiplist.range_mut(2..4) {
+    ///     item.hits += 1;
+    /// }
+    /// let hits: Vec<u32> = skiplist.iter().map(|item| item.hits).collect();
+    /// assert_eq!(hits, vec![0, 1, 1, 0, 0]);
+    /// ```
+    pub fn range_mut<Q, R>(&mut self, range: R) -> RangeMut<'_, K, E, R, Q>
+    where
+        K: Clone + Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        RangeMut {
+            next: self.lower_bound(range.start_bound()),
+            range,
+            _marker: PhantomData,
+            _query: PhantomData,
+        }
+    }
+
+    /// Removes every entry whose key falls within `range` and returns them in key order.
+    ///
+    /// The span is unlinked in a single pass: one descent finds the last entry
+    /// before the range at every level, and each of those is then linked
+    /// straight to the first entry after the range at its level. The removed
+    /// entries have their forward pointers reset, so they can be inserted again.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use skiplist::{SkipList, SkipListEntry, SkipListNode};
+    /// # struct Item { id: i32, skiplist_meta: SkipListNode }
+    /// # impl SkipListEntry for Item {
+    /// #     type Key = i32;
+    /// #     fn key(&self) -> &i32 { &self.id }
+    /// #     fn skiplist_node(&self) -> &SkipListNode { &self.skiplist_meta }
+    /// #     fn skiplist_node_mut(&mut self) -> &mut SkipListNode { &mut self.skiplist_meta }
+    /// # }
+    /// let mut skiplist: SkipList<i32, Item> = SkipList::new();
+    /// for id in 1..=10 {
+    ///     let item = Box::new(Item { id, skiplist_meta: SkipListNode::new() });
+    ///     assert!(skiplist.insert(item).is_ok());
+    /// }
+    ///
+    /// let removed: Vec<i32> = skiplist.remove_range(3..8).iter().map(|item| item.id).collect();
+    /// assert_eq!(removed, vec![3, 4, 5, 6, 7]);
+    /// assert_eq!(skiplist.len(), 5);
+    /// assert!(skiplist.get(&5).is_none());
+    /// ```
+    pub fn remove_range<Q, R>(&mut self, range: R) -> Vec<Box<E>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let (start, end) = (range.start_bound(), range.end_bound());
+
+        // Last node before the range at each level
+        let mut update: Vec<*mut SkipListNode> = vec![std::ptr::null_mut(); self.level + 1];
+        let mut current = &mut self.head as *mut SkipListNode;
+
+        for level in (0..=self.level).rev() {
+            unsafe {
+                while let Some(next_ptr) = (&(*current).forward)[level] {
+                    let next_entry = next_ptr.cast::<E>().as_ptr();
+                    if !before_start((*next_entry).key(), start) {
+                        break;
+                    }
+                    current = (*next_entry).skiplist_node_mut() as *mut SkipListNode;
+                }
+            }
+            update[level] = current;
+        }
+
+        // Collect the span along level 0 before any links change
+        let mut removed = Vec::new();
+        let mut next = unsafe { (&(*update[0]).forward)[0] };
+        while let Some(ptr) = next {
+            // SAFETY: Pointer is valid (stored by insert from Box<E>)
+            let entry: &E = unsafe { ptr.cast::<E>().as_ref() };
+            if past_end(entry.key(), end) {
+                break;
+            }
+            removed.push(ptr);
+            next = entry.skiplist_node().forward[0];
+        }
+
+        if removed.is_empty() {
+            return Vec::new();
+        }
+
+        // Link each predecessor past the span at its level. Only the
+        // predecessors are written, so the span's own pointers stay intact
+        // while later levels walk through it.
+        for (level, &update_node_ptr) in update.iter().enumerate() {
+            unsafe {
+                let update_node = &mut *update_node_ptr;
+                let mut next = update_node.forward[level];
+                let mut width = update_node.span[level];
+                while let Some(ptr) = next {
+                    let entry: &E = ptr.cast::<E>().as_ref();
+                    if past_end(entry.key(), end) {
+                        break;
+                    }
+                    next = entry.skiplist_node().forward[level];
+                    width += entry.skiplist_node().span[level];
+                }
+                update_node.forward[level] = next;
+                update_node.span[level] = width - removed.len();
+            }
+        }
+
+        // Update list level if we removed from top levels
+        while self.level > 0 && self.head.forward[self.level].is_none() {
+            self.level -= 1;
+        }
+
+        self.len -= removed.len();
+
+        // SAFETY: The entries are unlinked and were stored by insert from Box<E>
+        removed
+            .into_iter()
+            .map(|ptr| unsafe { RawCursor::take::<E>(ptr) })
+            .collect()
+    }
+
+    /// Returns an iterator over every entry with the given key, in insertion order.
+    ///
+    /// Yields at most one entry unless the skiplist allows duplicates.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use skiplist::{SkipList, SkipListEntry, SkipListNode};
+    /// # struct Event { at: u64, name: &'static str, skiplist_meta: SkipListNode }
+    /// # impl SkipListEntry for Event {
+    /// #     type Key = u64;
+    /// #     fn key(&self) -> &u64 { &self.at }
+    /// #     fn skiplist_node(&self) -> &SkipListNode { &self.skiplist_meta }
+    /// #     fn skiplist_node_mut(&mut self) -> &mut SkipListNode { &mut self.skiplist_meta }
+    /// # }
+    /// let mut schedule: SkipList<u64, Event> = SkipList::builder().allow_duplicates(true).build();
+    /// for (at, name) in [(10, "a"), (20, "b"), (10, "c"), (10, "d")] {
+    ///     let event = Box::new(Event { at, name, skiplist_meta: SkipListNode::new() });
+    ///     assert!(schedule.insert(event).is_ok());
+    /// }
+    ///
+    /// let names: Vec<&str> = schedule.get_all(&10).map(|event| event.name).collect();
+    /// assert_eq!(names, vec!["a", "c", "d"]);
+    /// ```
+    pub fn get_all<'a, Q>(&'a self, key: &'a Q) -> Range<'a, K, E, (Bound<&'a Q>, Bound<&'a Q>), Q>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.range((Bound::Included(key), Bound::Included(key)))
+    }
+
+    /// Returns the number of entries with the given key.
+    ///
+    /// Counts through the link widths in O(log n), however many entries share the key.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use skiplist::{SkipList, SkipListEntry, SkipListNode};
+    /// # struct Event { at: u64, skiplist_meta: SkipListNode }
+    /// # impl SkipListEntry for Event {
+    /// #     type Key = u64;
+    /// #     fn key(&self) -> &u64 { &self.at }
+    /// #     fn skiplist_node(&self) -> &SkipListNode { &self.skiplist_meta }
+    /// #     fn skiplist_node_mut(&mut self) -> &mut SkipListNode { &mut self.skiplist_meta }
+    /// # }
+    /// let mut schedule: SkipList<u64, Event> = SkipList::builder().allow_duplicates(true).build();
+    /// for at in [10, 20, 10, 10] {
+    ///     let event = Box::new(Event { at, skiplist_meta: SkipListNode::new() });
+    ///     assert!(schedule.insert(event).is_ok());
+    /// }
+    ///
+    /// assert_eq!(schedule.count(&10), 3);
+    /// assert_eq!(schedule.count(&15), 0);
+    /// ```
+    pub fn count<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (first, _) = self.position_of(Bound::Included(key));
+        let (after, _) = self.position_of(Bound::Excluded(key));
+        after - first
+    }
+
+    /// Removes the earliest inserted entry with the given key and returns it.
+    ///
+    /// Equivalent to [`remove`](Self::remove); the name makes the intent clear
+    /// when duplicates are allowed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use skiplist::{SkipList, SkipListEntry, SkipListNode};
+    /// # struct Event { at: u64, name: &'static str, skiplist_meta: SkipListNode }
+    /// # impl SkipListEntry for Event {
+    /// #     type Key = u64;
+    /// #     fn key(&self) -> &u64 { &self.at }
+    /// #     fn skiplist_node(&self) -> &SkipListNode { &self.skiplist_meta }
+    /// #     fn skiplist_node_mut(&mut self) -> &mut SkipListNode { &mut self.skiplist_meta }
+    /// # }
+    /// let mut schedule: SkipList<u64, Event> = SkipList::builder().allow_duplicates(true).build();
+    /// for (at, name) in [(10, "a"), (10, "b")] {
+    ///     let event = Box::new(Event { at, name, skiplist_meta: SkipListNode::new() });
+    ///     assert!(schedule.insert(event).is_ok());
+    /// }
+    ///
+    /// assert_eq!(schedule.remove_one(&10).map(|event| event.name), Some("a"));
+    /// assert_eq!(schedule.remove_one(&10).map(|event| event.name), Some("b"));
+    /// assert!(schedule.remove_one(&10).is_none());
+    /// ```
+    pub fn remove_one<Q>(&mut self, key: &Q) -> Option<Box<E>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.remove(key)
+    }
+
+    /// Removes every entry with the given key, returning them in insertion order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use skiplist::{SkipList, SkipListEntry, SkipListNode};
+    /// # struct Event { at: u64, name: &'static str, skiplist_meta: SkipListNode }
+    /// # impl SkipListEntry for Event {
+    /// #     type Key = u64;
+    /// #     fn key(&self) -> &u64 { &self.at }
+    /// #     fn skiplist_node(&self) -> &SkipListNode { &self.skiplist_meta }
+    /// #     fn skiplist_node_mut(&mut self) -> &mut SkipListNode { &mut self.skiplist_meta }
+    /// # }
+    /// let mut schedule: SkipList<u64, Event> = SkipList::builder().allow_duplicates(true).build();
+    /// for (at, name) in [(10, "a"), (20, "b"), (10, "c")] {
+    ///     let event = Box::new(Event { at, name, skiplist_meta: SkipListNode::new() });
+    ///     assert!(schedule.insert(event).is_ok());
+    /// }
+    ///
+    /// let due: Vec<&str> = schedule.remove_all(&10).iter().map(|event| event.name).collect();
+    /// assert_eq!(due, vec!["a", "c"]);
+    /// assert_eq!(schedule.len(), 1);
+    /// ```
+    pub fn remove_all<Q>(&mut self, key: &Q) -> Vec<Box<E>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.remove_range((Bound::Included(key), Bound::Included(key)))
+    }
+
+    /// Returns `true` if several entries may share a key.
+    ///
+    /// Set with [`SkipListBuilder::allow_duplicates`].
+    pub fn allows_duplicates(&self) -> bool {
+        self.allow_duplicates
+    }
+
+    /// Returns the number of entries before the start bound of a range, and
+    /// the first entry that is not.
+    fn position_of<Q>(&self, start: Bound<&Q>) -> (usize, Option<NonNull<u8>>)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        // Start from the head (sentinel node)
+        let mut current = &self.head;
+        let mut traversed = 0;
+
+        // Move forward at each level while next.key is before the range,
+        // counting skipped entries
+        for level in (0..=self.level).rev() {
+            while let Some(next_ptr) = current.forward[level] {
+                // SAFETY: Pointer is valid (stored by insert from Box<E>)
+                let next_entry: &E = unsafe { next_ptr.cast::<E>().as_ref() };
+                if !before_start(next_entry.key(), start) {
+                    break;
+                }
+                traversed += current.span[level];
+                current = next_entry.skiplist_node();
+            }
+        }
+
+        (traversed, current.forward[0])
+    }
+
+    /// Returns the first entry that is not before the start bound of a range.
+    fn lower_bound<Q>(&self, start: Bound<&Q>) -> Option<NonNull<u8>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.position_of(start).1
+    }
+
+    /// Detaches every entry from the skiplist, returning a cursor that owns them.
+    ///
+    /// The skiplist is left empty (and valid) before any entry is handed out,
+    /// so leaking the returned cursor only leaks the entries.
+    fn detach(&mut self) -> RawCursor {
+        let cursor = RawCursor::new(&self.head, self.level, self.len);
+
+        for forward in self.head.forward.iter_mut() {
+            *forward = None;
+        }
+        for span in self.head.span.iter_mut() {
+            *span = 0;
+        }
+        self.len = 0;
+        self.level = 0;
+
+        cursor
+    }
+}
+
+impl<K, E> Drop for SkipList<K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+    /// Frees every entry still owned by the skiplist.
+    ///
+    /// Entries were handed to the skiplist via `Box::into_raw` in `insert`, so
+    /// each one is reconstituted as a `Box<E>` while walking level 0 and dropped.
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+/// Forward pointers for every level, stored inline for the default `MAX_LEVEL`.
+type Lanes = SmallVec<[Option<NonNull<u8>>; MAX_LEVEL + 1]>;
+
+/// Returns the embedded node of the entry behind a type-erased pointer.
+///
+/// # Safety
+///
+/// `ptr` must point to a live `E` that was inserted into a skiplist.
+#[inline]
+unsafe fn node_of<'a, E: SkipListEntry + 'a>(ptr: NonNull<u8>) -> &'a SkipListNode {
+    ptr.cast::<E>().as_ref().skiplist_node()
+}
+
+/// Returns `true` if `key` comes before the range starting at `start`.
+#[inline]
+fn before_start<K, Q>(key: &K, start: Bound<&Q>) -> bool
+where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    let key = key.borrow();
+    match start {
+        Bound::Included(start) => key < start,
+        Bound::Excluded(start) => key <= start,
+        Bound::Unbounded => false,
+    }
+}
+
+/// Returns `true` if `key` comes after the range ending at `end`.
+#[inline]
+fn past_end<K, Q>(key: &K, end: Bound<&Q>) -> bool
+where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    let key = key.borrow();
+    match end {
+        Bound::Included(end) => key > end,
+        Bound::Excluded(end) => key >= end,
+        Bound::Unbounded => false,
+    }
+}
+
+/// A position in the skiplist found by `find_slot`.
+///
+/// Holds the last node before the position at every level together with each
+/// node's position, which is everything needed to link a new entry there,
+/// unlink the entry after it, or swap that entry out. Only valid while the
+/// skiplist is unchanged.
+struct Slot {
+    /// Last node before the position at each level (the head above the current height).
+    update: Vec<*mut SkipListNode>,
+
+    /// Position of each update node (the head is at 0, entries at 1..=len).
+    rank: Vec<usize>,
+}
+
+/// Position state shared by all skiplist iterators.
+///
+/// The front boundary is kept as the first unvisited entry at every level
+/// (`front`); the back boundary as the last unvisited entry before it at every
+/// level (`back`, built on the first `next_back`), where `None` stands for the
+/// front boundary. Both ends move by following forward pointers of unvisited
+/// entries and comparing pointers, never keys, so entries already handed out
+/// are not read again. This is what lets `IterMut`, `Drain` and `IntoIter`
+/// walk backwards while earlier entries are borrowed or freed.
+#[derive(Clone)]
+struct RawCursor {
+    /// First unvisited entry at each level.
+    front: Lanes,
+
+    /// Last unvisited entry at each level (`None` = front boundary).
+    back: Option<Lanes>,
+
+    /// Number of unvisited entries.
+    remaining: usize,
+}
+
+impl RawCursor {
+    /// Creates a cursor over every entry reachable from `head`.
+    fn new(head: &SkipListNode, level: usize, len: usize) -> Self {
+        Self {
+            front: head.forward[..=level].iter().copied().collect(),
+            back: None,
+            remaining: len,
+        }
+    }
+
+    /// Follows the forward pointer at `level` from `from` (`None` = front boundary).
+    ///
+    /// # Safety
+    ///
+    /// `from` must be an unvisited entry of type `E`.
+    unsafe fn forward<E: SkipListEntry>(
+        &self,
+        from: Option<NonNull<u8>>,
+        level: usize,
+    ) -> Option<NonNull<u8>> {
+        match from {
+            Some(ptr) => node_of::<E>(ptr).forward[level],
+            None => self.front[level],
+        }
+    }
+
+    /// Visits the first unvisited entry.
+    ///
+    /// # Safety
+    ///
+    /// The cursor's entries must be live values of type `E`.
+    unsafe fn next_front<E: SkipListEntry>(&mut self) -> Option<NonNull<u8>> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let ptr = self.front[0]?;
+
+        // The entry is the first unvisited one at each of its levels
+        for (level, &next) in node_of::<E>(ptr).forward.iter().enumerate() {
+            self.front[level] = next;
+            if let Some(back) = &mut self.back {
+                if back[level] == Some(ptr) {
+                    back[level] = None;
+                }
+            }
+        }
+
+        self.remaining -= 1;
+        Some(ptr)
+    }
+
+    /// Visits the last unvisited entry.
+    ///
+    /// # Safety
+    ///
+    /// The cursor's entries must be live values of type `E`.
+    unsafe fn next_back<E: SkipListEntry>(&mut self) -> Option<NonNull<u8>> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let top = self.front.len() - 1;
+
+        let mut back = match self.back.take() {
+            Some(back) => back,
+            None => {
+                // First step from the back: walk each level to its end
+                let mut back = Lanes::from_elem(None, top + 1);
+                let mut current = None;
+                for level in (0..=top).rev() {
+                    while let Some(next) = self.forward::<E>(current, level) {
+                        current = Some(next);
+                    }
+                    back[level] = current;
+                }
+                back
+            }
+        };
+
+        let Some(ptr) = back[0] else {
+            self.back = Some(back);
+            return None;
+        };
+        let height = node_of::<E>(ptr).forward.len() - 1;
+
+        // Above its height the entry isn't linked, so the lanes there are
+        // unchanged. Below, descend from the last entry before it on the
+        // first level it doesn't reach, stopping just short of it.
+        let mut current = if height < top { back[height + 1] } else { None };
+        for level in (0..=height).rev() {
+            while let Some(next) = self.forward::<E>(current, level) {
+                if next == ptr {
+                    break;
+                }
+                current = Some(next);
+            }
+            back[level] = current;
+        }
+
+        self.back = Some(back);
+        self.remaining -= 1;
+        Some(ptr)
+    }
+
+    /// Takes ownership of a visited entry, resetting its forward pointers so
+    /// it can be inserted again.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have come from `Box::into_raw` and be owned by this cursor.
+    unsafe fn take<E: SkipListEntry>(ptr: NonNull<u8>) -> Box<E> {
+        let mut entry = Box::from_raw(ptr.cast::<E>().as_ptr());
+        let node = entry.skiplist_node_mut();
+        node.forward.clear();
+        node.span.clear();
+        entry
+    }
+}
+
+/// An iterator over the entries of a [`SkipList`], in key order.
+///
+/// Created by [`SkipList::iter`].
+pub struct Iter<'a, K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+    cursor: RawCursor,
+    _marker: PhantomData<&'a SkipList<K, E>>,
+}
+
+impl<K, E> Clone for Iter<'_, K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            cursor: self.cursor.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, K, E> Iterator for Iter<'a, K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+    type Item = &'a E;
+
+    fn next(&mut self) -> Option<&'a E> {
+        // SAFETY: The skiplist is borrowed for 'a, so every entry stays alive
+        // and unmodified while the iterator exists
+        unsafe {
+            self.cursor
+                .next_front::<E>()
+                .map(|ptr| ptr.cast::<E>().as_ref())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.cursor.remaining, Some(self.cursor.remaining))
+    }
+}
+
+impl<K, E> DoubleEndedIterator for Iter<'_, K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        // SAFETY: As in next()
+        unsafe {
+            self.cursor
+                .next_back::<E>()
+                .map(|ptr| ptr.cast::<E>().as_ref())
+        }
+    }
+}
+
+impl<K, E> ExactSizeIterator for Iter<'_, K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+}
+
+impl<K, E> FusedIterator for Iter<'_, K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+}
+
+/// An iterator over mutable guards for the entries of a [`SkipList`], in key order.
+///
+/// Created by [`SkipList::iter_mut`].
+pub struct IterMut<'a, K, E>
+where
+    K: Ord + Clone,
+    E: SkipListEntry<Key = K>,
+{
+    cursor: RawCursor,
+    _marker: PhantomData<&'a mut SkipList<K, E>>,
+}
+
+impl<'a, K, E> Iterator for IterMut<'a, K, E>
+where
+    K: Ord + Clone,
+    E: SkipListEntry<Key = K>,
+{
+    type Item = RefMut<'a, K, E>;
+
+    fn next(&mut self) -> Option<RefMut<'a, K, E>> {
+        // SAFETY: The skiplist is mutably borrowed for 'a and each entry is
+        // visited once, so the guards never alias
+        unsafe { self.cursor.next_front::<E>().map(|ptr| RefMut::new(ptr)) }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.cursor.remaining, Some(self.cursor.remaining))
+    }
+}
+
+impl<K, E> DoubleEndedIterator for IterMut<'_, K, E>
+where
+    K: Ord + Clone,
+    E: SkipListEntry<Key = K>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        // SAFETY: As in next(); the cursor never reads visited entries, which
+        // may be borrowed through their guards
+        unsafe { self.cursor.next_back::<E>().map(|ptr| RefMut::new(ptr)) }
+    }
+}
+
+impl<K, E> ExactSizeIterator for IterMut<'_, K, E>
+where
+    K: Ord + Clone,
+    E: SkipListEntry<Key = K>,
+{
+}
+
+impl<K, E> FusedIterator for IterMut<'_, K, E>
+where
+    K: Ord + Clone,
+    E: SkipListEntry<Key = K>,
+{
+}
+
+/// An iterator over the entries of a [`SkipList`] within a key range, in key order.
+///
+/// Created by [`SkipList::range`].
+pub struct Range<'a, K, E, R, Q = K>
+where
+    K: Ord + Borrow<Q>,
+    E: SkipListEntry<Key = K>,
+    R: RangeBounds<Q>,
+    Q: Ord + ?Sized,
+{
+    /// Next entry to yield, if it is still within the range.
+    next: Option<NonNull<u8>>,
+    range: R,
+    _marker: PhantomData<&'a SkipList<K, E>>,
+    /// The borrowed key form the bounds are expressed in.
+    _query: PhantomData<fn(&Q)>,
+}
+
+impl<K, E, R, Q> Clone for Range<'_, K, E, R, Q>
+where
+    K: Ord + Borrow<Q>,
+    E: SkipListEntry<Key = K>,
+    R: RangeBounds<Q> + Clone,
+    Q: Ord + ?Sized,
+{
+    fn clone(&self) -> Self {
+        Self {
+            next: self.next,
+            range: self.range.clone(),
+            _marker: PhantomData,
+            _query: PhantomData,
+        }
+    }
+}
+
+impl<'a, K, E, R, Q> Iterator for Range<'a, K, E, R, Q>
+where
+    K: Ord + Borrow<Q>,
+    E: SkipListEntry<Key = K>,
+    R: RangeBounds<Q>,
+    Q: Ord + ?Sized,
+{
+    type Item = &'a E;
+
+    fn next(&mut self) -> Option<&'a E> {
+        // SAFETY: The skiplist is borrowed for 'a, so every entry stays alive
+        // and unmodified while the iterator exists
+        let entry: &'a E = unsafe { self.next?.cast::<E>().as_ref() };
+        if past_end(entry.key(), self.range.end_bound()) {
+            self.next = None;
+            return None;
+        }
+
+        self.next = entry.skiplist_node().forward[0];
+        Some(entry)
+    }
+}
+
+impl<K, E, R, Q> FusedIterator for Range<'_, K, E, R, Q>
+where
+    K: Ord + Borrow<Q>,
+    E: SkipListEntry<Key = K>,
+    R: RangeBounds<Q>,
+    Q: Ord + ?Sized,
+{
+}
+
+/// An iterator over mutable guards for the entries of a [`SkipList`] within a
+/// key range, in key order.
+///
+/// Created by [`SkipList::range_mut`].
+pub struct RangeMut<'a, K, E, R, Q = K>
+where
+    K: Ord + Clone + Borrow<Q>,
+    E: SkipListEntry<Key = K>,
+    R: RangeBounds<Q>,
+    Q: Ord + ?Sized,
+{
+    /// Next entry to yield, if it is still within the range.
+    next: Option<NonNull<u8>>,
+    range: R,
+    _marker: PhantomData<&'a mut SkipList<K, E>>,
+    /// The borrowed key form the bounds are expressed in.
+    _query: PhantomData<fn(&Q)>,
+}
+
+impl<'a, K, E, R, Q> Iterator for RangeMut<'a, K, E, R, Q>
+where
+    K: Ord + Clone + Borrow<Q>,
+    E: SkipListEntry<Key = K>,
+    R: RangeBounds<Q>,
+    Q: Ord + ?Sized,
+{
+    type Item = RefMut<'a, K, E>;
+
+    fn next(&mut self) -> Option<RefMut<'a, K, E>> {
+        let ptr = self.next?;
+
+        // SAFETY: The skiplist is mutably borrowed for 'a and the entry hasn't
+        // been yielded yet, so nothing else accesses it. Its successor is read
+        // before the guard is created, and yielded entries are never read again.
+        unsafe {
+            let entry = ptr.cast::<E>().as_ref();
+            if past_end(entry.key(), self.range.end_bound()) {
+                self.next = None;
+                return None;
+            }
+            self.next = entry.skiplist_node().forward[0];
+            Some(RefMut::new(ptr))
+        }
+    }
+}
+
+impl<K, E, R, Q> FusedIterator for RangeMut<'_, K, E, R, Q>
+where
+    K: Ord + Clone + Borrow<Q>,
+    E: SkipListEntry<Key = K>,
+    R: RangeBounds<Q>,
+    Q: Ord + ?Sized,
+{
+}
+
+/// Mutable access to an entry yielded by [`SkipList::iter_mut`] or
+/// [`SkipList::range_mut`].
+///
+/// Dereferences to the entry. Everything except the key may be changed; the
+/// guard panics when dropped if the key no longer matches the one the entry
+/// had when it was yielded, since the skiplist would otherwise be out of order.
+pub struct RefMut<'a, K, E>
+where
+    K: Ord + Clone,
+    E: SkipListEntry<Key = K>,
+{
+    entry: NonNull<E>,
+    key: K,
+    _marker: PhantomData<&'a mut E>,
+}
+
+impl<K, E> RefMut<'_, K, E>
+where
+    K: Ord + Clone,
+    E: SkipListEntry<Key = K>,
+{
+    /// # Safety
+    ///
+    /// `ptr` must point to a live `E` that nothing else accesses for the
+    /// guard's lifetime.
+    unsafe fn new(ptr: NonNull<u8>) -> Self {
+        let entry = ptr.cast::<E>();
+        Self {
+            entry,
+            key: entry.as_ref().key().clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<K, E> std::ops::Deref for RefMut<'_, K, E>
+where
+    K: Ord + Clone,
+    E: SkipListEntry<Key = K>,
+{
+    type Target = E;
+
+    fn deref(&self) -> &E {
+        // SAFETY: The guard has exclusive access to the entry (see new())
+        unsafe { self.entry.as_ref() }
+    }
+}
+
+impl<K, E> std::ops::DerefMut for RefMut<'_, K, E>
+where
+    K: Ord + Clone,
+    E: SkipListEntry<Key = K>,
+{
+    fn deref_mut(&mut self) -> &mut E {
+        // SAFETY: The guard has exclusive access to the entry (see new())
+        unsafe { self.entry.as_mut() }
+    }
+}
+
+impl<K, E> Drop for RefMut<'_, K, E>
+where
+    K: Ord + Clone,
+    E: SkipListEntry<Key = K>,
+{
+    fn drop(&mut self) {
+        // Don't turn an unrelated panic into an abort
+        if !std::thread::panicking() {
+            // SAFETY: The guard has exclusive access to the entry (see new())
+            let key = unsafe { self.entry.as_ref() }.key();
+            assert!(
+                *key == self.key,
+                "key of a skiplist entry was modified through a RefMut guard"
+            );
+        }
+    }
+}
+
+/// A view into a single key of a [`SkipList`], which may be occupied or vacant.
+///
+/// Created by [`SkipList::entry`].
+pub enum Entry<'a, K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+    /// An entry with the key exists.
+    Occupied(OccupiedEntry<'a, K, E>),
+
+    /// No entry with the key exists.
+    Vacant(VacantEntry<'a, K, E>),
+}
+
+impl<'a, K, E> Entry<'a, K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+    /// Returns the key this entry was looked up with.
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(occupied) => occupied.key(),
+            Entry::Vacant(vacant) => vacant.key(),
+        }
+    }
+
+    /// Inserts `entry` if the key is vacant and returns a mutable reference to
+    /// the entry with the key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is vacant and `entry` has a different key.
+    pub fn or_insert(self, entry: Box<E>) -> &'a mut E {
+        self.or_insert_with(|| entry)
+    }
+
+    /// Inserts the entry returned by `make` if the key is vacant and returns a
+    /// mutable reference to the entry with the key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is vacant and the new entry has a different key.
+    pub fn or_insert_with<F>(self, make: F) -> &'a mut E
+    where
+        F: FnOnce() -> Box<E>,
+    {
+        self.or_insert_with_key(|_| make())
+    }
+
+    /// Like [`or_insert_with`](Self::or_insert_with), but `make` is given the key.
+    pub fn or_insert_with_key<F>(self, make: F) -> &'a mut E
+    where
+        F: FnOnce(&K) -> Box<E>,
+    {
+        match self {
+            Entry::Occupied(occupied) => occupied.into_mut(),
+            Entry::Vacant(vacant) => {
+                let entry = make(vacant.key());
+                vacant.insert(entry)
+            }
+        }
+    }
+
+    /// Calls `modify` on the entry if the key is occupied.
+    pub fn and_modify<F>(mut self, modify: F) -> Self
+    where
+        F: FnOnce(&mut E),
+    {
+        if let Entry::Occupied(occupied) = &mut self {
+            modify(occupied.get_mut());
+        }
+        self
+    }
+}
+
+/// A view into an existing entry of a [`SkipList`]. Part of [`Entry`].
+///
+/// The key must not be changed through [`get_mut`](Self::get_mut) or
+/// [`into_mut`](Self::into_mut), since the skiplist would be out of order.
+pub struct OccupiedEntry<'a, K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+    list: &'a mut SkipList<K, E>,
+    slot: Slot,
+    entry: NonNull<u8>,
+}
+
+impl<'a, K, E> OccupiedEntry<'a, K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+    /// Returns the key of the entry.
+    pub fn key(&self) -> &K {
+        self.get().key()
+    }
+
+    /// Returns a reference to the entry.
+    pub fn get(&self) -> &E {
+        // SAFETY: The entry is live while the skiplist is borrowed
+        unsafe { self.entry.cast::<E>().as_ref() }
+    }
+
+    /// Returns a mutable reference to the entry.
+    pub fn get_mut(&mut self) -> &mut E {
+        // SAFETY: The skiplist is borrowed mutably, so access is exclusive
+        unsafe { self.entry.cast::<E>().as_mut() }
+    }
+
+    /// Converts the view into a mutable reference that lives as long as the skiplist borrow.
+    pub fn into_mut(self) -> &'a mut E {
+        // SAFETY: The skiplist is borrowed mutably for 'a, so access is exclusive
+        unsafe { self.entry.cast::<E>().as_mut() }
+    }
+
+    /// Puts `entry` in place of the existing entry and returns the old one.
+    ///
+    /// The new entry takes over the old entry's tower, so no other node moves.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `entry` has a different key.
+    pub fn replace(&mut self, entry: Box<E>) -> Box<E> {
+        assert!(
+            entry.key() == self.key(),
+            "replacement entry has a different key than the occupied entry"
+        );
+        // SAFETY: The slot and entry were found by SkipList::entry and the
+        // skiplist is borrowed mutably since
+        unsafe {
+            let old = SkipList::swap(&self.slot, self.entry, entry);
+            self.entry = (&(*self.slot.update[0]).forward)[0].unwrap();
+            old
+        }
+    }
+
+    /// Removes the entry from the skiplist and returns it.
+    pub fn remove(self) -> Box<E> {
+        // SAFETY: The slot and entry were found by SkipList::entry and the
+        // skiplist is borrowed mutably since
+        unsafe { self.list.unlink(&self.slot.update, self.entry) }
+    }
+}
+
+/// A view into a vacant key of a [`SkipList`]. Part of [`Entry`].
+pub struct VacantEntry<'a, K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+    list: &'a mut SkipList<K, E>,
+    slot: Slot,
+    key: K,
+}
+
+impl<'a, K, E> VacantEntry<'a, K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+    /// Returns the key this entry was looked up with.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Takes ownership of the key.
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Inserts `entry` at the position found by the lookup, without searching
+    /// again, and returns a mutable reference to it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `entry` has a different key than the one looked up.
+    pub fn insert(mut self, entry: Box<E>) -> &'a mut E {
+        assert!(
+            *entry.key() == self.key,
+            "inserted entry has a different key than the vacant entry"
+        );
+        // SAFETY: The slot was found by SkipList::entry, the skiplist is
+        // borrowed mutably since, and the entry's key belongs there
+        unsafe {
+            let ptr = self.list.link(&mut self.slot, entry);
+            ptr.cast::<E>().as_mut()
+        }
+    }
+}
+
+/// A draining iterator over the entries of a [`SkipList`], in key order.
+///
+/// Created by [`SkipList::drain`]. Yields each entry as an owned `Box<E>` whose
+/// embedded [`SkipListNode`] has been reset, so it can be inserted again.
+pub struct Drain<'a, K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+    /// Entries detached from the skiplist; owned by this iterator.
+    cursor: RawCursor,
+
+    /// Ties the iterator to the exclusive borrow of the skiplist.
+    _marker: PhantomData<&'a mut SkipList<K, E>>,
+}
+
+impl<K, E> Iterator for Drain<'_, K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+    type Item = Box<E>;
+
+    fn next(&mut self) -> Option<Box<E>> {
+        // SAFETY: Every entry came from `Box::into_raw` in insert() and was
+        // detached from the skiplist by drain(), so the cursor owns it
+        unsafe {
+            self.cursor
+                .next_front::<E>()
+                .map(|ptr| RawCursor::take::<E>(ptr))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.cursor.remaining, Some(self.cursor.remaining))
+    }
+}
+
+impl<K, E> DoubleEndedIterator for Drain<'_, K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+    fn next_back(&mut self) -> Option<Box<E>> {
+        // SAFETY: As in next()
+        unsafe {
+            self.cursor
+                .next_back::<E>()
+                .map(|ptr| RawCursor::take::<E>(ptr))
+        }
+    }
+}
+
+impl<K, E> ExactSizeIterator for Drain<'_, K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+}
+
+impl<K, E> FusedIterator for Drain<'_, K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+}
+
+impl<K, E> Drop for Drain<'_, K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+    /// Drops any entries that were not consumed.
+    fn drop(&mut self) {
+        for entry in self.by_ref() {
+            drop(entry);
+        }
+    }
+}
+
+/// An owning iterator over the entries of a [`SkipList`], in key order.
+///
+/// Created by [`SkipList::into_iter`](IntoIterator::into_iter). Yields each
+/// entry as an owned `Box<E>`, like [`Drain`].
+pub struct IntoIter<K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+    /// Entries detached from the consumed skiplist; owned by this iterator.
+    cursor: RawCursor,
+
+    _marker: PhantomData<Box<E>>,
+}
+
+impl<K, E> Iterator for IntoIter<K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+    type Item = Box<E>;
+
+    fn next(&mut self) -> Option<Box<E>> {
+        // SAFETY: The entries were detached from the consumed skiplist, so the
+        // cursor owns them
+        unsafe {
+            self.cursor
+                .next_front::<E>()
+                .map(|ptr| RawCursor::take::<E>(ptr))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.cursor.remaining, Some(self.cursor.remaining))
+    }
+}
+
+impl<K, E> DoubleEndedIterator for IntoIter<K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+    fn next_back(&mut self) -> Option<Box<E>> {
+        // SAFETY: As in next()
+        unsafe {
+            self.cursor
+                .next_back::<E>()
+                .map(|ptr| RawCursor::take::<E>(ptr))
+        }
+    }
+}
+
+impl<K, E> ExactSizeIterator for IntoIter<K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+}
+
+impl<K, E> FusedIterator for IntoIter<K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+}
+
+impl<K, E> Drop for IntoIter<K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+    /// Drops any entries that were not consumed.
+    fn drop(&mut self) {
+        for entry in self.by_ref() {
+            drop(entry);
+        }
+    }
+}
+
+impl<K, E> IntoIterator for SkipList<K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+    type Item = Box<E>;
+    type IntoIter = IntoIter<K, E>;
+
+    /// Consumes the skiplist, yielding its entries in key order.
+    fn into_iter(mut self) -> IntoIter<K, E> {
+        IntoIter {
+            cursor: self.detach(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, K, E> IntoIterator for &'a SkipList<K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+    type Item = &'a E;
+    type IntoIter = Iter<'a, K, E>;
+
+    fn into_iter(self) -> Iter<'a, K, E> {
+        self.iter()
+    }
+}
+
+impl<'a, K, E> IntoIterator for &'a mut SkipList<K, E>
+where
+    K: Ord + Clone,
+    E: SkipListEntry<Key = K>,
+{
+    type Item = RefMut<'a, K, E>;
+    type IntoIter = IterMut<'a, K, E>;
+
+    fn into_iter(self) -> IterMut<'a, K, E> {
+        self.iter_mut()
+    }
+}
+
+impl<K, E> Default for SkipList<K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Configures and creates a [`SkipList`].
+///
+/// Created by [`SkipList::builder`]. Unset options keep the defaults of
+/// [`SkipList::new`]: `MAX_LEVEL` levels, `DEFAULT_PROBABILITY` promotion and
+/// a time-based seed.
+pub struct SkipListBuilder<K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+    max_level: usize,
+    probability: f64,
+    seed: Option<u64>,
+    levels: Option<Box<dyn LevelGenerator>>,
+    allow_duplicates: bool,
+    _marker: PhantomData<(K, E)>,
+}
+
+impl<K, E> SkipListBuilder<K, E>
+where
+    K: Ord,
+    E: SkipListEntry<Key = K>,
+{
+    /// Seeds the built-in level generator, making the layout reproducible.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets the maximum level a node can reach.
+    pub fn max_level(mut self, max_level: usize) -> Self {
+        self.max_level = max_level;
+        self
+    }
+
+    /// Sets the probability of promoting a node to the next level.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `probability` is not within `0.0..=1.0`.
+    pub fn probability(mut self, probability: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&probability),
+            "level promotion probability must be within 0.0..=1.0, got {}",
+            probability
+        );
+        self.probability = probability;
+        self
+    }
+
+    /// Replaces the built-in level generator.
+    ///
+    /// The seed and probability only configure the built-in generator, so
+    /// they are ignored once a custom one is supplied.
+    pub fn level_generator<G>(mut self, levels: G) -> Self
+    where
+        G: LevelGenerator + 'static,
+    {
+        self.levels = Some(Box::new(levels));
+        self
+    }
+
+    /// Allows several entries with the same key (multimap mode).
+    ///
+    /// Entries with equal keys are kept in insertion order: `insert` places a
+    /// new entry after every existing entry with its key, and key lookups such
+    /// as `get`, `remove` and `rank` find the earliest one. See
+    /// [`SkipList::get_all`], [`SkipList::count`], [`SkipList::remove_one`] and
+    /// [`SkipList::remove_all`].
+    pub fn allow_duplicates(mut self, allow: bool) -> Self {
+        self.allow_duplicates = allow;
+        self
+    }
+
+    /// Creates the empty skiplist.
+    pub fn build(self) -> SkipList<K, E> {
+        let levels = self.levels.unwrap_or_else(|| match self.seed {
+            Some(seed) => Box::new(XorShiftLevels::new(seed, self.probability)),
+            None => Box::new(XorShiftLevels::from_time(self.probability)),
+        });
+
+        SkipList {
+            head: SkipListNode::with_level(self.max_level),
+            len: 0,
+            level: 0,
+            max_level: self.max_level,
+            levels,
+            allow_duplicates: self.allow_duplicates,
+            _marker: PhantomData,
+        }
     }
 }
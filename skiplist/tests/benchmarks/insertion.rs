@@ -1,11 +1,10 @@
-use crate::benchmarks::{format_duration, format_ops_per_sec, time_operation, BenchItem};
-use skiplist::SkipList;
+use crate::benchmarks::{format_duration, format_ops_per_sec, new_list, time_operation, BenchItem};
 
 #[test]
 #[ignore]
 fn perf_insert_sequential() {
     const N: usize = 100_000;
-    let mut list = SkipList::new();
+    let mut list = new_list();
 
     let elapsed = time_operation(|| {
         for i in 0..N as i32 {
@@ -24,7 +23,7 @@ fn perf_insert_sequential() {
 #[ignore]
 fn perf_insert_reverse() {
     const N: usize = 100_000;
-    let mut list = SkipList::new();
+    let mut list = new_list();
 
     let elapsed = time_operation(|| {
         for i in (0..N as i32).rev() {
@@ -43,7 +42,7 @@ fn perf_insert_reverse() {
 #[ignore]
 fn perf_insert_random() {
     const N: usize = 100_000;
-    let mut list = SkipList::new();
+    let mut list = new_list();
 
     // Generate pseudo-random sequence using simple LCG
     let mut keys = Vec::with_capacity(N);
@@ -70,7 +69,7 @@ fn perf_insert_random() {
 #[ignore]
 fn perf_insert_duplicate_attempts() {
     const N: usize = 50_000;
-    let mut list = SkipList::new();
+    let mut list = new_list();
 
     // Pre-populate half
     for i in 0..N as i32 {
@@ -1,11 +1,11 @@
-use crate::benchmarks::{format_duration, format_ops_per_sec, time_operation, BenchItem};
-use skiplist::{SkipList, SkipListEntry};
+use crate::benchmarks::{format_duration, format_ops_per_sec, new_list, time_operation, BenchItem};
+use skiplist::SkipListEntry;
 
 #[test]
 #[ignore]
 fn perf_mixed_insert_lookup() {
     const N: usize = 50_000;
-    let mut list = SkipList::new();
+    let mut list = new_list();
 
     let elapsed = time_operation(|| {
         for i in 0..N as i32 {
@@ -29,7 +29,7 @@ fn perf_mixed_insert_lookup() {
 #[ignore]
 fn perf_mixed_insert_remove() {
     const N: usize = 50_000;
-    let mut list = SkipList::new();
+    let mut list = new_list();
 
     let elapsed = time_operation(|| {
         for i in 0..N as i32 {
@@ -57,7 +57,7 @@ fn perf_mixed_insert_remove() {
 #[ignore]
 fn perf_mixed_all_operations() {
     const N: usize = 30_000;
-    let mut list = SkipList::new();
+    let mut list = new_list();
 
     let elapsed = time_operation(|| {
         for i in 0..N as i32 {
@@ -93,7 +93,7 @@ fn perf_mixed_all_operations() {
 #[ignore]
 fn perf_producer_consumer_pattern() {
     const N: usize = 50_000;
-    let mut list = SkipList::new();
+    let mut list = new_list();
 
     let elapsed = time_operation(|| {
         for i in 0..N as i32 {
@@ -71,9 +71,17 @@ where
     start.elapsed().as_nanos()
 }
 
+/// Fixed seed so every run builds the same skiplist layout
+pub const BENCH_SEED: u64 = 0x5EED_B3AC;
+
+/// Helper to create an empty skiplist with a reproducible layout
+pub fn new_list() -> SkipList<i32, BenchItem> {
+    SkipList::builder().seed(BENCH_SEED).build()
+}
+
 /// Helper to create a pre-populated skiplist for benchmarks
 pub fn create_populated_list(size: usize) -> SkipList<i32, BenchItem> {
-    let mut list = SkipList::new();
+    let mut list = new_list();
     for i in 0..size as i32 {
         list.insert(Box::new(BenchItem::new(i))).unwrap();
     }
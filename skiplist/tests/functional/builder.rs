@@ -0,0 +1,160 @@
+use crate::functional::{expect_skiplist, TestItem};
+use skiplist::{LevelGenerator, SkipList, XorShiftLevels, MAX_LEVEL};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn levels_of(generator: &mut impl LevelGenerator, max_level: usize, count: usize) -> Vec<usize> {
+    (0..count)
+        .map(|_| generator.random_level(max_level))
+        .collect()
+}
+
+fn insert_keys(list: &mut SkipList<i32, TestItem>, keys: impl IntoIterator<Item = i32>) {
+    for key in keys {
+        assert!(list.insert(Box::new(TestItem::with_key(key))).is_ok());
+    }
+}
+
+/// Generator that always returns the same level and records what it was asked
+struct Fixed {
+    level: usize,
+    calls: Rc<RefCell<Vec<usize>>>,
+}
+
+impl LevelGenerator for Fixed {
+    fn random_level(&mut self, max_level: usize) -> usize {
+        self.calls.borrow_mut().push(max_level);
+        self.level
+    }
+}
+
+// ============================================================================
+// XorShiftLevels
+// ============================================================================
+
+#[test]
+fn same_seed_same_levels() {
+    let a = levels_of(&mut XorShiftLevels::new(7, 0.5), MAX_LEVEL, 1000);
+    let b = levels_of(&mut XorShiftLevels::new(7, 0.5), MAX_LEVEL, 1000);
+    let c = levels_of(&mut XorShiftLevels::new(8, 0.5), MAX_LEVEL, 1000);
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+}
+
+#[test]
+fn zero_seed_still_produces_levels() {
+    let levels = levels_of(&mut XorShiftLevels::new(0, 0.5), MAX_LEVEL, 1000);
+    assert!(levels.iter().any(|&level| level > 0));
+}
+
+#[test]
+fn probability_shapes_level_distribution() {
+    const DRAWS: usize = 100_000;
+    for p in [0.25, 0.5, 0.75] {
+        let levels = levels_of(&mut XorShiftLevels::new(12345, p), 64, DRAWS);
+        for k in 1..=3 {
+            let reached = levels.iter().filter(|&&level| level >= k).count();
+            let expected = p.powi(k as i32);
+            let observed = reached as f64 / DRAWS as f64;
+            assert!(
+                (observed - expected).abs() < 0.01,
+                "p = {}, level {}: expected {:.3}, observed {:.3}",
+                p,
+                k,
+                expected,
+                observed
+            );
+        }
+    }
+}
+
+#[test]
+fn extreme_probabilities() {
+    assert!(levels_of(&mut XorShiftLevels::new(1, 0.0), 8, 100)
+        .iter()
+        .all(|&level| level == 0));
+    assert!(levels_of(&mut XorShiftLevels::new(1, 1.0), 8, 100)
+        .iter()
+        .all(|&level| level == 8));
+}
+
+#[test]
+#[should_panic(expected = "level promotion probability must be within 0.0..=1.0")]
+fn generator_rejects_invalid_probability() {
+    XorShiftLevels::new(1, 1.5);
+}
+
+// ============================================================================
+// Builder
+// ============================================================================
+
+#[test]
+fn builder_defaults_work_like_new() {
+    let mut list: SkipList<i32, TestItem> = SkipList::builder().build();
+    insert_keys(&mut list, [3, 1, 2]);
+    expect_skiplist(&list, &[1, 2, 3]);
+}
+
+#[test]
+fn builder_with_all_options() {
+    for p in [0.0, 0.25, 0.5, 0.75, 1.0] {
+        let mut list: SkipList<i32, TestItem> = SkipList::builder()
+            .seed(99)
+            .max_level(6)
+            .probability(p)
+            .build();
+        insert_keys(&mut list, (0..500).rev());
+        for key in (0..500).step_by(3) {
+            assert!(list.remove(&key).is_some());
+        }
+
+        let expected: Vec<i32> = (0..500).filter(|k| k % 3 != 0).collect();
+        expect_skiplist(&list, &expected);
+        assert_eq!(list.rank(&499), Some(expected.len() - 1));
+    }
+}
+
+#[test]
+#[should_panic(expected = "level promotion probability must be within 0.0..=1.0")]
+fn builder_rejects_invalid_probability() {
+    let _ = SkipList::<i32, TestItem>::builder().probability(-0.1);
+}
+
+#[test]
+fn custom_generator_is_used() {
+    let calls = Rc::new(RefCell::new(Vec::new()));
+    let generator = Fixed {
+        level: 2,
+        calls: Rc::clone(&calls),
+    };
+    let mut list: SkipList<i32, TestItem> = SkipList::builder()
+        .max_level(5)
+        .seed(1)
+        .level_generator(generator)
+        .build();
+
+    insert_keys(&mut list, [5, 3, 9]);
+    assert_eq!(*calls.borrow(), vec![5, 5, 5]);
+    expect_skiplist(&list, &[3, 5, 9]);
+
+    // Duplicates are rejected before a level is drawn
+    assert!(list.insert(Box::new(TestItem::with_key(3))).is_err());
+    assert_eq!(calls.borrow().len(), 3);
+}
+
+#[test]
+fn custom_generator_levels_are_clamped() {
+    let mut list: SkipList<i32, TestItem> = SkipList::builder()
+        .max_level(3)
+        .level_generator(Fixed {
+            level: 1000,
+            calls: Rc::default(),
+        })
+        .build();
+
+    insert_keys(&mut list, 0..100);
+    expect_skiplist(&list, &(0..100).collect::<Vec<_>>());
+    assert_eq!(list.range_by_index(40..43).count(), 3);
+    assert_eq!(list.remove_range(10..90).len(), 80);
+    expect_skiplist(&list, &(0..10).chain(90..100).collect::<Vec<_>>());
+}
@@ -0,0 +1,399 @@
+use crate::functional::Rng;
+use skiplist::concurrent::Entry;
+use skiplist::ConcurrentSkipList;
+use std::collections::BTreeMap;
+use std::ops::Bound;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+const THREADS: usize = 8;
+
+/// Value that counts how many times it has been dropped
+struct Tracked {
+    id: usize,
+    drops: Arc<AtomicUsize>,
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.drops.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+fn keys_of(map: &ConcurrentSkipList<u64, u64>) -> Vec<u64> {
+    map.iter().map(|entry| *entry.key()).collect()
+}
+
+// ============================================================================
+// Single-threaded behaviour
+// ============================================================================
+
+#[test]
+fn operations_match_btreemap() {
+    let mut rng = Rng(0xC0FF_EE11);
+    let map = ConcurrentSkipList::new();
+    let mut reference = BTreeMap::new();
+
+    for step in 0..20_000u64 {
+        let key = rng.next() % 500;
+        match rng.next() % 3 {
+            0 => {
+                let inserted = map.insert(key, step).is_ok();
+                assert_eq!(inserted, !reference.contains_key(&key));
+                reference.entry(key).or_insert(step);
+            }
+            1 => {
+                let removed = map.remove(&key).map(|entry| *entry.value());
+                assert_eq!(removed, reference.remove(&key));
+            }
+            _ => {
+                let found = map.get(&key).map(|entry| *entry.value());
+                assert_eq!(found, reference.get(&key).copied());
+            }
+        }
+
+        if step % 2000 == 0 {
+            assert_eq!(map.len(), reference.len());
+            assert_eq!(keys_of(&map), reference.keys().copied().collect::<Vec<_>>());
+        }
+    }
+
+    let pairs: Vec<(u64, u64)> = map
+        .iter()
+        .map(|entry| (*entry.key(), *entry.value()))
+        .collect();
+    assert_eq!(pairs, reference.into_iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn insert_rejects_duplicates() {
+    let map = ConcurrentSkipList::new();
+    assert!(map.insert(1, "one").is_ok());
+    assert_eq!(map.insert(1, "uno"), Err((1, "uno")));
+    assert_eq!(*map.get(&1).unwrap().value(), "one");
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn ranges_and_first() {
+    let map = ConcurrentSkipList::new();
+    assert!(map.first().is_none());
+    for key in (0..100u64).rev() {
+        map.insert(key * 2, ()).unwrap();
+    }
+
+    assert_eq!(map.first().map(|entry| *entry.key()), Some(0));
+    let keys: Vec<u64> = map.range(9..=15).map(|entry| *entry.key()).collect();
+    assert_eq!(keys, vec![10, 12, 14]);
+    let keys: Vec<u64> = map.range(190..).map(|entry| *entry.key()).collect();
+    assert_eq!(keys, vec![190, 192, 194, 196, 198]);
+    assert_eq!(map.range(..0).count(), 0);
+    assert_eq!((&map).into_iter().count(), 100);
+}
+
+#[test]
+fn string_keys_are_found_by_str() {
+    let map = ConcurrentSkipList::new();
+    for name in ["carol", "alice", "bob"] {
+        map.insert(name.to_string(), name.len()).unwrap();
+    }
+
+    assert_eq!(map.get("bob").map(|entry| *entry.value()), Some(3));
+    assert!(map.contains_key("alice"));
+    let names: Vec<String> = map
+        .range::<str, _>((Bound::Included("b"), Bound::Unbounded))
+        .map(|entry| entry.key().clone())
+        .collect();
+    assert_eq!(names, vec!["bob", "carol"]);
+    assert!(map.remove("carol").is_some());
+    assert!(!map.contains_key("carol"));
+}
+
+#[test]
+fn removed_entry_stays_readable() {
+    let map = ConcurrentSkipList::new();
+    map.insert(7, "seven".to_string()).unwrap();
+
+    let held: Entry<'_, i32, String> = map.get(&7).unwrap();
+    assert!(!held.is_removed());
+
+    // Churn enough to trigger collection while the entry is still held
+    let removed = map.remove(&7).unwrap();
+    for key in 100..1000 {
+        map.insert(key, key.to_string()).unwrap();
+        map.remove(&key).unwrap();
+    }
+
+    assert!(held.is_removed());
+    assert_eq!(held.value(), "seven");
+    assert_eq!(removed.key(), held.key());
+    assert!(map.is_empty());
+}
+
+// ============================================================================
+// Concurrent writers
+// ============================================================================
+
+#[test]
+fn concurrent_disjoint_inserts() {
+    let map = ConcurrentSkipList::new();
+    let per_thread = 5000u64;
+
+    thread::scope(|scope| {
+        for thread in 0..THREADS as u64 {
+            let map = &map;
+            scope.spawn(move || {
+                for i in 0..per_thread {
+                    let key = i * THREADS as u64 + thread;
+                    assert!(map.insert(key, key * 10).is_ok());
+                }
+            });
+        }
+    });
+
+    let total = per_thread * THREADS as u64;
+    assert_eq!(map.len(), total as usize);
+    assert_eq!(keys_of(&map), (0..total).collect::<Vec<_>>());
+    for key in 0..total {
+        assert_eq!(map.get(&key).map(|entry| *entry.value()), Some(key * 10));
+    }
+}
+
+#[test]
+fn racing_inserts_of_same_key_have_one_winner() {
+    let map = ConcurrentSkipList::new();
+    let wins = AtomicUsize::new(0);
+    let barrier = Barrier::new(THREADS);
+
+    thread::scope(|scope| {
+        for thread in 0..THREADS as u64 {
+            let (map, wins, barrier) = (&map, &wins, &barrier);
+            scope.spawn(move || {
+                barrier.wait();
+                for key in 0..2000u64 {
+                    if map.insert(key, thread).is_ok() {
+                        wins.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            });
+        }
+    });
+
+    assert_eq!(wins.load(Ordering::Relaxed), 2000);
+    assert_eq!(map.len(), 2000);
+    assert_eq!(keys_of(&map), (0..2000).collect::<Vec<_>>());
+}
+
+#[test]
+fn racing_removes_of_same_key_have_one_winner() {
+    let map = ConcurrentSkipList::new();
+    for key in 0..5000u64 {
+        map.insert(key, key).unwrap();
+    }
+    let removed = AtomicUsize::new(0);
+    let barrier = Barrier::new(THREADS);
+
+    thread::scope(|scope| {
+        for _ in 0..THREADS {
+            let (map, removed, barrier) = (&map, &removed, &barrier);
+            scope.spawn(move || {
+                barrier.wait();
+                for key in 0..5000u64 {
+                    if let Some(entry) = map.remove(&key) {
+                        assert_eq!(*entry.value(), key);
+                        removed.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            });
+        }
+    });
+
+    assert_eq!(removed.load(Ordering::Relaxed), 5000);
+    assert!(map.is_empty());
+    assert!(map.iter().next().is_none());
+}
+
+#[test]
+fn mixed_workload_keeps_per_thread_keys_consistent() {
+    let map = ConcurrentSkipList::new();
+
+    // Each thread owns the keys congruent to its index, so it can check its
+    // own view exactly while the others churn around it
+    let finals: Vec<BTreeMap<u64, u64>> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..THREADS as u64)
+            .map(|thread| {
+                let map = &map;
+                scope.spawn(move || {
+                    let mut rng = Rng(0x9E37_79B9 + thread);
+                    let mut mine = BTreeMap::new();
+                    for step in 0..20_000u64 {
+                        let key = (rng.next() % 1000) * THREADS as u64 + thread;
+                        if rng.coin() {
+                            let inserted = map.insert(key, step).is_ok();
+                            assert_eq!(inserted, !mine.contains_key(&key));
+                            mine.entry(key).or_insert(step);
+                        } else {
+                            let removed = map.remove(&key).map(|entry| *entry.value());
+                            assert_eq!(removed, mine.remove(&key));
+                        }
+                    }
+                    mine
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    let mut expected: BTreeMap<u64, u64> = BTreeMap::new();
+    for mine in finals {
+        expected.extend(mine);
+    }
+    let pairs: Vec<(u64, u64)> = map
+        .iter()
+        .map(|entry| (*entry.key(), *entry.value()))
+        .collect();
+    assert_eq!(pairs, expected.into_iter().collect::<Vec<_>>());
+    assert_eq!(map.len(), pairs.len());
+}
+
+#[test]
+fn reinserting_removed_keys_keeps_towers_consistent() {
+    // A handful of hot keys are removed and reinserted constantly, so new
+    // nodes are often linked while a removed node with the same key is
+    // still in an upper level
+    let map = ConcurrentSkipList::new();
+
+    thread::scope(|scope| {
+        for thread in 0..THREADS as u64 {
+            let map = &map;
+            scope.spawn(move || {
+                let mut rng = Rng(0x7777 + thread);
+                for _ in 0..20_000 {
+                    let key = rng.next() % 8;
+                    if rng.coin() {
+                        let _ = map.insert(key, key);
+                    } else {
+                        map.remove(&key);
+                    }
+                    for entry in map.range(key..) {
+                        assert!(*entry.key() >= key);
+                    }
+                }
+            });
+        }
+    });
+
+    let keys = keys_of(&map);
+    assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(keys.len(), map.len());
+    for key in 0..8 {
+        assert_eq!(map.contains_key(&key), keys.contains(&key));
+    }
+}
+
+// ============================================================================
+// Readers during writes
+// ============================================================================
+
+#[test]
+fn readers_see_sorted_snapshots_during_writes() {
+    let map = ConcurrentSkipList::new();
+    // Even keys stay put for the whole test; odd keys come and go
+    for key in (0..4000u64).step_by(2) {
+        map.insert(key, key).unwrap();
+    }
+    let done = AtomicUsize::new(0);
+
+    thread::scope(|scope| {
+        for thread in 0..THREADS as u64 / 2 {
+            let (map, done) = (&map, &done);
+            scope.spawn(move || {
+                let mut rng = Rng(0xABCD + thread);
+                for _ in 0..20_000 {
+                    let key = (rng.next() % 2000) * 2 + 1;
+                    if rng.coin() {
+                        let _ = map.insert(key, key);
+                    } else {
+                        map.remove(&key);
+                    }
+                }
+                done.fetch_add(1, Ordering::Release);
+            });
+        }
+
+        for _ in 0..THREADS / 2 {
+            let (map, done) = (&map, &done);
+            scope.spawn(move || {
+                while done.load(Ordering::Acquire) < THREADS / 2 {
+                    let keys: Vec<u64> = map.iter().map(|entry| *entry.key()).collect();
+                    assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+
+                    let evens = keys.iter().filter(|key| *key % 2 == 0).count();
+                    assert_eq!(evens, 2000);
+
+                    for key in (0..4000u64).step_by(250) {
+                        assert_eq!(map.get(&key).map(|entry| *entry.value()), Some(key));
+                    }
+                    let window: Vec<u64> = map
+                        .range(1000..1100)
+                        .map(|entry| *entry.key())
+                        .filter(|key| key % 2 == 0)
+                        .collect();
+                    assert_eq!(window, (1000..1100).step_by(2).collect::<Vec<_>>());
+                }
+            });
+        }
+    });
+
+    let keys = keys_of(&map);
+    assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(keys.len(), map.len());
+}
+
+// ============================================================================
+// Memory reclamation
+// ============================================================================
+
+#[test]
+fn every_value_is_dropped_exactly_once() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let created = AtomicUsize::new(0);
+    let rejected = AtomicUsize::new(0);
+    let map = ConcurrentSkipList::new();
+
+    thread::scope(|scope| {
+        for thread in 0..THREADS as u64 {
+            let (map, drops, created, rejected) = (&map, &drops, &created, &rejected);
+            scope.spawn(move || {
+                let mut rng = Rng(0x5151 + thread);
+                for _ in 0..10_000 {
+                    let key = rng.next() % 256;
+                    if rng.coin() {
+                        let id = created.fetch_add(1, Ordering::Relaxed);
+                        let value = Tracked {
+                            id,
+                            drops: Arc::clone(drops),
+                        };
+                        // A rejected value comes back to us and is dropped here
+                        if map.insert(key, value).is_err() {
+                            rejected.fetch_add(1, Ordering::Relaxed);
+                        }
+                    } else if let Some(entry) = map.remove(&key) {
+                        assert!(entry.value().id < created.load(Ordering::Relaxed));
+                    }
+                }
+            });
+        }
+    });
+
+    // Removed values are reclaimed while the list is still in use
+    let live = map.len();
+    let total = created.load(Ordering::Relaxed);
+    let rejected = rejected.load(Ordering::Relaxed);
+    assert!(drops.load(Ordering::Relaxed) > rejected);
+    assert!(drops.load(Ordering::Relaxed) <= total - live);
+
+    drop(map);
+    assert_eq!(drops.load(Ordering::Relaxed), total);
+}
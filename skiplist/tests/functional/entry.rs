@@ -0,0 +1,235 @@
+use crate::functional::{expect_skiplist, skiplist_of, Rng, TestItem};
+use skiplist::{Entry, SkipList};
+use std::collections::BTreeMap;
+
+fn item(key: i32, value: &str) -> Box<TestItem> {
+    Box::new(TestItem::new(key, value.to_string()))
+}
+
+fn values_of(list: &SkipList<i32, TestItem>) -> Vec<String> {
+    list.iter().map(|item| item.value.clone()).collect()
+}
+
+// ============================================================================
+// replace
+// ============================================================================
+
+#[test]
+fn replace_inserts_missing_keys() {
+    let mut list = skiplist_of(&[10, 30]);
+    assert!(list.replace(item(20, "new")).is_none());
+
+    expect_skiplist(&list, &[10, 20, 30]);
+    assert_eq!(list.get(&20).unwrap().value, "new");
+}
+
+#[test]
+fn replace_swaps_existing_entry() {
+    let mut list = skiplist_of(&[10, 20, 30]);
+    let old = list.replace(item(20, "new")).unwrap();
+
+    assert_eq!(old.key, 20);
+    assert_eq!(old.value, "value_20");
+    expect_skiplist(&list, &[10, 20, 30]);
+    assert_eq!(list.get(&20).unwrap().value, "new");
+}
+
+#[test]
+fn replace_keeps_positions_and_both_directions() {
+    let keys: Vec<i32> = (0..500).collect();
+    let mut list = skiplist_of(&keys);
+
+    for key in (0..500).step_by(3) {
+        assert!(list.replace(item(key, "replaced")).is_some());
+    }
+
+    expect_skiplist(&list, &keys);
+    for (index, key) in keys.iter().enumerate() {
+        assert_eq!(list.get_by_index(index).unwrap().key, *key);
+        assert_eq!(list.rank(key), Some(index));
+    }
+    let reversed: Vec<i32> = list.iter().rev().map(|item| item.key).collect();
+    assert_eq!(reversed, keys.iter().rev().copied().collect::<Vec<_>>());
+    let replaced = list.iter().filter(|item| item.value == "replaced").count();
+    assert_eq!(replaced, 167);
+}
+
+#[test]
+fn replaced_entries_can_be_reinserted() {
+    let mut list = skiplist_of(&(0..100).collect::<Vec<_>>());
+    let mut other = SkipList::new();
+
+    for key in 0..100 {
+        let old = list.replace(item(key, "fresh")).unwrap();
+        assert!(other.insert(old).is_ok());
+    }
+
+    expect_skiplist(&list, &(0..100).collect::<Vec<_>>());
+    expect_skiplist(&other, &(0..100).collect::<Vec<_>>());
+    assert!(other.iter().all(|item| item.value.starts_with("value_")));
+}
+
+#[test]
+fn replace_in_multimap_targets_earliest_entry() {
+    let mut list = SkipList::builder().allow_duplicates(true).build();
+    for value in ["a", "b", "c"] {
+        list.insert(item(5, value)).unwrap();
+    }
+
+    let old = list.replace(item(5, "z")).unwrap();
+    assert_eq!(old.value, "a");
+    assert_eq!(values_of(&list), vec!["z", "b", "c"]);
+    assert_eq!(list.count(&5), 3);
+}
+
+// ============================================================================
+// entry
+// ============================================================================
+
+#[test]
+fn entry_reports_occupied_and_vacant() {
+    let mut list = skiplist_of(&[1, 2, 3]);
+
+    match list.entry(2) {
+        Entry::Occupied(occupied) => assert_eq!(occupied.get().value, "value_2"),
+        Entry::Vacant(_) => panic!("expected key 2 to be occupied"),
+    }
+    match list.entry(4) {
+        Entry::Occupied(_) => panic!("expected key 4 to be vacant"),
+        Entry::Vacant(vacant) => {
+            assert_eq!(*vacant.key(), 4);
+            assert_eq!(vacant.into_key(), 4);
+        }
+    }
+    expect_skiplist(&list, &[1, 2, 3]);
+}
+
+#[test]
+fn or_insert_with_counts_occurrences() {
+    let mut list = SkipList::new();
+    let words = [3, 1, 3, 2, 3, 1];
+
+    for &key in &words {
+        list.entry(key)
+            .or_insert_with(|| item(key, ""))
+            .value
+            .push('x');
+    }
+
+    expect_skiplist(&list, &[1, 2, 3]);
+    assert_eq!(values_of(&list), vec!["xx", "x", "xxx"]);
+}
+
+#[test]
+fn or_insert_keeps_existing_entry() {
+    let mut list = skiplist_of(&[7]);
+    let existing = list.entry(7).or_insert(item(7, "ignored"));
+    assert_eq!(existing.value, "value_7");
+
+    let inserted = list.entry(8).or_insert_with_key(|&key| item(key, "made"));
+    assert_eq!(inserted.key, 8);
+    assert_eq!(list.get(&8).unwrap().value, "made");
+}
+
+#[test]
+fn and_modify_only_touches_occupied_entries() {
+    let mut list = skiplist_of(&[1]);
+
+    list.entry(1)
+        .and_modify(|item| item.value = "modified".to_string())
+        .or_insert(item(1, "unused"));
+    list.entry(2)
+        .and_modify(|item| item.value = "modified".to_string())
+        .or_insert(item(2, "inserted"));
+
+    assert_eq!(values_of(&list), vec!["modified", "inserted"]);
+}
+
+#[test]
+fn occupied_entry_remove_and_replace() {
+    let mut list = skiplist_of(&[1, 2, 3, 4]);
+
+    if let Entry::Occupied(occupied) = list.entry(2) {
+        assert_eq!(occupied.remove().key, 2);
+    }
+    expect_skiplist(&list, &[1, 3, 4]);
+
+    if let Entry::Occupied(mut occupied) = list.entry(3) {
+        let old = occupied.replace(item(3, "first"));
+        assert_eq!(old.value, "value_3");
+        let old = occupied.replace(item(3, "second"));
+        assert_eq!(old.value, "first");
+        occupied.get_mut().value.push('!');
+        assert_eq!(*occupied.key(), 3);
+    }
+    expect_skiplist(&list, &[1, 3, 4]);
+    assert_eq!(list.get(&3).unwrap().value, "second!");
+}
+
+#[test]
+#[should_panic(expected = "different key than the vacant entry")]
+fn vacant_insert_rejects_other_keys() {
+    let mut list = skiplist_of(&[1, 5]);
+    if let Entry::Vacant(vacant) = list.entry(3) {
+        vacant.insert(item(4, "wrong"));
+    }
+}
+
+#[test]
+#[should_panic(expected = "different key than the occupied entry")]
+fn occupied_replace_rejects_other_keys() {
+    let mut list = skiplist_of(&[1, 5]);
+    if let Entry::Occupied(mut occupied) = list.entry(5) {
+        occupied.replace(item(6, "wrong"));
+    }
+}
+
+#[test]
+fn entry_operations_match_btreemap() {
+    let mut rng = Rng(0x5EED_1234);
+    let mut list = SkipList::new();
+    let mut reference = BTreeMap::new();
+
+    for step in 0..5000 {
+        let key = (rng.next() % 400) as i32;
+        let value = format!("v{}", step);
+        match rng.next() % 4 {
+            0 => {
+                let old = list.replace(item(key, &value)).map(|old| old.value);
+                assert_eq!(old, reference.insert(key, value));
+            }
+            1 => match list.entry(key) {
+                Entry::Occupied(occupied) => {
+                    assert_eq!(Some(occupied.remove().value), reference.remove(&key));
+                }
+                Entry::Vacant(vacant) => {
+                    assert!(!reference.contains_key(&key));
+                    vacant.insert(item(key, &value));
+                    reference.insert(key, value);
+                }
+            },
+            _ => {
+                let entry = list.entry(key).or_insert_with(|| item(key, ""));
+                entry.value.push('+');
+                reference.entry(key).or_default().push('+');
+            }
+        }
+
+        if step % 500 == 0 {
+            let keys: Vec<i32> = reference.keys().copied().collect();
+            expect_skiplist(&list, &keys);
+            for (index, (key, value)) in reference.iter().enumerate() {
+                let found = list.get_by_index(index).unwrap();
+                assert_eq!((&found.key, &found.value), (key, value));
+            }
+        }
+    }
+
+    let expected: Vec<String> = reference.values().cloned().collect();
+    assert_eq!(values_of(&list), expected);
+    let reversed: Vec<i32> = list.iter().rev().map(|item| item.key).collect();
+    assert_eq!(
+        reversed,
+        reference.keys().rev().copied().collect::<Vec<_>>()
+    );
+}
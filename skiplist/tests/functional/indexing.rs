@@ -0,0 +1,219 @@
+use crate::functional::{expect_skiplist, skiplist_of, Rng, TestItem};
+use skiplist::SkipList;
+use std::collections::VecDeque;
+
+fn keys_of<'a>(items: impl Iterator<Item = &'a TestItem>) -> Vec<i32> {
+    items.map(|item| item.key).collect()
+}
+
+/// Checks every positional lookup against the sorted reference keys
+fn check_positions(list: &SkipList<i32, TestItem>, keys: &[i32]) {
+    assert_eq!(list.len(), keys.len());
+    for (index, key) in keys.iter().enumerate() {
+        assert_eq!(list.get_by_index(index).map(|item| item.key), Some(*key));
+        assert_eq!(list.rank(key), Some(index), "rank of {}", key);
+    }
+    assert!(list.get_by_index(keys.len()).is_none());
+}
+
+// ============================================================================
+// get_by_index and rank
+// ============================================================================
+
+#[test]
+fn get_by_index_in_key_order() {
+    let list = skiplist_of(&[50, 10, 40, 20, 30]);
+    check_positions(&list, &[10, 20, 30, 40, 50]);
+    assert!(list.get_by_index(usize::MAX).is_none());
+}
+
+#[test]
+fn rank_of_missing_keys_is_none() {
+    let list = skiplist_of(&[10, 20, 30]);
+    assert_eq!(list.rank(&5), None);
+    assert_eq!(list.rank(&25), None);
+    assert_eq!(list.rank(&35), None);
+
+    let empty: SkipList<i32, TestItem> = SkipList::new();
+    assert_eq!(empty.rank(&0), None);
+    assert!(empty.get_by_index(0).is_none());
+}
+
+#[test]
+fn positions_shift_after_insert_and_remove() {
+    let mut list = skiplist_of(&[10, 20, 30]);
+    list.insert(Box::new(TestItem::with_key(15))).unwrap();
+    check_positions(&list, &[10, 15, 20, 30]);
+
+    list.remove(&10);
+    check_positions(&list, &[15, 20, 30]);
+
+    // A rejected duplicate leaves every position untouched
+    assert!(list.insert(Box::new(TestItem::with_key(20))).is_err());
+    check_positions(&list, &[15, 20, 30]);
+}
+
+#[test]
+fn positions_after_remove_range_and_clear() {
+    let mut list = skiplist_of(&(0..500).collect::<Vec<_>>());
+    list.remove_range(100..400);
+    let expected: Vec<i32> = (0..100).chain(400..500).collect();
+    check_positions(&list, &expected);
+
+    list.clear();
+    check_positions(&list, &[]);
+    for key in [3, 1, 2] {
+        list.insert(Box::new(TestItem::with_key(key))).unwrap();
+    }
+    check_positions(&list, &[1, 2, 3]);
+}
+
+// ============================================================================
+// remove_by_index
+// ============================================================================
+
+#[test]
+fn remove_by_index_returns_entry() {
+    let mut list = skiplist_of(&[10, 20, 30, 40]);
+
+    assert_eq!(list.remove_by_index(1).map(|item| item.key), Some(20));
+    check_positions(&list, &[10, 30, 40]);
+
+    assert_eq!(list.remove_by_index(2).map(|item| item.key), Some(40));
+    assert_eq!(list.remove_by_index(0).map(|item| item.key), Some(10));
+    assert!(list.remove_by_index(1).is_none());
+    expect_skiplist(&list, &[30]);
+}
+
+#[test]
+fn removed_by_index_can_be_reinserted() {
+    let mut source = skiplist_of(&[1, 2, 3]);
+    let mut target = SkipList::new();
+    while let Some(item) = source.remove_by_index(0) {
+        assert!(target.insert(item).is_ok());
+    }
+    expect_skiplist(&source, &[]);
+    check_positions(&target, &[1, 2, 3]);
+}
+
+// ============================================================================
+// range_by_index
+// ============================================================================
+
+#[test]
+fn range_by_index_pages() {
+    let list = skiplist_of(&(0..25).map(|k| k * 10).collect::<Vec<_>>());
+
+    assert_eq!(keys_of(list.range_by_index(0..3)), vec![0, 10, 20]);
+    assert_eq!(keys_of(list.range_by_index(10..=12)), vec![100, 110, 120]);
+    assert_eq!(keys_of(list.range_by_index(22..)), vec![220, 230, 240]);
+    assert_eq!(keys_of(list.range_by_index(..2)), vec![0, 10]);
+    assert_eq!(list.range_by_index(..).len(), 25);
+
+    // Pages past the end are clamped
+    assert_eq!(
+        keys_of(list.range_by_index(20..30)),
+        vec![200, 210, 220, 230, 240]
+    );
+    assert_eq!(keys_of(list.range_by_index(30..40)), vec![]);
+    assert_eq!(keys_of(list.range_by_index(5..5)), vec![]);
+}
+
+#[test]
+fn range_by_index_double_ended() {
+    let list = skiplist_of(&(0..10).collect::<Vec<_>>());
+    let mut iter = list.range_by_index(3..8);
+    assert_eq!(iter.len(), 5);
+    assert_eq!(iter.next_back().unwrap().key, 7);
+    assert_eq!(iter.next().unwrap().key, 3);
+    assert_eq!(keys_of(iter.rev()), vec![6, 5, 4]);
+}
+
+#[test]
+fn range_by_index_random_interleaving() {
+    let mut rng = Rng(0x1DE_A5ED);
+    let mut list = SkipList::new();
+    let mut keys = Vec::new();
+    while keys.len() < 3000 {
+        let key = rng.below(1_000_000) as i32;
+        if list.insert(Box::new(TestItem::with_key(key))).is_ok() {
+            keys.push(key);
+        }
+    }
+    keys.sort();
+
+    for _ in 0..200 {
+        let start = rng.below(3100);
+        let end = start + rng.below(300);
+        let mut reference: VecDeque<i32> = keys
+            .iter()
+            .skip(start)
+            .take(end.saturating_sub(start))
+            .copied()
+            .collect();
+
+        let mut iter = list.range_by_index(start..end);
+        while !reference.is_empty() {
+            assert_eq!(iter.len(), reference.len());
+            if rng.next().is_multiple_of(2) {
+                assert_eq!(iter.next_back().unwrap().key, reference.pop_back().unwrap());
+            } else {
+                assert_eq!(iter.next().unwrap().key, reference.pop_front().unwrap());
+            }
+        }
+        assert!(iter.next().is_none());
+        assert!(iter.next_back().is_none());
+    }
+}
+
+// ============================================================================
+// Randomized maintenance
+// ============================================================================
+
+#[test]
+fn positions_survive_random_operations() {
+    let mut rng = Rng(0xC0DE_1234);
+    let mut list = SkipList::new();
+    let mut reference: Vec<i32> = Vec::new();
+
+    for round in 0..3000u32 {
+        match rng.below(10) {
+            0..=4 => {
+                let key = rng.below(2000) as i32;
+                let inserted = list.insert(Box::new(TestItem::with_key(key))).is_ok();
+                if let Err(index) = reference.binary_search(&key) {
+                    assert!(inserted);
+                    reference.insert(index, key);
+                } else {
+                    assert!(!inserted);
+                }
+            }
+            5 | 6 => {
+                let key = rng.below(2000) as i32;
+                let removed = list.remove(&key).is_some();
+                if let Ok(index) = reference.binary_search(&key) {
+                    assert!(removed);
+                    reference.remove(index);
+                } else {
+                    assert!(!removed);
+                }
+            }
+            7 | 8 if !reference.is_empty() => {
+                let index = rng.below(reference.len());
+                let removed = list.remove_by_index(index).unwrap();
+                assert_eq!(removed.key, reference.remove(index));
+            }
+            _ => {
+                let start = rng.below(2000) as i32;
+                let end = start + rng.below(50) as i32;
+                list.remove_range(start..end);
+                reference.retain(|key| !(start..end).contains(key));
+            }
+        }
+
+        if round.is_multiple_of(100) {
+            check_positions(&list, &reference);
+        }
+    }
+    check_positions(&list, &reference);
+}
@@ -0,0 +1,211 @@
+use crate::functional::{expect_skiplist, skiplist_of, Rng, TestItem};
+use skiplist::{SkipList, SkipListEntry};
+use std::collections::VecDeque;
+
+fn keys_of<'a>(items: impl Iterator<Item = &'a TestItem>) -> Vec<i32> {
+    items.map(|item| item.key).collect()
+}
+
+// ============================================================================
+// Borrowing iteration
+// ============================================================================
+
+#[test]
+fn iter_empty() {
+    let list: SkipList<i32, TestItem> = SkipList::new();
+    assert_eq!(list.iter().next().map(|item| item.key), None);
+    assert_eq!(list.iter().next_back().map(|item| item.key), None);
+    assert_eq!(list.iter().len(), 0);
+}
+
+#[test]
+fn iter_forward_in_key_order() {
+    let list = skiplist_of(&[50, 10, 40, 20, 30]);
+    assert_eq!(keys_of(list.iter()), vec![10, 20, 30, 40, 50]);
+
+    // `&SkipList` works in for loops
+    let mut seen = Vec::new();
+    for item in &list {
+        seen.push(item.key);
+    }
+    assert_eq!(seen, vec![10, 20, 30, 40, 50]);
+}
+
+#[test]
+fn iter_backward_in_reverse_key_order() {
+    let list = skiplist_of(&[50, 10, 40, 20, 30]);
+    assert_eq!(keys_of(list.iter().rev()), vec![50, 40, 30, 20, 10]);
+}
+
+#[test]
+fn iter_exact_size() {
+    let list = skiplist_of(&[1, 2, 3, 4]);
+    let mut iter = list.iter();
+    assert_eq!(iter.len(), 4);
+    iter.next();
+    iter.next_back();
+    assert_eq!(iter.size_hint(), (2, Some(2)));
+    assert_eq!(keys_of(iter), vec![2, 3]);
+}
+
+#[test]
+fn iter_both_ends_meet_without_crossing() {
+    let list = skiplist_of(&[1, 2, 3, 4, 5]);
+    let mut iter = list.iter();
+
+    assert_eq!(iter.next().unwrap().key, 1);
+    assert_eq!(iter.next_back().unwrap().key, 5);
+    assert_eq!(iter.next_back().unwrap().key, 4);
+    assert_eq!(iter.next().unwrap().key, 2);
+    assert_eq!(iter.next().unwrap().key, 3);
+    assert!(iter.next().is_none());
+    assert!(iter.next_back().is_none());
+}
+
+#[test]
+fn iter_random_interleaving_matches_reference() {
+    let mut rng = Rng(0xBADC_0FFE);
+    for size in [1, 2, 17, 200, 2000] {
+        let mut list = SkipList::new();
+        let mut expected: Vec<i32> = Vec::new();
+        while expected.len() < size {
+            let key = (rng.next() % 1_000_000) as i32;
+            if list.insert(Box::new(TestItem::with_key(key))).is_ok() {
+                expected.push(key);
+            }
+        }
+        expected.sort();
+
+        let mut reference: VecDeque<i32> = expected.into_iter().collect();
+        let mut iter = list.iter();
+        while !reference.is_empty() {
+            assert_eq!(iter.len(), reference.len());
+            if rng.next().is_multiple_of(3) {
+                assert_eq!(iter.next_back().unwrap().key, reference.pop_back().unwrap());
+            } else {
+                assert_eq!(iter.next().unwrap().key, reference.pop_front().unwrap());
+            }
+        }
+        assert!(iter.next().is_none());
+        assert!(iter.next_back().is_none());
+    }
+}
+
+#[test]
+fn iter_is_cloneable() {
+    let list = skiplist_of(&[1, 2, 3]);
+    let mut iter = list.iter();
+    iter.next();
+
+    let copy = iter.clone();
+    assert_eq!(keys_of(iter), vec![2, 3]);
+    assert_eq!(keys_of(copy.rev()), vec![3, 2]);
+}
+
+#[test]
+fn iter_after_removals() {
+    let mut list = skiplist_of(&(0..100).collect::<Vec<_>>());
+    for key in (0..100).filter(|k| k % 3 != 0) {
+        list.remove(&key);
+    }
+
+    let expected: Vec<i32> = (0..100).filter(|k| k % 3 == 0).collect();
+    assert_eq!(keys_of(list.iter()), expected);
+    let reversed: Vec<i32> = expected.iter().rev().copied().collect();
+    assert_eq!(keys_of(list.iter().rev()), reversed);
+}
+
+// ============================================================================
+// Mutable iteration
+// ============================================================================
+
+#[test]
+fn iter_mut_updates_values() {
+    let mut list = skiplist_of(&[3, 1, 2]);
+    for mut item in list.iter_mut() {
+        item.value = format!("updated_{}", item.key);
+    }
+
+    assert_eq!(list.get(&1).unwrap().value, "updated_1");
+    assert_eq!(list.get(&3).unwrap().value, "updated_3");
+    expect_skiplist(&list, &[1, 2, 3]);
+}
+
+#[test]
+fn iter_mut_from_both_ends_while_holding_guards() {
+    let mut list = skiplist_of(&(0..50).collect::<Vec<_>>());
+    {
+        let mut iter = list.iter_mut();
+        let mut first = iter.next().unwrap();
+        let mut last = iter.next_back().unwrap();
+
+        // Walking the middle from the back must not disturb held guards
+        let middle: Vec<i32> = iter.by_ref().rev().map(|item| item.key).collect();
+        assert_eq!(middle, (1..49).rev().collect::<Vec<_>>());
+
+        first.value = "first".to_string();
+        last.value = "last".to_string();
+    }
+
+    assert_eq!(list.get(&0).unwrap().value, "first");
+    assert_eq!(list.get(&49).unwrap().value, "last");
+
+    // `&mut SkipList` works in for loops
+    for mut item in &mut list {
+        item.value.clear();
+    }
+    assert!(list.iter().all(|item| item.value.is_empty()));
+}
+
+#[test]
+#[should_panic(expected = "key of a skiplist entry was modified")]
+fn iter_mut_rejects_key_changes() {
+    let mut list = skiplist_of(&[1, 2, 3]);
+    for mut item in list.iter_mut() {
+        if item.key == 2 {
+            item.key = 10;
+        }
+    }
+}
+
+// ============================================================================
+// Owning iteration
+// ============================================================================
+
+#[test]
+fn into_iter_yields_owned_entries() {
+    let list = skiplist_of(&[2, 3, 1]);
+    let items: Vec<Box<TestItem>> = list.into_iter().collect();
+    let keys: Vec<i32> = items.iter().map(|item| *item.key()).collect();
+    assert_eq!(keys, vec![1, 2, 3]);
+}
+
+#[test]
+fn into_iter_double_ended() {
+    let list = skiplist_of(&[1, 2, 3, 4, 5]);
+    let mut iter = list.into_iter();
+    assert_eq!(iter.len(), 5);
+    assert_eq!(iter.next_back().unwrap().key, 5);
+    assert_eq!(iter.next().unwrap().key, 1);
+
+    let rest: Vec<i32> = iter.rev().map(|item| item.key).collect();
+    assert_eq!(rest, vec![4, 3, 2]);
+}
+
+#[test]
+fn into_iter_entries_can_be_reinserted() {
+    let source = skiplist_of(&[5, 4, 3]);
+    let mut target = SkipList::new();
+    for item in source.into_iter().rev() {
+        assert!(target.insert(item).is_ok());
+    }
+    expect_skiplist(&target, &[3, 4, 5]);
+}
+
+#[test]
+fn drain_double_ended() {
+    let mut list = skiplist_of(&[1, 2, 3, 4]);
+    let keys: Vec<i32> = list.drain().rev().map(|item| item.key).collect();
+    assert_eq!(keys, vec![4, 3, 2, 1]);
+    expect_skiplist(&list, &[]);
+}
@@ -0,0 +1,241 @@
+use skiplist::{SkipList, SkipListEntry, SkipListNode};
+use std::cmp::Ordering;
+use std::ops::Bound;
+
+/// Entry keyed by an owned `String`, to query with `&str`
+#[derive(Debug)]
+struct Named {
+    name: String,
+    skiplist_meta: SkipListNode,
+}
+
+impl SkipListEntry for Named {
+    type Key = String;
+
+    fn key(&self) -> &String {
+        &self.name
+    }
+
+    fn skiplist_node(&self) -> &SkipListNode {
+        &self.skiplist_meta
+    }
+
+    fn skiplist_node_mut(&mut self) -> &mut SkipListNode {
+        &mut self.skiplist_meta
+    }
+}
+
+fn named(name: &str) -> Box<Named> {
+    Box::new(Named {
+        name: name.to_string(),
+        skiplist_meta: SkipListNode::new(),
+    })
+}
+
+fn names_of<'a>(entries: impl Iterator<Item = &'a Named>) -> Vec<&'a str> {
+    entries.map(|entry| entry.name.as_str()).collect()
+}
+
+fn roster(names: &[&str]) -> SkipList<String, Named> {
+    let mut list = SkipList::new();
+    for name in names {
+        assert!(list.insert(named(name)).is_ok());
+    }
+    list
+}
+
+/// Name ordered by its ASCII-lowercase form, then by the original spelling
+#[derive(PartialEq, Eq)]
+struct CaseInsensitive(String);
+
+impl Ord for CaseInsensitive {
+    fn cmp(&self, other: &Self) -> Ordering {
+        fold(&self.0)
+            .cmp(fold(&other.0))
+            .then_with(|| self.0.cmp(&other.0))
+    }
+}
+
+impl PartialOrd for CaseInsensitive {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn fold(name: &str) -> impl Iterator<Item = u8> + '_ {
+    name.bytes().map(|byte| byte.to_ascii_lowercase())
+}
+
+struct Contact {
+    name: CaseInsensitive,
+    skiplist_meta: SkipListNode,
+}
+
+impl SkipListEntry for Contact {
+    type Key = CaseInsensitive;
+
+    fn key(&self) -> &CaseInsensitive {
+        &self.name
+    }
+
+    fn skiplist_node(&self) -> &SkipListNode {
+        &self.skiplist_meta
+    }
+
+    fn skiplist_node_mut(&mut self) -> &mut SkipListNode {
+        &mut self.skiplist_meta
+    }
+}
+
+fn contacts(names: &[&str]) -> SkipList<CaseInsensitive, Contact> {
+    let mut list = SkipList::new();
+    for name in names {
+        let contact = Box::new(Contact {
+            name: CaseInsensitive(name.to_string()),
+            skiplist_meta: SkipListNode::new(),
+        });
+        assert!(list.insert(contact).is_ok());
+    }
+    list
+}
+
+/// Orders a contact's name against `target` ignoring case
+fn ignoring_case(target: &str) -> impl Fn(&CaseInsensitive) -> Ordering + '_ {
+    move |key| fold(&key.0).cmp(fold(target))
+}
+
+// ============================================================================
+// Borrowed key forms
+// ============================================================================
+
+#[test]
+fn string_keys_are_found_by_str() {
+    let mut list = roster(&["carol", "alice", "bob"]);
+
+    assert_eq!(
+        list.get("bob").map(|entry| entry.name.as_str()),
+        Some("bob")
+    );
+    assert!(list.get("dave").is_none());
+    assert_eq!(list.rank("carol"), Some(2));
+
+    list.get_mut("alice").unwrap().name.make_ascii_lowercase();
+    assert!(list.remove_by_key("carol"));
+    assert_eq!(
+        list.remove("alice").map(|entry| entry.name),
+        Some("alice".to_string())
+    );
+    assert_eq!(names_of(list.iter()), vec!["bob"]);
+}
+
+#[test]
+fn ordered_lookups_accept_str() {
+    let list = roster(&["b", "d", "f"]);
+
+    assert_eq!(list.successor("b").map(|e| e.name.as_str()), Some("d"));
+    assert_eq!(list.predecessor("d").map(|e| e.name.as_str()), Some("b"));
+    assert_eq!(list.floor("e").map(|e| e.name.as_str()), Some("d"));
+    assert_eq!(list.ceiling("e").map(|e| e.name.as_str()), Some("f"));
+}
+
+#[test]
+fn ranges_accept_str_bounds() {
+    let mut list = roster(&["ant", "bee", "cat", "dog", "eel"]);
+
+    let middle = list.range::<str, _>((Bound::Included("b"), Bound::Excluded("d")));
+    assert_eq!(names_of(middle), vec!["bee", "cat"]);
+
+    for mut entry in list.range_mut::<str, _>((Bound::Excluded("cat"), Bound::Unbounded)) {
+        entry.name.make_ascii_lowercase();
+    }
+
+    let removed = list.remove_range::<str, _>((Bound::Unbounded, Bound::Included("bee")));
+    assert_eq!(
+        names_of(removed.iter().map(|entry| &**entry)),
+        vec!["ant", "bee"]
+    );
+    assert_eq!(names_of(list.iter()), vec!["cat", "dog", "eel"]);
+}
+
+#[test]
+fn multimap_lookups_accept_str() {
+    let mut list = SkipList::builder().allow_duplicates(true).build();
+    for name in ["x", "y", "x"] {
+        list.insert(named(name)).unwrap();
+    }
+
+    assert_eq!(list.count("x"), 2);
+    assert_eq!(names_of(list.get_all("x")), vec!["x", "x"]);
+    assert!(list.remove_one("y").is_some());
+    assert_eq!(list.remove_all("x").len(), 2);
+    assert!(list.is_empty());
+}
+
+// ============================================================================
+// search_by
+// ============================================================================
+
+#[test]
+fn search_by_matches_get_on_whole_keys() {
+    let list = roster(&["kiwi", "apple", "fig", "pear"]);
+
+    for name in ["apple", "fig", "kiwi", "pear", "banana", "zucchini", ""] {
+        let found = list.search_by(|key| key.as_str().cmp(name));
+        assert_eq!(
+            found.map(|entry| entry.name.as_str()),
+            list.get(name).map(|entry| entry.name.as_str())
+        );
+    }
+}
+
+#[test]
+fn search_by_ignores_case_through_projection() {
+    let list = contacts(&["bob", "Alice", "carol", "ALICE", "Dave"]);
+
+    let names: Vec<&str> = list.iter().map(|contact| contact.name.0.as_str()).collect();
+    assert_eq!(names, vec!["ALICE", "Alice", "bob", "carol", "Dave"]);
+
+    // The first of several spellings that match is returned
+    let alice = list.search_by(ignoring_case("aLiCe")).unwrap();
+    assert_eq!(alice.name.0, "ALICE");
+
+    let dave = list.search_by(ignoring_case("DAVE")).unwrap();
+    assert_eq!(dave.name.0, "Dave");
+
+    assert!(list.search_by(ignoring_case("eve")).is_none());
+    assert!(list.search_by(ignoring_case("aaron")).is_none());
+}
+
+#[test]
+fn search_by_on_prefix_projection() {
+    let list = roster(&["alpha", "beta", "bravo", "charlie", "delta"]);
+
+    // Compare only the first letter: every "b..." name counts as a match
+    let first_b = list.search_by(|key| key.as_bytes()[0].cmp(&b'b'));
+    assert_eq!(first_b.map(|entry| entry.name.as_str()), Some("beta"));
+
+    let first_e = list.search_by(|key| key.as_bytes()[0].cmp(&b'e'));
+    assert!(first_e.is_none());
+}
+
+#[test]
+fn search_by_on_large_list() {
+    let names: Vec<String> = (0..2000).map(|n| format!("{:05}", n * 3)).collect();
+    let mut list = SkipList::new();
+    for name in &names {
+        list.insert(named(name)).unwrap();
+    }
+
+    for n in 0..6000u32 {
+        let target = format!("{:05}", n);
+        let found = list.search_by(|key| key.as_str().cmp(&target));
+        if n.is_multiple_of(3) {
+            assert_eq!(found.map(|entry| &entry.name), Some(&target));
+        } else {
+            assert!(found.is_none());
+        }
+    }
+
+    let empty: SkipList<String, Named> = SkipList::new();
+    assert!(empty.search_by(|_| Ordering::Equal).is_none());
+}
@@ -150,8 +150,40 @@ pub fn verify_sorted_order(list: &SkipList<i32, TestItem>) {
     assert_eq!(count, list.len(), "iteration count doesn't match len()");
 }
 
+/// Deterministic xorshift generator so the tests need no extra dependencies
+pub struct Rng(pub u64);
+
+impl Rng {
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Returns a value in `0..bound`
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+
+    /// Flips a coin using the top bit, which unlike the low bits isn't tied
+    /// to the previous draw's
+    pub fn coin(&mut self) -> bool {
+        self.next() >> 63 == 0
+    }
+}
+
 // Test modules
 mod basic_operations;
+mod builder;
+mod concurrent;
 mod edge_cases;
+mod entry;
+mod indexing;
+mod iteration;
+mod lookup;
+mod multimap;
 mod navigation;
 mod ordering;
+mod ownership;
+mod range;
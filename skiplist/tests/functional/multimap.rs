@@ -0,0 +1,191 @@
+use crate::functional::{expect_skiplist, skiplist_of, Rng, TestItem};
+use skiplist::SkipList;
+use std::collections::{BTreeMap, VecDeque};
+
+fn multimap() -> SkipList<i32, TestItem> {
+    SkipList::builder().allow_duplicates(true).build()
+}
+
+/// Inserts `(key, value)` pairs, tagging each value so insertion order is visible
+fn insert_pairs(list: &mut SkipList<i32, TestItem>, pairs: &[(i32, &str)]) {
+    for &(key, value) in pairs {
+        let item = Box::new(TestItem::new(key, value.to_string()));
+        assert!(list.insert(item).is_ok());
+    }
+}
+
+fn values_of<'a>(items: impl Iterator<Item = &'a TestItem>) -> Vec<String> {
+    items.map(|item| item.value.clone()).collect()
+}
+
+// ============================================================================
+// Mode selection
+// ============================================================================
+
+#[test]
+fn unique_mode_still_rejects_duplicates() {
+    let mut list = skiplist_of(&[1, 2, 3]);
+    assert!(!list.allows_duplicates());
+    assert!(list.insert(Box::new(TestItem::with_key(2))).is_err());
+    assert_eq!(list.count(&2), 1);
+    assert_eq!(list.remove_all(&2).len(), 1);
+    expect_skiplist(&list, &[1, 3]);
+}
+
+#[test]
+fn multimap_accepts_equal_keys() {
+    let mut list = multimap();
+    assert!(list.allows_duplicates());
+    insert_pairs(&mut list, &[(5, "a"), (5, "b"), (3, "c"), (5, "d")]);
+
+    assert_eq!(list.len(), 4);
+    assert_eq!(list.count(&5), 3);
+    assert_eq!(list.count(&3), 1);
+    assert_eq!(list.count(&4), 0);
+}
+
+// ============================================================================
+// Insertion order among equal keys
+// ============================================================================
+
+#[test]
+fn equal_keys_keep_insertion_order() {
+    let mut list = multimap();
+    insert_pairs(
+        &mut list,
+        &[(2, "a"), (1, "b"), (2, "c"), (3, "d"), (2, "e"), (1, "f")],
+    );
+
+    assert_eq!(values_of(list.iter()), vec!["b", "f", "a", "c", "e", "d"]);
+    assert_eq!(values_of(list.get_all(&2)), vec!["a", "c", "e"]);
+    assert_eq!(values_of(list.get_all(&1)), vec!["b", "f"]);
+    assert_eq!(values_of(list.get_all(&7)), Vec::<String>::new());
+    assert_eq!(
+        values_of(list.iter().rev()),
+        vec!["d", "e", "c", "a", "f", "b"]
+    );
+}
+
+#[test]
+fn key_lookups_find_earliest_entry() {
+    let mut list = multimap();
+    insert_pairs(
+        &mut list,
+        &[(1, "x"), (2, "a"), (2, "b"), (2, "c"), (3, "y")],
+    );
+
+    assert_eq!(list.get(&2).unwrap().value, "a");
+    assert_eq!(list.rank(&2), Some(1));
+    assert_eq!(list.ceiling(&2).unwrap().value, "a");
+    assert_eq!(list.floor(&2).unwrap().value, "c");
+    assert_eq!(list.successor(&2).unwrap().value, "y");
+    assert_eq!(list.predecessor(&2).unwrap().value, "x");
+
+    list.get_mut(&2).unwrap().value = "first".to_string();
+    assert_eq!(values_of(list.get_all(&2)), vec!["first", "b", "c"]);
+}
+
+// ============================================================================
+// Removal
+// ============================================================================
+
+#[test]
+fn remove_one_pops_in_insertion_order() {
+    let mut list = multimap();
+    insert_pairs(&mut list, &[(7, "a"), (7, "b"), (8, "z"), (7, "c")]);
+
+    let popped: Vec<String> = std::iter::from_fn(|| list.remove_one(&7))
+        .map(|item| item.value)
+        .collect();
+    assert_eq!(popped, vec!["a", "b", "c"]);
+    assert_eq!(list.count(&7), 0);
+    assert_eq!(values_of(list.iter()), vec!["z"]);
+}
+
+#[test]
+fn remove_all_returns_every_match() {
+    let mut list = multimap();
+    insert_pairs(
+        &mut list,
+        &[(1, "a"), (2, "b"), (2, "c"), (3, "d"), (2, "e")],
+    );
+
+    let removed: Vec<String> = list
+        .remove_all(&2)
+        .into_iter()
+        .map(|item| item.value)
+        .collect();
+    assert_eq!(removed, vec!["b", "c", "e"]);
+    assert!(list.remove_all(&2).is_empty());
+    assert_eq!(values_of(list.iter()), vec!["a", "d"]);
+    assert_eq!(list.rank(&3), Some(1));
+}
+
+#[test]
+fn many_equal_keys_span_levels() {
+    let mut list = multimap();
+    for i in 0..1000 {
+        let item = Box::new(TestItem::new(i % 3, i.to_string()));
+        assert!(list.insert(item).is_ok());
+    }
+
+    assert_eq!(list.count(&0), 334);
+    assert_eq!(list.count(&1), 333);
+    assert_eq!(list.rank(&1), Some(334));
+    assert_eq!(list.get_by_index(334).unwrap().value, "1");
+
+    let ones: Vec<String> = (0..1000)
+        .filter(|i| i % 3 == 1)
+        .map(|i: i32| i.to_string())
+        .collect();
+    assert_eq!(values_of(list.get_all(&1)), ones);
+    assert_eq!(list.remove_all(&1).len(), 333);
+    assert_eq!(list.len(), 667);
+    assert_eq!(list.get(&2).unwrap().value, "2");
+}
+
+#[test]
+fn random_operations_match_reference() {
+    let mut rng = Rng(0xE7E_4715);
+    let mut list = multimap();
+    let mut reference: BTreeMap<i32, VecDeque<String>> = BTreeMap::new();
+
+    for step in 0..5000 {
+        let key = rng.below(50) as i32;
+        match rng.below(10) {
+            0..=5 => {
+                let value = step.to_string();
+                let item = Box::new(TestItem::new(key, value.clone()));
+                assert!(list.insert(item).is_ok());
+                reference.entry(key).or_default().push_back(value);
+            }
+            6..=8 => {
+                let removed = list.remove_one(&key).map(|item| item.value);
+                let expected = reference
+                    .get_mut(&key)
+                    .and_then(|values| values.pop_front());
+                assert_eq!(removed, expected);
+            }
+            _ => {
+                let removed: Vec<String> = list
+                    .remove_all(&key)
+                    .into_iter()
+                    .map(|item| item.value)
+                    .collect();
+                let expected: Vec<String> =
+                    reference.remove(&key).map(Vec::from).unwrap_or_default();
+                assert_eq!(removed, expected);
+            }
+        }
+
+        let expected = reference.get(&key).map_or(0, |values| values.len());
+        assert_eq!(list.count(&key), expected);
+    }
+
+    let expected: Vec<String> = reference.values().flatten().cloned().collect();
+    assert_eq!(values_of(list.iter()), expected);
+    for (key, values) in &reference {
+        let values: Vec<String> = values.iter().cloned().collect();
+        assert_eq!(values_of(list.get_all(key)), values);
+    }
+}
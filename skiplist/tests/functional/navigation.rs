@@ -147,3 +147,106 @@ fn first_and_successor_on_single_element() {
         panic!("Expected first element");
     }
 }
+
+fn key_of(item: Option<&TestItem>) -> Option<i32> {
+    item.map(|item| *item.key())
+}
+
+#[test]
+fn last_returns_largest_key() {
+    let list = skiplist_of(&[10, 5, 20, 1, 15]);
+    assert_eq!(key_of(list.last()), Some(20));
+
+    let empty: SkipList<i32, TestItem> = SkipList::new();
+    assert!(empty.last().is_none());
+}
+
+#[test]
+fn last_after_removing_last() {
+    let mut list = skiplist_of(&(0..1000).collect::<Vec<_>>());
+    for expected in (900..1000).rev() {
+        assert_eq!(key_of(list.last()), Some(expected));
+        list.remove_by_key(&expected);
+    }
+    assert_eq!(key_of(list.last()), Some(899));
+}
+
+#[test]
+fn predecessor_returns_previous_element() {
+    let list = skiplist_of(&[1, 5, 10, 15, 20]);
+
+    assert_eq!(key_of(list.predecessor(&10)), Some(5));
+    assert_eq!(key_of(list.predecessor(&12)), Some(10));
+    assert_eq!(key_of(list.predecessor(&100)), Some(20));
+    assert!(list.predecessor(&1).is_none());
+    assert!(list.predecessor(&-5).is_none());
+}
+
+#[test]
+fn floor_and_ceiling_on_present_and_missing_keys() {
+    let list = skiplist_of(&[1, 5, 10, 15, 20]);
+
+    assert_eq!(key_of(list.floor(&10)), Some(10));
+    assert_eq!(key_of(list.floor(&14)), Some(10));
+    assert_eq!(key_of(list.floor(&25)), Some(20));
+    assert!(list.floor(&0).is_none());
+
+    assert_eq!(key_of(list.ceiling(&10)), Some(10));
+    assert_eq!(key_of(list.ceiling(&11)), Some(15));
+    assert_eq!(key_of(list.ceiling(&-3)), Some(1));
+    assert!(list.ceiling(&21).is_none());
+}
+
+#[test]
+fn iterate_backwards_using_predecessor() {
+    let keys: Vec<i32> = (0..300).map(|k| k * 3).collect();
+    let list = skiplist_of(&keys);
+
+    let mut visited = Vec::new();
+    let mut current = list.last();
+    while let Some(item) = current {
+        visited.push(*item.key());
+        current = list.predecessor(item.key());
+    }
+
+    let expected: Vec<i32> = keys.iter().rev().copied().collect();
+    assert_eq!(visited, expected);
+}
+
+#[test]
+fn lookups_match_btreeset() {
+    use std::collections::BTreeSet;
+
+    let keys: Vec<i32> = (0..500).map(|k| (k * 7919) % 2000).collect();
+    let list = skiplist_of(&keys);
+    let reference: BTreeSet<i32> = keys.iter().copied().collect();
+
+    for key in -5..2005 {
+        assert_eq!(
+            key_of(list.floor(&key)),
+            reference.range(..=key).next_back().copied()
+        );
+        assert_eq!(
+            key_of(list.predecessor(&key)),
+            reference.range(..key).next_back().copied()
+        );
+        assert_eq!(
+            key_of(list.ceiling(&key)),
+            reference.range(key..).next().copied()
+        );
+        assert_eq!(
+            key_of(list.successor(&key)),
+            reference.range(key + 1..).next().copied()
+        );
+    }
+    assert_eq!(key_of(list.last()), reference.last().copied());
+}
+
+#[test]
+fn all_lookups_on_empty_list_return_none() {
+    let list: SkipList<i32, TestItem> = SkipList::new();
+    assert!(list.last().is_none());
+    assert!(list.predecessor(&0).is_none());
+    assert!(list.floor(&0).is_none());
+    assert!(list.ceiling(&0).is_none());
+}
@@ -0,0 +1,173 @@
+use crate::functional::{expect_skiplist, skiplist_of, TestItem};
+use skiplist::{SkipList, SkipListEntry, SkipListNode};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Entry that records its key in a shared log when dropped
+struct Tracked {
+    key: i32,
+    drops: Rc<RefCell<Vec<i32>>>,
+    skiplist_meta: SkipListNode,
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.drops.borrow_mut().push(self.key);
+    }
+}
+
+impl SkipListEntry for Tracked {
+    type Key = i32;
+
+    fn key(&self) -> &Self::Key {
+        &self.key
+    }
+
+    fn skiplist_node(&self) -> &SkipListNode {
+        &self.skiplist_meta
+    }
+
+    fn skiplist_node_mut(&mut self) -> &mut SkipListNode {
+        &mut self.skiplist_meta
+    }
+}
+
+/// Builds a skiplist of tracked entries sharing one drop log
+fn tracked_list(keys: &[i32]) -> (SkipList<i32, Tracked>, Rc<RefCell<Vec<i32>>>) {
+    let drops = Rc::new(RefCell::new(Vec::new()));
+    let mut list = SkipList::new();
+    for &key in keys {
+        let entry = Box::new(Tracked {
+            key,
+            drops: Rc::clone(&drops),
+            skiplist_meta: SkipListNode::new(),
+        });
+        assert!(list.insert(entry).is_ok());
+    }
+    (list, drops)
+}
+
+#[test]
+fn drop_frees_every_entry_in_key_order() {
+    let (list, drops) = tracked_list(&[5, 1, 9, 3, 7]);
+    assert!(drops.borrow().is_empty());
+
+    drop(list);
+    assert_eq!(*drops.borrow(), vec![1, 3, 5, 7, 9]);
+}
+
+#[test]
+fn drop_large_list() {
+    let keys: Vec<i32> = (0..10_000).rev().collect();
+    let (list, drops) = tracked_list(&keys);
+    drop(list);
+
+    let expected: Vec<i32> = (0..10_000).collect();
+    assert_eq!(*drops.borrow(), expected);
+}
+
+#[test]
+fn drop_skips_entries_already_removed() {
+    let (mut list, drops) = tracked_list(&[1, 2, 3]);
+    let removed = list.remove(&2).unwrap();
+    drop(list);
+    assert_eq!(*drops.borrow(), vec![1, 3]);
+
+    // The removed entry is owned by the caller and dropped exactly once
+    drop(removed);
+    assert_eq!(*drops.borrow(), vec![1, 3, 2]);
+}
+
+#[test]
+fn clear_drops_entries_and_allows_reuse() {
+    let (mut list, drops) = tracked_list(&[4, 2, 6]);
+    list.clear();
+
+    assert_eq!(*drops.borrow(), vec![2, 4, 6]);
+    assert!(list.is_empty());
+    assert_eq!(list.len(), 0);
+    assert!(list.first().is_none());
+    assert!(list.get(&4).is_none());
+
+    let entry = Box::new(Tracked {
+        key: 10,
+        drops: Rc::clone(&drops),
+        skiplist_meta: SkipListNode::new(),
+    });
+    assert!(list.insert(entry).is_ok());
+    assert_eq!(list.len(), 1);
+    assert_eq!(*list.first().unwrap().key(), 10);
+}
+
+#[test]
+fn clear_empty_list() {
+    let mut list: SkipList<i32, TestItem> = SkipList::new();
+    list.clear();
+    assert!(list.is_empty());
+}
+
+#[test]
+fn drain_returns_entries_in_key_order() {
+    let mut list = skiplist_of(&[30, 10, 50, 20, 40]);
+    let drain = list.drain();
+    assert_eq!(drain.len(), 5);
+
+    let keys: Vec<i32> = drain.map(|item| item.key).collect();
+    assert_eq!(keys, vec![10, 20, 30, 40, 50]);
+    expect_skiplist(&list, &[]);
+}
+
+#[test]
+fn drain_partially_consumed_drops_the_rest() {
+    let (mut list, drops) = tracked_list(&[1, 2, 3, 4, 5]);
+    {
+        let mut drain = list.drain();
+        let first = drain.next().unwrap();
+        assert_eq!(first.key, 1);
+        assert_eq!(drain.size_hint(), (4, Some(4)));
+        drop(first);
+    }
+
+    assert_eq!(*drops.borrow(), vec![1, 2, 3, 4, 5]);
+    assert!(list.is_empty());
+}
+
+#[test]
+fn drained_entries_can_be_reinserted() {
+    let mut source = skiplist_of(&[3, 1, 2]);
+    let mut target = SkipList::new();
+
+    for item in source.drain() {
+        assert!(target.insert(item).is_ok());
+    }
+
+    expect_skiplist(&source, &[]);
+    expect_skiplist(&target, &[1, 2, 3]);
+
+    // The source is empty but still usable
+    source.insert(Box::new(TestItem::with_key(7))).unwrap();
+    expect_skiplist(&source, &[7]);
+}
+
+#[test]
+fn leaked_drain_leaves_list_empty() {
+    let mut list = skiplist_of(&[1, 2, 3]);
+    std::mem::forget(list.drain());
+
+    expect_skiplist(&list, &[]);
+    list.insert(Box::new(TestItem::with_key(5))).unwrap();
+    expect_skiplist(&list, &[5]);
+}
+
+#[test]
+fn into_iter_partially_consumed_drops_the_rest() {
+    let (list, drops) = tracked_list(&[1, 2, 3, 4, 5]);
+    let mut iter = list.into_iter();
+    let first = iter.next().unwrap();
+    let last = iter.next_back().unwrap();
+    drop(iter);
+
+    assert_eq!(*drops.borrow(), vec![2, 3, 4]);
+    drop((first, last));
+    assert_eq!(*drops.borrow(), vec![2, 3, 4, 1, 5]);
+}
@@ -0,0 +1,269 @@
+use crate::functional::{expect_skiplist, skiplist_of, Rng, TestItem};
+use skiplist::SkipList;
+use std::collections::BTreeSet;
+use std::ops::Bound;
+
+/// A random bound on a key below `max`
+fn random_bound(rng: &mut Rng, max: i32) -> Bound<i32> {
+    let key = (rng.next() % max as u64) as i32;
+    match rng.next() % 3 {
+        0 => Bound::Included(key),
+        1 => Bound::Excluded(key),
+        _ => Bound::Unbounded,
+    }
+}
+
+fn keys_of<'a>(items: impl Iterator<Item = &'a TestItem>) -> Vec<i32> {
+    items.map(|item| item.key).collect()
+}
+
+fn evens() -> SkipList<i32, TestItem> {
+    skiplist_of(&(0..50).map(|k| k * 2).collect::<Vec<_>>())
+}
+
+// ============================================================================
+// range
+// ============================================================================
+
+#[test]
+fn range_all_bound_kinds() {
+    let list = skiplist_of(&[10, 20, 30, 40, 50]);
+
+    assert_eq!(keys_of(list.range(20..40)), vec![20, 30]);
+    assert_eq!(keys_of(list.range(20..=40)), vec![20, 30, 40]);
+    assert_eq!(keys_of(list.range(15..45)), vec![20, 30, 40]);
+    assert_eq!(keys_of(list.range(..30)), vec![10, 20]);
+    assert_eq!(keys_of(list.range(..=30)), vec![10, 20, 30]);
+    assert_eq!(keys_of(list.range(30..)), vec![30, 40, 50]);
+    assert_eq!(keys_of(list.range(..)), vec![10, 20, 30, 40, 50]);
+    assert_eq!(
+        keys_of(list.range((Bound::Excluded(20), Bound::Excluded(50)))),
+        vec![30, 40]
+    );
+    assert_eq!(
+        keys_of(list.range((Bound::Excluded(20), Bound::Unbounded))),
+        vec![30, 40, 50]
+    );
+}
+
+#[test]
+fn range_empty_results() {
+    let list = skiplist_of(&[10, 20, 30]);
+
+    assert_eq!(keys_of(list.range(11..20)), vec![]);
+    assert_eq!(keys_of(list.range(31..)), vec![]);
+    assert_eq!(keys_of(list.range(..10)), vec![]);
+    assert_eq!(keys_of(list.range(20..20)), vec![]);
+    assert_eq!(
+        keys_of(list.range((Bound::Excluded(20), Bound::Excluded(20)))),
+        vec![]
+    );
+
+    // A start past the end yields nothing rather than panicking
+    #[allow(clippy::reversed_empty_ranges)]
+    let reversed = list.range(30..10);
+    assert_eq!(keys_of(reversed), vec![]);
+
+    let empty: SkipList<i32, TestItem> = SkipList::new();
+    assert_eq!(keys_of(empty.range(..)), vec![]);
+}
+
+#[test]
+fn range_is_fused_and_cloneable() {
+    let list = evens();
+    let mut range = list.range(10..16);
+    assert_eq!(range.next().unwrap().key, 10);
+
+    let copy = range.clone();
+    assert_eq!(keys_of(range.by_ref()), vec![12, 14]);
+    assert!(range.next().is_none());
+    assert!(range.next().is_none());
+    assert_eq!(keys_of(copy), vec![12, 14]);
+}
+
+#[test]
+fn range_with_borrowed_bounds() {
+    let list = evens();
+    let (low, high) = (30, 36);
+    let keys: Vec<i32> = list
+        .range((Bound::Included(low), Bound::Included(high)))
+        .map(|item| item.key)
+        .collect();
+    assert_eq!(keys, vec![30, 32, 34, 36]);
+}
+
+#[test]
+fn range_random_bounds_match_btreeset() {
+    let mut rng = Rng(0x0123_4567);
+    let mut list = SkipList::new();
+    let mut reference = BTreeSet::new();
+    while reference.len() < 1000 {
+        let key = (rng.next() % 5000) as i32;
+        if list.insert(Box::new(TestItem::with_key(key))).is_ok() {
+            reference.insert(key);
+        }
+    }
+
+    for _ in 0..500 {
+        let bounds = (random_bound(&mut rng, 5200), random_bound(&mut rng, 5200));
+        let expected: Vec<i32> = match bounds {
+            // BTreeSet panics on these; the skiplist yields nothing
+            (Bound::Included(s) | Bound::Excluded(s), Bound::Included(e) | Bound::Excluded(e))
+                if s > e =>
+            {
+                vec![]
+            }
+            (Bound::Excluded(s), Bound::Excluded(e)) if s == e => vec![],
+            _ => reference.range(bounds).copied().collect(),
+        };
+        assert_eq!(keys_of(list.range(bounds)), expected, "{:?}", bounds);
+    }
+}
+
+// ============================================================================
+// range_mut
+// ============================================================================
+
+#[test]
+fn range_mut_updates_only_the_range() {
+    let mut list = skiplist_of(&[1, 2, 3, 4, 5, 6]);
+    for mut item in list.range_mut(2..=4) {
+        item.value = "touched".to_string();
+    }
+
+    let touched: Vec<i32> = list
+        .iter()
+        .filter(|item| item.value == "touched")
+        .map(|item| item.key)
+        .collect();
+    assert_eq!(touched, vec![2, 3, 4]);
+    expect_skiplist(&list, &[1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn range_mut_guards_can_be_held() {
+    let mut list = evens();
+    {
+        let mut guards: Vec<_> = list.range_mut(20..30).collect();
+        assert_eq!(guards.len(), 5);
+        for guard in guards.iter_mut() {
+            guard.value.push('!');
+        }
+    }
+    assert_eq!(list.get(&28).unwrap().value, "value_28!");
+    assert_eq!(list.get(&30).unwrap().value, "value_30");
+}
+
+#[test]
+#[should_panic(expected = "key of a skiplist entry was modified")]
+fn range_mut_rejects_key_changes() {
+    let mut list = skiplist_of(&[1, 2, 3]);
+    for mut item in list.range_mut(2..) {
+        item.key += 10;
+    }
+}
+
+// ============================================================================
+// remove_range
+// ============================================================================
+
+#[test]
+fn remove_range_returns_span_in_order() {
+    let mut list = skiplist_of(&(1..=10).collect::<Vec<_>>());
+    let removed = list.remove_range(3..8);
+
+    let keys: Vec<i32> = removed.iter().map(|item| item.key).collect();
+    assert_eq!(keys, vec![3, 4, 5, 6, 7]);
+    expect_skiplist(&list, &[1, 2, 8, 9, 10]);
+    assert_eq!(keys_of(list.iter().rev()), vec![10, 9, 8, 2, 1]);
+}
+
+#[test]
+fn remove_range_edges_and_empty_spans() {
+    let mut list = evens();
+
+    assert!(list.remove_range(11..12).is_empty());
+    assert!(list.remove_range(200..).is_empty());
+    assert_eq!(list.len(), 50);
+
+    let head: Vec<i32> = list.remove_range(..6).iter().map(|i| i.key).collect();
+    assert_eq!(head, vec![0, 2, 4]);
+
+    let tail: Vec<i32> = list.remove_range(90..).iter().map(|i| i.key).collect();
+    assert_eq!(tail, vec![90, 92, 94, 96, 98]);
+
+    let inner: Vec<i32> = list
+        .remove_range((Bound::Excluded(40), Bound::Included(50)))
+        .iter()
+        .map(|i| i.key)
+        .collect();
+    assert_eq!(inner, vec![42, 44, 46, 48, 50]);
+
+    let expected: Vec<i32> = (3..45)
+        .map(|k| k * 2)
+        .filter(|k| !(42..=50).contains(k))
+        .collect();
+    expect_skiplist(&list, &expected);
+}
+
+#[test]
+fn remove_range_everything_then_reuse() {
+    let mut list = evens();
+    assert_eq!(list.remove_range(..).len(), 50);
+    expect_skiplist(&list, &[]);
+    assert!(list.first().is_none());
+
+    list.insert(Box::new(TestItem::with_key(7))).unwrap();
+    list.insert(Box::new(TestItem::with_key(3))).unwrap();
+    expect_skiplist(&list, &[3, 7]);
+}
+
+#[test]
+fn removed_entries_can_be_reinserted() {
+    let mut source = skiplist_of(&[1, 2, 3, 4, 5]);
+    let mut target = SkipList::new();
+    for item in source.remove_range(2..=4) {
+        assert!(target.insert(item).is_ok());
+    }
+
+    expect_skiplist(&source, &[1, 5]);
+    expect_skiplist(&target, &[2, 3, 4]);
+}
+
+#[test]
+fn remove_range_random_matches_btreeset() {
+    let mut rng = Rng(0xFEED_BEEF);
+    let mut list = SkipList::new();
+    let mut reference = BTreeSet::new();
+
+    for round in 0..200 {
+        // Keep the list populated so spans cross several express lanes
+        for _ in 0..20 {
+            let key = (rng.next() % 3000) as i32;
+            if list.insert(Box::new(TestItem::with_key(key))).is_ok() {
+                reference.insert(key);
+            }
+        }
+
+        let start = (rng.next() % 3000) as i32;
+        let end = start + (rng.next() % 200) as i32;
+        let removed: Vec<i32> = list
+            .remove_range(start..end)
+            .iter()
+            .map(|item| item.key)
+            .collect();
+        let expected: Vec<i32> = reference.range(start..end).copied().collect();
+        assert_eq!(removed, expected, "round {}", round);
+        for key in &expected {
+            reference.remove(key);
+        }
+
+        assert_eq!(list.len(), reference.len());
+        if round % 20 == 0 {
+            let keys: Vec<i32> = reference.iter().copied().collect();
+            expect_skiplist(&list, &keys);
+            let reversed: Vec<i32> = keys.iter().rev().copied().collect();
+            assert_eq!(keys_of(list.iter().rev()), reversed);
+        }
+    }
+}